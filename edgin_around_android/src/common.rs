@@ -1,13 +1,25 @@
 use jni::{
-    objects::{JObject, JString},
-    sys::{jlong, jlongArray},
+    objects::{JObject, JString, ReleaseMode},
+    sys::{jfloat, jlong, jlongArray, jobjectArray},
     JNIEnv,
 };
 
-use crate::errors as err;
+use edgin_around_rendering::{
+    game::{Actor, Transition, TransitionKind},
+    renderers::{Highlight, HighlightStyle},
+    utils::{coordinates::Point, ids::ActorId},
+};
+
+use crate::{consts, errors as err};
 
 pub type ActorIdJni = jlong;
 pub type ActorIdArrayJni = jlongArray;
+pub type DecalIdJni = jlong;
+pub type DecalIdArrayJni = jlongArray;
+pub type EmitterIdJni = jlong;
+pub type EmitterIdArrayJni = jlongArray;
+pub type PathIdJni = jlong;
+pub type PathIdArrayJni = jlongArray;
 
 pub const HOLDER_FIELD_NAME: &str = "nativePtrHolder";
 
@@ -17,6 +29,63 @@ pub fn make_string(env: &JNIEnv, recipient: JString) -> Option<String> {
     env.get_string(recipient).ok().map(|s| s.into())
 }
 
+pub fn get_actors(env: &JNIEnv, actors_array: jobjectArray) -> Vec<Actor> {
+    let mut actors_vec = Vec::<Actor>::new();
+    for i in 0..env.get_array_length(actors_array).expect(err::JNI_ARRAY_LENGHT) {
+        let element = env.get_object_array_element(actors_array, i).expect(err::JNI_ARRAY_ELEMENT);
+        let actor = get_holder::<Actor>(env, &element);
+        actors_vec.push(actor.clone())
+    }
+    actors_vec
+}
+
+pub fn get_points(env: &JNIEnv, points_array: jobjectArray) -> Vec<Point> {
+    let mut points_vec = Vec::<Point>::new();
+    for i in 0..env.get_array_length(points_array).expect(err::JNI_ARRAY_LENGHT) {
+        let element = env.get_object_array_element(points_array, i).expect(err::JNI_ARRAY_ELEMENT);
+        let point = get_holder::<Point>(env, &element);
+        points_vec.push(point.clone())
+    }
+    points_vec
+}
+
+pub fn get_actor_ids(env: &JNIEnv, actor_ids_array: ActorIdArrayJni) -> Vec<ActorId> {
+    let actor_ids_data = env
+        .get_array_elements::<ActorIdJni>(actor_ids_array, ReleaseMode::NoCopyBack)
+        .expect(err::JNI_ARRAY_ELEMENTS);
+    let length = actor_ids_data.size().expect(err::JNI_ARRAY_LENGHT) as usize;
+    let data = unsafe { std::slice::from_raw_parts(actor_ids_data.as_ptr(), length) };
+    data.iter().map(|id| *id as ActorId).collect()
+}
+
+/// Makes a transition of the kind with the given name. Throws `IllegalArgumentException` and
+/// returns `None` if the kind is not known.
+pub fn make_transition(env: &JNIEnv, kind: JString, duration: jfloat) -> Option<Transition> {
+    let kind = make_string(env, kind).expect(err::JNI_MAKE_STRING);
+    match TransitionKind::from_name(&kind) {
+        Some(kind) => Some(Transition::new(kind, duration)),
+        None => {
+            let message = format!("Unknown transition '{}'", kind);
+            env.throw_new(consts::CLASS_ILLEGAL_ARGUMENT_EXCEPTION, message).expect(err::JNI_THROW);
+            None
+        }
+    }
+}
+
+/// Makes a highlight of the style with the given name. Throws `IllegalArgumentException` and
+/// returns `None` if the style is not known.
+pub fn make_highlight(env: &JNIEnv, style: JString, color: [jfloat; 4]) -> Option<Highlight> {
+    let style = make_string(env, style).expect(err::JNI_MAKE_STRING);
+    match HighlightStyle::from_name(&style) {
+        Some(style) => Some(Highlight::new(style, color)),
+        None => {
+            let message = format!("Unknown highlight style '{}'", style);
+            env.throw_new(consts::CLASS_ILLEGAL_ARGUMENT_EXCEPTION, message).expect(err::JNI_THROW);
+            None
+        }
+    }
+}
+
 pub fn initialize_once() {
     INIT.call_once(|| {
         // Configure logger
@@ -1,2 +1,4 @@
 pub const CLASS_POINT: &str = "com/edgin/around/rendering/PointBridge";
 pub const METHOD_POINT_CONSTRUCTOR_SIGNATURE: &str = "()V";
+pub const CLASS_IO_EXCEPTION: &str = "java/io/IOException";
+pub const CLASS_ILLEGAL_ARGUMENT_EXCEPTION: &str = "java/lang/IllegalArgumentException";
@@ -0,0 +1,134 @@
+use jni::{
+    objects::{JObject, JString},
+    sys::{jfloat, jobjectArray},
+    JNIEnv,
+};
+
+use edgin_around_rendering::{
+    renderers::{Decal, DecalAnimation, DecalAnimationKind, DecalShape},
+    utils::{coordinates::Point, errors as rendering_err},
+};
+
+use crate::{common, consts, errors as err};
+
+/// Copies the point out of the bridge object, so that the same object can be passed many times.
+unsafe fn get_point(env: &JNIEnv, object: &JObject) -> Point {
+    common::get_holder::<Point>(env, object).clone()
+}
+
+/// Creates the decal or throws if the shape is invalid.
+unsafe fn initialize(env: &JNIEnv, object: &JObject, shape: DecalShape) {
+    if shape.is_valid() {
+        common::set_holder(env, object, Decal::new(shape));
+    } else {
+        env.throw_new(consts::CLASS_ILLEGAL_ARGUMENT_EXCEPTION, rendering_err::DECAL_INVALID_SHAPE)
+            .expect(err::JNI_THROW);
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_DecalBridge_initializeCircle(
+    env: JNIEnv,
+    object: JObject,
+    center: JObject,
+    radius: jfloat,
+) {
+    let center = get_point(&env, &center);
+    initialize(&env, &object, DecalShape::Circle { center, radius });
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_DecalBridge_initializeRing(
+    env: JNIEnv,
+    object: JObject,
+    center: JObject,
+    inner_radius: jfloat,
+    outer_radius: jfloat,
+) {
+    let center = get_point(&env, &center);
+    initialize(&env, &object, DecalShape::Ring { center, inner_radius, outer_radius });
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_DecalBridge_initializeSector(
+    env: JNIEnv,
+    object: JObject,
+    center: JObject,
+    radius: jfloat,
+    bearing: jfloat,
+    angle: jfloat,
+) {
+    let center = get_point(&env, &center);
+    initialize(&env, &object, DecalShape::Sector { center, radius, bearing, angle });
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_DecalBridge_initializePolygon(
+    env: JNIEnv,
+    object: JObject,
+    vertices_array: jobjectArray,
+) {
+    let vertices = common::get_points(&env, vertices_array);
+    initialize(&env, &object, DecalShape::Polygon { vertices });
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_DecalBridge_setColor(
+    env: JNIEnv,
+    object: JObject,
+    red: jfloat,
+    green: jfloat,
+    blue: jfloat,
+    alpha: jfloat,
+) {
+    let mut decal = common::get_holder::<Decal>(&env, &object);
+    decal.color = [red, green, blue, alpha];
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_DecalBridge_setTexture(
+    env: JNIEnv,
+    object: JObject,
+    texture: JString,
+) {
+    let texture = common::make_string(&env, texture).expect(err::JNI_MAKE_STRING);
+    let mut decal = common::get_holder::<Decal>(&env, &object);
+    decal.texture = Some(texture);
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_DecalBridge_clearTexture(
+    env: JNIEnv,
+    object: JObject,
+) {
+    let mut decal = common::get_holder::<Decal>(&env, &object);
+    decal.texture = None;
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_DecalBridge_setAnimation(
+    env: JNIEnv,
+    object: JObject,
+    kind: JString,
+    speed: jfloat,
+) {
+    let kind = common::make_string(&env, kind).expect(err::JNI_MAKE_STRING);
+    match DecalAnimationKind::from_name(&kind) {
+        Some(kind) => {
+            let mut decal = common::get_holder::<Decal>(&env, &object);
+            decal.animation = DecalAnimation::new(kind, speed);
+        }
+        None => {
+            let message = format!("Unknown decal animation '{}'", kind);
+            env.throw_new(consts::CLASS_ILLEGAL_ARGUMENT_EXCEPTION, message).expect(err::JNI_THROW);
+        }
+    }
+}
@@ -1,12 +1,15 @@
 use jni::{
     objects::{JObject, JString},
-    sys::jfloat,
+    sys::{jfloat, jint, jlong},
     JNIEnv,
 };
 
-use edgin_around_rendering::game::ElevationFunction;
+use edgin_around_rendering::{
+    game::ElevationFunction,
+    utils::{errors as rendering_err, noise::MAX_OCTAVES},
+};
 
-use crate::{common, errors as err};
+use crate::{common, consts, errors as err};
 
 #[no_mangle]
 #[allow(non_snake_case)]
@@ -32,3 +35,74 @@ pub unsafe extern "C" fn Java_com_edgin_around_rendering_ElevationBridge_addTerr
     let name = common::make_string(&env, name).expect(err::JNI_MAKE_STRING);
     elevation.add_terrain(&name, theta, phi)
 }
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_ElevationBridge_addLocalTerrain(
+    env: JNIEnv,
+    object: JObject,
+    name: JString,
+    theta: jfloat,
+    phi: jfloat,
+    range: jfloat,
+    amplitude: jfloat,
+    falloff: JString,
+) {
+    let mut elevation = common::get_holder::<ElevationFunction>(&env, &object);
+    let name = common::make_string(&env, name).expect(err::JNI_MAKE_STRING);
+    let falloff = common::make_string(&env, falloff).expect(err::JNI_MAKE_STRING);
+    elevation.add_local_terrain(&name, theta, phi, range, amplitude, &falloff)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_ElevationBridge_setSeaLevel(
+    env: JNIEnv,
+    object: JObject,
+    sea_level: jfloat,
+) {
+    let mut elevation = common::get_holder::<ElevationFunction>(&env, &object);
+    elevation.set_sea_level(sea_level)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_ElevationBridge_addNoiseTerrain(
+    env: JNIEnv,
+    object: JObject,
+    seed: jlong,
+    octaves: jint,
+    frequency: jfloat,
+    amplitude: jfloat,
+) {
+    if octaves < 0 || octaves as u32 > MAX_OCTAVES {
+        env.throw_new(
+            consts::CLASS_ILLEGAL_ARGUMENT_EXCEPTION,
+            rendering_err::NOISE_INVALID_OCTAVES,
+        )
+        .expect(err::JNI_THROW);
+        return;
+    }
+
+    let mut elevation = common::get_holder::<ElevationFunction>(&env, &object);
+    elevation.add_noise_terrain(seed as u64, octaves as u32, frequency, amplitude)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_ElevationBridge_addHeightmapTerrain(
+    env: JNIEnv,
+    object: JObject,
+    path: JString,
+    min_height: jfloat,
+    max_height: jfloat,
+) {
+    let path = common::make_string(&env, path).expect(err::JNI_MAKE_STRING);
+    let result = {
+        let mut elevation = common::get_holder::<ElevationFunction>(&env, &object);
+        elevation.add_heightmap_terrain(std::path::Path::new(&path), min_height, max_height)
+    };
+    if let Err(message) = result {
+        env.throw_new(consts::CLASS_IO_EXCEPTION, message).expect(err::JNI_THROW);
+    }
+}
@@ -2,8 +2,10 @@ pub mod about;
 pub mod actor;
 mod common;
 mod consts;
+pub mod decal;
 pub mod elevation;
 mod errors;
+pub mod path;
 pub mod point;
 pub mod preview_expositor;
 pub mod scene;
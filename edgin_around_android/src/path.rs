@@ -0,0 +1,107 @@
+use jni::{
+    objects::{JObject, JString, ReleaseMode},
+    sys::{jfloat, jfloatArray, jobjectArray},
+    JNIEnv,
+};
+
+use edgin_around_rendering::renderers::{Dash, Path};
+
+use crate::{common, errors as err};
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_PathBridge_initialize(
+    env: JNIEnv,
+    object: JObject,
+    points_array: jobjectArray,
+    width: jfloat,
+) {
+    let points = common::get_points(&env, points_array);
+    common::set_holder(&env, &object, Path::new(points, width));
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_PathBridge_setColor(
+    env: JNIEnv,
+    object: JObject,
+    red: jfloat,
+    green: jfloat,
+    blue: jfloat,
+    alpha: jfloat,
+) {
+    let mut path = common::get_holder::<Path>(&env, &object);
+    path.colors = vec![[red, green, blue, alpha]];
+}
+
+/// Sets colors at the points of the path given as consecutive RGBA components.
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_PathBridge_setColors(
+    env: JNIEnv,
+    object: JObject,
+    colors_array: jfloatArray,
+) {
+    let colors_data = env
+        .get_array_elements::<jfloat>(colors_array, ReleaseMode::NoCopyBack)
+        .expect(err::JNI_ARRAY_ELEMENTS);
+    let length = colors_data.size().expect(err::JNI_ARRAY_LENGHT) as usize;
+    let data = std::slice::from_raw_parts(colors_data.as_ptr(), length);
+    let mut path = common::get_holder::<Path>(&env, &object);
+    path.colors = data.chunks_exact(4).map(|c| [c[0], c[1], c[2], c[3]]).collect();
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_PathBridge_setDash(
+    env: JNIEnv,
+    object: JObject,
+    length: jfloat,
+    gap: jfloat,
+) {
+    let mut path = common::get_holder::<Path>(&env, &object);
+    path.dash = Some(Dash::new(length, gap));
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_PathBridge_clearDash(
+    env: JNIEnv,
+    object: JObject,
+) {
+    let mut path = common::get_holder::<Path>(&env, &object);
+    path.dash = None;
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_PathBridge_setTexture(
+    env: JNIEnv,
+    object: JObject,
+    texture: JString,
+) {
+    let texture = common::make_string(&env, texture).expect(err::JNI_MAKE_STRING);
+    let mut path = common::get_holder::<Path>(&env, &object);
+    path.texture = Some(texture);
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_PathBridge_clearTexture(
+    env: JNIEnv,
+    object: JObject,
+) {
+    let mut path = common::get_holder::<Path>(&env, &object);
+    path.texture = None;
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_PathBridge_setScrollSpeed(
+    env: JNIEnv,
+    object: JObject,
+    scroll_speed: jfloat,
+) {
+    let mut path = common::get_holder::<Path>(&env, &object);
+    path.scroll_speed = scroll_speed;
+}
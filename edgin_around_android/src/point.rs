@@ -1,8 +1,27 @@
-use jni::{objects::JObject, sys::jfloat, JNIEnv};
+use jni::{
+    objects::{JClass, JObject},
+    sys::{jfloat, jobject, jobjectArray},
+    JNIEnv,
+};
 
 use edgin_around_rendering::utils::coordinates::Point;
 
-use crate::common;
+use crate::{common, consts, errors as err};
+
+/// Copies the point out of the bridge object, so that the same object can be passed many times.
+unsafe fn get_point(env: &JNIEnv, object: &JObject) -> Point {
+    common::get_holder::<Point>(env, object).clone()
+}
+
+/// Wraps the point in a new bridge object.
+unsafe fn new_point(env: &JNIEnv, point: Point) -> jobject {
+    let class = env.find_class(consts::CLASS_POINT).expect(err::JNI_CLASS_NOT_FOUND);
+    let object = env
+        .new_object(class, consts::METHOD_POINT_CONSTRUCTOR_SIGNATURE, &[])
+        .expect(err::JNI_NEW_OBJECT);
+    common::set_holder(env, &object, point);
+    object.into_inner()
+}
 
 #[no_mangle]
 #[allow(non_snake_case)]
@@ -35,3 +54,95 @@ pub unsafe extern "C" fn Java_com_edgin_around_rendering_PointBridge_getPhi(
     let point = common::get_holder::<Point>(&env, &object);
     point.phi
 }
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_PointBridge_movedBy(
+    env: JNIEnv,
+    object: JObject,
+    distance: jfloat,
+    bearing: jfloat,
+    radius: jfloat,
+) -> jobject {
+    let point = get_point(&env, &object);
+    new_point(&env, point.moved_by(distance, bearing, radius))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_PointBridge_greatCircleDistance(
+    env: JNIEnv,
+    object: JObject,
+    other: JObject,
+    radius: jfloat,
+) -> jfloat {
+    Point::great_circle_distance(&get_point(&env, &object), &get_point(&env, &other), radius)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_PointBridge_initialBearing(
+    env: JNIEnv,
+    object: JObject,
+    other: JObject,
+) -> jfloat {
+    Point::initial_bearing(&get_point(&env, &object), &get_point(&env, &other))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_PointBridge_finalBearing(
+    env: JNIEnv,
+    object: JObject,
+    other: JObject,
+) -> jfloat {
+    Point::final_bearing(&get_point(&env, &object), &get_point(&env, &other))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_PointBridge_interpolate(
+    env: JNIEnv,
+    object: JObject,
+    other: JObject,
+    fraction: jfloat,
+) -> jobject {
+    let point = Point::interpolate(&get_point(&env, &object), &get_point(&env, &other), fraction);
+    new_point(&env, point)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_PointBridge_midpoint(
+    env: JNIEnv,
+    object: JObject,
+    other: JObject,
+) -> jobject {
+    let point = Point::midpoint(&get_point(&env, &object), &get_point(&env, &other));
+    new_point(&env, point)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_PointBridge_crossTrackDistance(
+    env: JNIEnv,
+    object: JObject,
+    start: JObject,
+    end: JObject,
+    radius: jfloat,
+) -> jfloat {
+    let (start, end) = (get_point(&env, &start), get_point(&env, &end));
+    Point::cross_track_distance(&get_point(&env, &object), &start, &end, radius)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_PointBridge_polygonArea(
+    env: JNIEnv,
+    _class: JClass,
+    vertices_array: jobjectArray,
+    radius: jfloat,
+) -> jfloat {
+    let vertices = common::get_points(&env, vertices_array);
+    Point::polygon_area(&vertices, radius)
+}
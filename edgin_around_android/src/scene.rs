@@ -1,11 +1,11 @@
 use jni::{
-    objects::{JObject, ReleaseMode},
-    sys::{jfloat, jobject, jobjectArray},
+    objects::{JObject, JString},
+    sys::{jboolean, jdouble, jfloat, jint, jobject, jobjectArray, JNI_FALSE},
     JNIEnv,
 };
 
 use edgin_around_rendering::{
-    game::{Actor, ElevationFunction, Scene},
+    game::{ActorAppearance, ElevationFunction, MotionSmoothing, Scene},
     utils::{coordinates::Point, ids::ActorId},
 };
 
@@ -21,6 +21,42 @@ pub unsafe extern "C" fn Java_com_edgin_around_rendering_SceneBridge_initialize(
     common::set_holder(&env, &object, scene);
 }
 
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_SceneBridge_loadFrom(
+    env: JNIEnv,
+    object: JObject,
+    path: JString,
+) {
+    let path = common::make_string(&env, path).expect(err::JNI_MAKE_STRING);
+    match Scene::load_from(std::path::Path::new(&path)) {
+        Ok(loaded_scene) => {
+            let mut scene = common::get_holder::<Scene>(&env, &object);
+            *scene = loaded_scene;
+        }
+        Err(message) => {
+            env.throw_new(consts::CLASS_IO_EXCEPTION, message).expect(err::JNI_THROW);
+        }
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_SceneBridge_saveTo(
+    env: JNIEnv,
+    object: JObject,
+    path: JString,
+) {
+    let path = common::make_string(&env, path).expect(err::JNI_MAKE_STRING);
+    let result = {
+        let scene = common::get_holder::<Scene>(&env, &object);
+        scene.save_to(std::path::Path::new(&path))
+    };
+    if let Err(message) = result {
+        env.throw_new(consts::CLASS_IO_EXCEPTION, message).expect(err::JNI_THROW);
+    }
+}
+
 #[no_mangle]
 #[allow(non_snake_case)]
 pub unsafe extern "C" fn Java_com_edgin_around_rendering_SceneBridge_configure(
@@ -34,6 +70,20 @@ pub unsafe extern "C" fn Java_com_edgin_around_rendering_SceneBridge_configure(
     scene.configure(hero_actor_id as ActorId, elevation.clone())
 }
 
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_SceneBridge_reconfigure(
+    env: JNIEnv,
+    object: JObject,
+    hero_actor_id: common::ActorIdJni,
+    elevation_object: JObject,
+    reset_renderers: jboolean,
+) {
+    let mut scene = common::get_holder::<Scene>(&env, &object);
+    let elevation = common::get_holder::<ElevationFunction>(&env, &elevation_object);
+    scene.reconfigure(hero_actor_id as ActorId, elevation.clone(), reset_renderers != JNI_FALSE)
+}
+
 #[no_mangle]
 #[allow(non_snake_case)]
 pub unsafe extern "C" fn Java_com_edgin_around_rendering_SceneBridge_getHeroId(
@@ -51,14 +101,9 @@ pub unsafe extern "C" fn Java_com_edgin_around_rendering_SceneBridge_createActor
     object: JObject,
     actors_array: jobjectArray,
 ) {
+    let actors = common::get_actors(&env, actors_array);
     let mut scene = common::get_holder::<Scene>(&env, &object);
-    let mut actors_vec = Vec::<edgin_around_rendering::game::Actor>::new();
-    for i in 0..env.get_array_length(actors_array).expect(err::JNI_ARRAY_LENGHT) {
-        let element = env.get_object_array_element(actors_array, i).expect(err::JNI_ARRAY_ELEMENT);
-        let actor = common::get_holder::<Actor>(&env, &element);
-        actors_vec.push(actor.clone())
-    }
-    scene.create_actors(&actors_vec)
+    scene.create_actors(&actors)
 }
 
 #[no_mangle]
@@ -68,16 +113,9 @@ pub unsafe extern "C" fn Java_com_edgin_around_rendering_SceneBridge_deleteActor
     object: JObject,
     actor_ids_array: common::ActorIdArrayJni,
 ) {
+    let actor_ids = common::get_actor_ids(&env, actor_ids_array);
     let mut scene = common::get_holder::<Scene>(&env, &object);
-    let mut actor_ids_vec = Vec::<ActorId>::new();
-    let actor_ids_length = env.get_array_length(actor_ids_array).expect(err::JNI_ARRAY_LENGHT);
-    let actor_ids_data = env
-        .get_array_elements::<common::ActorIdJni>(actor_ids_array, ReleaseMode::NoCopyBack)
-        .expect(err::JNI_ARRAY_ELEMENTS);
-    for i in 0..actor_ids_length as isize {
-        actor_ids_vec.push(*actor_ids_data.as_ptr().offset(i) as ActorId)
-    }
-    scene.delete_actors(&actor_ids_vec)
+    scene.delete_actors(&actor_ids)
 }
 
 #[no_mangle]
@@ -87,16 +125,57 @@ pub unsafe extern "C" fn Java_com_edgin_around_rendering_SceneBridge_hideActors(
     object: JObject,
     actor_ids_array: common::ActorIdArrayJni,
 ) {
+    let actor_ids = common::get_actor_ids(&env, actor_ids_array);
     let mut scene = common::get_holder::<Scene>(&env, &object);
-    let mut actor_ids_vec = Vec::new();
-    let actor_ids_length = env.get_array_length(actor_ids_array).expect(err::JNI_ARRAY_ELEMENT);
-    let actor_ids_data = env
-        .get_array_elements::<common::ActorIdJni>(actor_ids_array, ReleaseMode::NoCopyBack)
-        .expect(err::JNI_ARRAY_ELEMENTS);
-    for i in 0..actor_ids_length as isize {
-        actor_ids_vec.push(*actor_ids_data.as_ptr().offset(i) as ActorId)
+    scene.hide_actors(&actor_ids)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_SceneBridge_createActorsWithTransition(
+    env: JNIEnv,
+    object: JObject,
+    actors_array: jobjectArray,
+    kind: JString,
+    duration: jfloat,
+) {
+    if let Some(transition) = common::make_transition(&env, kind, duration) {
+        let actors_vec = common::get_actors(&env, actors_array);
+        let mut scene = common::get_holder::<Scene>(&env, &object);
+        scene.create_actors_with_transition(&actors_vec, transition)
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_SceneBridge_deleteActorsWithTransition(
+    env: JNIEnv,
+    object: JObject,
+    actor_ids_array: common::ActorIdArrayJni,
+    kind: JString,
+    duration: jfloat,
+) {
+    if let Some(transition) = common::make_transition(&env, kind, duration) {
+        let actor_ids_vec = common::get_actor_ids(&env, actor_ids_array);
+        let mut scene = common::get_holder::<Scene>(&env, &object);
+        scene.delete_actors_with_transition(&actor_ids_vec, transition)
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_SceneBridge_hideActorsWithTransition(
+    env: JNIEnv,
+    object: JObject,
+    actor_ids_array: common::ActorIdArrayJni,
+    kind: JString,
+    duration: jfloat,
+) {
+    if let Some(transition) = common::make_transition(&env, kind, duration) {
+        let actor_ids_vec = common::get_actor_ids(&env, actor_ids_array);
+        let mut scene = common::get_holder::<Scene>(&env, &object);
+        scene.hide_actors_with_transition(&actor_ids_vec, transition)
     }
-    scene.hide_actors(&actor_ids_vec)
 }
 
 #[no_mangle]
@@ -130,6 +209,48 @@ pub unsafe extern "C" fn Java_com_edgin_around_rendering_SceneBridge_findClosest
     result
 }
 
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_SceneBridge_findNearestActors(
+    env: JNIEnv,
+    object: JObject,
+    theta: jfloat,
+    phi: jfloat,
+    count: jint,
+) -> common::ActorIdArrayJni {
+    let reference_point = Point::new(theta, phi);
+    let scene = common::get_holder::<Scene>(&env, &object);
+    let ids = scene
+        .find_nearest_actors(&reference_point, count.max(0) as usize)
+        .iter()
+        .map(|id| *id as common::ActorIdJni)
+        .collect::<Vec<common::ActorIdJni>>();
+    let result = env.new_long_array(ids.len() as i32).expect(err::JNI_NEW_ARRAY);
+    env.set_long_array_region(result, 0, ids.as_slice()).expect(err::JNI_ARRAY_REGION);
+    result
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_SceneBridge_findActorsInCap(
+    env: JNIEnv,
+    object: JObject,
+    theta: jfloat,
+    phi: jfloat,
+    distance: jfloat,
+) -> common::ActorIdArrayJni {
+    let center = Point::new(theta, phi);
+    let scene = common::get_holder::<Scene>(&env, &object);
+    let ids = scene
+        .find_actors_in_cap(&center, distance)
+        .iter()
+        .map(|id| *id as common::ActorIdJni)
+        .collect::<Vec<common::ActorIdJni>>();
+    let result = env.new_long_array(ids.len() as i32).expect(err::JNI_NEW_ARRAY);
+    env.set_long_array_region(result, 0, ids.as_slice()).expect(err::JNI_ARRAY_REGION);
+    result
+}
+
 #[no_mangle]
 #[allow(non_snake_case)]
 pub unsafe extern "C" fn Java_com_edgin_around_rendering_SceneBridge_getActorPosition<'a>(
@@ -158,6 +279,48 @@ pub unsafe extern "C" fn Java_com_edgin_around_rendering_SceneBridge_getActorPos
     return position.into_inner();
 }
 
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_SceneBridge_raiseTerrain(
+    env: JNIEnv,
+    object: JObject,
+    theta: jfloat,
+    phi: jfloat,
+    range: jfloat,
+    amount: jfloat,
+) {
+    let mut scene = common::get_holder::<Scene>(&env, &object);
+    scene.raise_terrain(&Point::new(theta, phi), range, amount)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_SceneBridge_lowerTerrain(
+    env: JNIEnv,
+    object: JObject,
+    theta: jfloat,
+    phi: jfloat,
+    range: jfloat,
+    amount: jfloat,
+) {
+    let mut scene = common::get_holder::<Scene>(&env, &object);
+    scene.lower_terrain(&Point::new(theta, phi), range, amount)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_SceneBridge_flattenTerrain(
+    env: JNIEnv,
+    object: JObject,
+    theta: jfloat,
+    phi: jfloat,
+    range: jfloat,
+    height: jfloat,
+) {
+    let mut scene = common::get_holder::<Scene>(&env, &object);
+    scene.flatten_terrain(&Point::new(theta, phi), range, height)
+}
+
 #[no_mangle]
 #[allow(non_snake_case)]
 pub unsafe extern "C" fn Java_com_edgin_around_rendering_SceneBridge_setActorPosition(
@@ -168,11 +331,198 @@ pub unsafe extern "C" fn Java_com_edgin_around_rendering_SceneBridge_setActorPos
     phi: jfloat,
 ) {
     let mut scene = common::get_holder::<Scene>(&env, &object);
-    if let Some(actor) = scene.get_actor_mut(actor_id as ActorId) {
-        actor.set_position(Point::new(theta, phi));
+    scene.set_actor_position(actor_id as ActorId, Point::new(theta, phi))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_SceneBridge_updateActorPosition(
+    env: JNIEnv,
+    object: JObject,
+    actor_id: common::ActorIdJni,
+    theta: jfloat,
+    phi: jfloat,
+    timestamp: jdouble,
+) {
+    let mut scene = common::get_holder::<Scene>(&env, &object);
+    scene.update_actor_position(actor_id as ActorId, Point::new(theta, phi), timestamp)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_SceneBridge_setTime(
+    env: JNIEnv,
+    object: JObject,
+    time: jdouble,
+) {
+    let mut scene = common::get_holder::<Scene>(&env, &object);
+    scene.set_time(time)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_SceneBridge_setMotionSmoothing(
+    env: JNIEnv,
+    object: JObject,
+    delay: jdouble,
+    max_extrapolation: jdouble,
+    snap_distance: jfloat,
+) {
+    let mut scene = common::get_holder::<Scene>(&env, &object);
+    scene.set_motion_smoothing(MotionSmoothing::new(delay, max_extrapolation, snap_distance))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_SceneBridge_getRenderedPosition(
+    env: JNIEnv,
+    object: JObject,
+    actor_id: common::ActorIdJni,
+) -> jobject {
+    let point = {
+        let scene = common::get_holder::<Scene>(&env, &object);
+        match scene.get_rendered_position(actor_id as ActorId) {
+            Some(point) => point,
+            None => return std::ptr::null_mut(),
+        }
+    };
+
+    let class = env.find_class(consts::CLASS_POINT).expect(err::JNI_CLASS_NOT_FOUND);
+    let position = env
+        .new_object(class, consts::METHOD_POINT_CONSTRUCTOR_SIGNATURE, &[])
+        .expect(err::JNI_NEW_OBJECT);
+    common::set_holder(&env, &position, point);
+    return position.into_inner();
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_SceneBridge_setActorPath(
+    env: JNIEnv,
+    object: JObject,
+    actor_id: common::ActorIdJni,
+    waypoints_array: jobjectArray,
+    speed: jfloat,
+) {
+    let waypoints = common::get_points(&env, waypoints_array);
+    let result = {
+        let mut scene = common::get_holder::<Scene>(&env, &object);
+        scene.set_actor_path(actor_id as ActorId, waypoints, speed)
+    };
+    if let Err(message) = result {
+        env.throw_new(consts::CLASS_ILLEGAL_ARGUMENT_EXCEPTION, message).expect(err::JNI_THROW);
     }
 }
 
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_SceneBridge_isActorMoving(
+    env: JNIEnv,
+    object: JObject,
+    actor_id: common::ActorIdJni,
+) -> jboolean {
+    let scene = common::get_holder::<Scene>(&env, &object);
+    scene.is_actor_moving(actor_id as ActorId) as jboolean
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_SceneBridge_takeArrivedActors(
+    env: JNIEnv,
+    object: JObject,
+) -> common::ActorIdArrayJni {
+    let mut scene = common::get_holder::<Scene>(&env, &object);
+    let ids = scene
+        .take_arrived_actors()
+        .iter()
+        .map(|id| *id as common::ActorIdJni)
+        .collect::<Vec<common::ActorIdJni>>();
+    let result = env.new_long_array(ids.len() as i32).expect(err::JNI_NEW_ARRAY);
+    env.set_long_array_region(result, 0, ids.as_slice()).expect(err::JNI_ARRAY_REGION);
+    result
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_SceneBridge_setActorEntityName(
+    env: JNIEnv,
+    object: JObject,
+    actor_id: common::ActorIdJni,
+    entity_name: JString,
+) {
+    let entity_name = common::make_string(&env, entity_name).expect(err::JNI_MAKE_STRING);
+    let mut scene = common::get_holder::<Scene>(&env, &object);
+    scene.set_actor_entity_name(actor_id as ActorId, entity_name)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_SceneBridge_setActorBearing(
+    env: JNIEnv,
+    object: JObject,
+    actor_id: common::ActorIdJni,
+    bearing: jfloat,
+) {
+    let mut scene = common::get_holder::<Scene>(&env, &object);
+    scene.set_actor_bearing(actor_id as ActorId, Some(bearing))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_SceneBridge_clearActorBearing(
+    env: JNIEnv,
+    object: JObject,
+    actor_id: common::ActorIdJni,
+) {
+    let mut scene = common::get_holder::<Scene>(&env, &object);
+    scene.set_actor_bearing(actor_id as ActorId, None)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_SceneBridge_setActorAppearance(
+    env: JNIEnv,
+    object: JObject,
+    actor_id: common::ActorIdJni,
+    scale: jfloat,
+    red: jfloat,
+    green: jfloat,
+    blue: jfloat,
+    alpha: jfloat,
+    opacity: jfloat,
+    altitude: jfloat,
+) {
+    let mut appearance = ActorAppearance::new(scale, [red, green, blue, alpha], opacity, altitude);
+    let mut scene = common::get_holder::<Scene>(&env, &object);
+    let actor_id = actor_id as ActorId;
+    appearance.shadow_radius =
+        scene.get_actor(actor_id).and_then(|actor| actor.get_appearance().shadow_radius);
+    scene.set_actor_appearance(actor_id, appearance)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_SceneBridge_setActorShadowRadius(
+    env: JNIEnv,
+    object: JObject,
+    actor_id: common::ActorIdJni,
+    shadow_radius: jfloat,
+) {
+    let mut scene = common::get_holder::<Scene>(&env, &object);
+    scene.set_actor_shadow_radius(actor_id as ActorId, Some(shadow_radius))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_SceneBridge_clearActorShadowRadius(
+    env: JNIEnv,
+    object: JObject,
+    actor_id: common::ActorIdJni,
+) {
+    let mut scene = common::get_holder::<Scene>(&env, &object);
+    scene.set_actor_shadow_radius(actor_id as ActorId, None)
+}
+
 #[no_mangle]
 #[allow(non_snake_case)]
 pub unsafe extern "C" fn Java_com_edgin_around_rendering_SceneBridge_moveActorBy(
@@ -183,8 +533,5 @@ pub unsafe extern "C" fn Java_com_edgin_around_rendering_SceneBridge_moveActorBy
     bearing: jfloat,
 ) {
     let mut scene = common::get_holder::<Scene>(&env, &object);
-    let radius = scene.get_radius();
-    if let Some(actor) = scene.get_actor_mut(actor_id as ActorId) {
-        actor.move_by(distance, bearing, radius);
-    }
+    scene.move_actor_by(actor_id as ActorId, distance, bearing)
 }
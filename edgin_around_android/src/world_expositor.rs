@@ -1,16 +1,20 @@
 use jni::{
-    objects::{JObject, JString, ReleaseMode},
+    objects::{JObject, JString},
     sys::{jfloat, jint, jobjectArray},
     JNIEnv,
 };
 
 use edgin_around_rendering::{
     expositors::WorldExpositor,
-    game::{Actor, Scene},
-    utils::ids::ActorId,
+    game::{EmitterAnchor, Scene},
+    renderers::{Decal, Path},
+    utils::{
+        coordinates::Point,
+        ids::{ActorId, DecalId, EmitterId, PathId},
+    },
 };
 
-use crate::{common, errors as err};
+use crate::{common, consts, errors as err};
 
 #[no_mangle]
 #[allow(non_snake_case)]
@@ -65,34 +69,96 @@ pub unsafe extern "C" fn Java_com_edgin_around_rendering_WorldExpositorBridge_ge
 
 #[no_mangle]
 #[allow(non_snake_case)]
-pub unsafe extern "C" fn Java_com_edgin_around_rendering_WorldExpositorBridge_getHighlightedActorId(
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_WorldExpositorBridge_getHighlightedActorIds(
     env: JNIEnv,
     object: JObject,
-) -> common::ActorIdJni {
+) -> common::ActorIdArrayJni {
     let world = common::get_holder::<WorldExpositor>(&env, &object);
-    // TODO: Return proper invalid value.
-    world.get_highlighted_actor_id().unwrap_or(0) as common::ActorIdJni
+    let ids = world
+        .get_highlighted_actor_ids()
+        .iter()
+        .map(|id| *id as common::ActorIdJni)
+        .collect::<Vec<common::ActorIdJni>>();
+    let result = env.new_long_array(ids.len() as i32).expect(err::JNI_NEW_ARRAY);
+    env.set_long_array_region(result, 0, ids.as_slice()).expect(err::JNI_ARRAY_REGION);
+    result
 }
 
 #[no_mangle]
 #[allow(non_snake_case)]
-pub unsafe extern "C" fn Java_com_edgin_around_rendering_WorldExpositorBridge_setHighlightedActorId(
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_WorldExpositorBridge_highlightActor(
+    env: JNIEnv,
+    object: JObject,
+    actor_id: common::ActorIdJni,
+    style: JString,
+    red: jfloat,
+    green: jfloat,
+    blue: jfloat,
+    strength: jfloat,
+) {
+    if let Some(highlight) = common::make_highlight(&env, style, [red, green, blue, strength]) {
+        let mut world = common::get_holder::<WorldExpositor>(&env, &object);
+        world.highlight_actor(actor_id as ActorId, highlight)
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_WorldExpositorBridge_unhighlightActor(
+    env: JNIEnv,
+    object: JObject,
+    actor_id: common::ActorIdJni,
+) {
+    let mut world = common::get_holder::<WorldExpositor>(&env, &object);
+    world.unhighlight_actor(actor_id as ActorId)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_WorldExpositorBridge_clearHighlights(
+    env: JNIEnv,
+    object: JObject,
+) {
+    let mut world = common::get_holder::<WorldExpositor>(&env, &object);
+    world.clear_highlights()
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_WorldExpositorBridge_setHoveredActorId(
     env: JNIEnv,
     object: JObject,
     actor_id: common::ActorIdJni,
 ) {
     let mut world = common::get_holder::<WorldExpositor>(&env, &object);
-    world.set_highlighted_actor_id(Some(actor_id as ActorId))
+    world.set_hovered_actor_id(Some(actor_id as ActorId))
 }
 
 #[no_mangle]
 #[allow(non_snake_case)]
-pub unsafe extern "C" fn Java_com_edgin_around_rendering_WorldExpositorBridge_removeHighlight(
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_WorldExpositorBridge_clearHover(
     env: JNIEnv,
     object: JObject,
 ) {
     let mut world = common::get_holder::<WorldExpositor>(&env, &object);
-    world.set_highlighted_actor_id(None)
+    world.set_hovered_actor_id(None)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_WorldExpositorBridge_setHoverHighlight(
+    env: JNIEnv,
+    object: JObject,
+    style: JString,
+    red: jfloat,
+    green: jfloat,
+    blue: jfloat,
+    strength: jfloat,
+) {
+    if let Some(highlight) = common::make_highlight(&env, style, [red, green, blue, strength]) {
+        let mut world = common::get_holder::<WorldExpositor>(&env, &object);
+        world.set_hover_highlight(highlight)
+    }
 }
 
 #[no_mangle]
@@ -128,6 +194,34 @@ pub unsafe extern "C" fn Java_com_edgin_around_rendering_WorldExpositorBridge_ti
     world.tilt_by(angle)
 }
 
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_WorldExpositorBridge_setLighting(
+    env: JNIEnv,
+    object: JObject,
+    azimuth: jfloat,
+    altitude: jfloat,
+    ambient: jfloat,
+    diffuse: jfloat,
+) {
+    let mut world = common::get_holder::<WorldExpositor>(&env, &object);
+    world.set_lighting(azimuth, altitude, ambient, diffuse)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_WorldExpositorBridge_setMovementActions(
+    env: JNIEnv,
+    object: JObject,
+    walk_action: JString,
+    idle_action: JString,
+) {
+    let walk_action = common::make_string(&env, walk_action).expect(err::JNI_MAKE_STRING);
+    let idle_action = common::make_string(&env, idle_action).expect(err::JNI_MAKE_STRING);
+    let mut world = common::get_holder::<WorldExpositor>(&env, &object);
+    world.set_movement_actions(&walk_action, &idle_action)
+}
+
 #[no_mangle]
 #[allow(non_snake_case)]
 pub unsafe extern "C" fn Java_com_edgin_around_rendering_WorldExpositorBridge_createRenderers(
@@ -135,14 +229,9 @@ pub unsafe extern "C" fn Java_com_edgin_around_rendering_WorldExpositorBridge_cr
     object: JObject,
     actors_array: jobjectArray,
 ) {
+    let actors = common::get_actors(&env, actors_array);
     let mut world = common::get_holder::<WorldExpositor>(&env, &object);
-    let mut actors_vec = Vec::<Actor>::new();
-    for i in 0..env.get_array_length(actors_array).expect(err::JNI_ARRAY_LENGHT) {
-        let element = env.get_object_array_element(actors_array, i).expect(err::JNI_ARRAY_ELEMENT);
-        let actor = common::get_holder::<Actor>(&env, &element);
-        actors_vec.push(actor.clone())
-    }
-    world.create_renderers(&actors_vec)
+    world.create_renderers(&actors)
 }
 
 #[no_mangle]
@@ -152,17 +241,243 @@ pub unsafe extern "C" fn Java_com_edgin_around_rendering_WorldExpositorBridge_de
     object: JObject,
     actor_ids_array: common::ActorIdArrayJni,
 ) {
+    let actor_ids = common::get_actor_ids(&env, actor_ids_array);
     let mut world = common::get_holder::<WorldExpositor>(&env, &object);
-    let mut actor_ids_vec = Vec::<ActorId>::new();
-    let actor_ids_length =
-        env.get_array_length(actor_ids_array).expect(err::JNI_ARRAY_LENGHT) as isize;
-    let actor_ids_data = env
-        .get_array_elements::<common::ActorIdJni>(actor_ids_array, ReleaseMode::NoCopyBack)
-        .expect(err::JNI_ARRAY_ELEMENTS);
-    for i in 0..actor_ids_length {
-        actor_ids_vec.push(*actor_ids_data.as_ptr().offset(i) as ActorId)
+    world.delete_renderers(&actor_ids)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_WorldExpositorBridge_createRenderersWithTransition(
+    env: JNIEnv,
+    object: JObject,
+    actors_array: jobjectArray,
+    kind: JString,
+    duration: jfloat,
+) {
+    if let Some(transition) = common::make_transition(&env, kind, duration) {
+        let actors_vec = common::get_actors(&env, actors_array);
+        let mut world = common::get_holder::<WorldExpositor>(&env, &object);
+        world.create_renderers_with_transition(&actors_vec, transition)
     }
-    world.delete_renderers(&actor_ids_vec)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_WorldExpositorBridge_deleteRenderersWithTransition(
+    env: JNIEnv,
+    object: JObject,
+    actor_ids_array: common::ActorIdArrayJni,
+    kind: JString,
+    duration: jfloat,
+) {
+    if let Some(transition) = common::make_transition(&env, kind, duration) {
+        let actor_ids_vec = common::get_actor_ids(&env, actor_ids_array);
+        let mut world = common::get_holder::<WorldExpositor>(&env, &object);
+        world.delete_renderers_with_transition(&actor_ids_vec, transition)
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_WorldExpositorBridge_addDecal(
+    env: JNIEnv,
+    object: JObject,
+    decal_id: common::DecalIdJni,
+    decal_object: JObject,
+) {
+    let decal = common::get_holder::<Decal>(&env, &decal_object).clone();
+    let mut world = common::get_holder::<WorldExpositor>(&env, &object);
+    world.add_decal(decal_id as DecalId, decal)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_WorldExpositorBridge_removeDecal(
+    env: JNIEnv,
+    object: JObject,
+    decal_id: common::DecalIdJni,
+) {
+    let mut world = common::get_holder::<WorldExpositor>(&env, &object);
+    world.remove_decal(decal_id as DecalId)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_WorldExpositorBridge_clearDecals(
+    env: JNIEnv,
+    object: JObject,
+) {
+    let mut world = common::get_holder::<WorldExpositor>(&env, &object);
+    world.clear_decals()
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_WorldExpositorBridge_getDecalIds(
+    env: JNIEnv,
+    object: JObject,
+) -> common::DecalIdArrayJni {
+    let world = common::get_holder::<WorldExpositor>(&env, &object);
+    let ids = world
+        .get_decal_ids()
+        .iter()
+        .map(|id| *id as common::DecalIdJni)
+        .collect::<Vec<common::DecalIdJni>>();
+    let result = env.new_long_array(ids.len() as i32).expect(err::JNI_NEW_ARRAY);
+    env.set_long_array_region(result, 0, ids.as_slice()).expect(err::JNI_ARRAY_REGION);
+    result
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_WorldExpositorBridge_addPath(
+    env: JNIEnv,
+    object: JObject,
+    path_id: common::PathIdJni,
+    path_object: JObject,
+) {
+    let path = common::get_holder::<Path>(&env, &path_object).clone();
+    let mut world = common::get_holder::<WorldExpositor>(&env, &object);
+    world.add_path(path_id as PathId, path)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_WorldExpositorBridge_removePath(
+    env: JNIEnv,
+    object: JObject,
+    path_id: common::PathIdJni,
+) {
+    let mut world = common::get_holder::<WorldExpositor>(&env, &object);
+    world.remove_path(path_id as PathId)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_WorldExpositorBridge_clearPaths(
+    env: JNIEnv,
+    object: JObject,
+) {
+    let mut world = common::get_holder::<WorldExpositor>(&env, &object);
+    world.clear_paths()
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_WorldExpositorBridge_getPathIds(
+    env: JNIEnv,
+    object: JObject,
+) -> common::PathIdArrayJni {
+    let world = common::get_holder::<WorldExpositor>(&env, &object);
+    let ids = world
+        .get_path_ids()
+        .iter()
+        .map(|id| *id as common::PathIdJni)
+        .collect::<Vec<common::PathIdJni>>();
+    let result = env.new_long_array(ids.len() as i32).expect(err::JNI_NEW_ARRAY);
+    env.set_long_array_region(result, 0, ids.as_slice()).expect(err::JNI_ARRAY_REGION);
+    result
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_WorldExpositorBridge_addEmitterAtPoint(
+    env: JNIEnv,
+    object: JObject,
+    emitter_id: common::EmitterIdJni,
+    name: JString,
+    point_object: JObject,
+) {
+    let name = common::make_string(&env, name).expect(err::JNI_MAKE_STRING);
+    let point = common::get_holder::<Point>(&env, &point_object).clone();
+    let result = {
+        let mut world = common::get_holder::<WorldExpositor>(&env, &object);
+        world.add_emitter(emitter_id as EmitterId, &name, EmitterAnchor::Point(point))
+    };
+    if let Err(message) = result {
+        env.throw_new(consts::CLASS_IO_EXCEPTION, message).expect(err::JNI_THROW);
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_WorldExpositorBridge_addEmitterOnActor(
+    env: JNIEnv,
+    object: JObject,
+    emitter_id: common::EmitterIdJni,
+    name: JString,
+    actor_id: common::ActorIdJni,
+) {
+    let name = common::make_string(&env, name).expect(err::JNI_MAKE_STRING);
+    let anchor = EmitterAnchor::Actor(actor_id as ActorId);
+    let result = {
+        let mut world = common::get_holder::<WorldExpositor>(&env, &object);
+        world.add_emitter(emitter_id as EmitterId, &name, anchor)
+    };
+    if let Err(message) = result {
+        env.throw_new(consts::CLASS_IO_EXCEPTION, message).expect(err::JNI_THROW);
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_WorldExpositorBridge_addEmitterOnHook(
+    env: JNIEnv,
+    object: JObject,
+    emitter_id: common::EmitterIdJni,
+    name: JString,
+    actor_id: common::ActorIdJni,
+    hook_name: JString,
+) {
+    let name = common::make_string(&env, name).expect(err::JNI_MAKE_STRING);
+    let hook_name = common::make_string(&env, hook_name).expect(err::JNI_MAKE_STRING);
+    let anchor = EmitterAnchor::Hook(actor_id as ActorId, hook_name);
+    let result = {
+        let mut world = common::get_holder::<WorldExpositor>(&env, &object);
+        world.add_emitter(emitter_id as EmitterId, &name, anchor)
+    };
+    if let Err(message) = result {
+        env.throw_new(consts::CLASS_IO_EXCEPTION, message).expect(err::JNI_THROW);
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_WorldExpositorBridge_removeEmitter(
+    env: JNIEnv,
+    object: JObject,
+    emitter_id: common::EmitterIdJni,
+) {
+    let mut world = common::get_holder::<WorldExpositor>(&env, &object);
+    world.remove_emitter(emitter_id as EmitterId)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_WorldExpositorBridge_clearEmitters(
+    env: JNIEnv,
+    object: JObject,
+) {
+    let mut world = common::get_holder::<WorldExpositor>(&env, &object);
+    world.clear_emitters()
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_com_edgin_around_rendering_WorldExpositorBridge_getEmitterIds(
+    env: JNIEnv,
+    object: JObject,
+) -> common::EmitterIdArrayJni {
+    let world = common::get_holder::<WorldExpositor>(&env, &object);
+    let ids = world
+        .get_emitter_ids()
+        .iter()
+        .map(|id| *id as common::EmitterIdJni)
+        .collect::<Vec<common::EmitterIdJni>>();
+    let result = env.new_long_array(ids.len() as i32).expect(err::JNI_NEW_ARRAY);
+    env.set_long_array_region(result, 0, ids.as_slice()).expect(err::JNI_ARRAY_REGION);
+    result
 }
 
 #[no_mangle]
@@ -1,10 +1,26 @@
-use pyo3::prelude::*;
+use pyo3::{
+    exceptions::{PyIOError, PyValueError},
+    prelude::*,
+};
 
 use edgin_around_rendering::utils::{
     defs::{Radian, Zoom},
-    ids::ActorId,
+    ids::{ActorId, DecalId, EmitterId, PathId},
 };
 
+fn make_highlight(
+    style: &str,
+    color: (f32, f32, f32, f32),
+) -> PyResult<edgin_around_rendering::renderers::Highlight> {
+    match edgin_around_rendering::renderers::HighlightStyle::from_name(style) {
+        Some(style) => {
+            let color = [color.0, color.1, color.2, color.3];
+            Ok(edgin_around_rendering::renderers::Highlight::new(style, color))
+        }
+        None => Err(PyValueError::new_err(format!("Unknown highlight style '{}'", style))),
+    }
+}
+
 #[pyclass]
 pub struct WorldExpositor {
     pub(crate) world: edgin_around_rendering::expositors::WorldExpositor,
@@ -34,16 +50,45 @@ impl WorldExpositor {
         self.world.get_bearing()
     }
 
-    pub fn get_highlighted_actor_id(&self) -> Option<ActorId> {
-        self.world.get_highlighted_actor_id()
+    pub fn get_highlighted_actor_ids(&self) -> Vec<ActorId> {
+        self.world.get_highlighted_actor_ids()
+    }
+
+    pub fn highlight_actor(
+        &mut self,
+        actor_id: ActorId,
+        style: &str,
+        color: (f32, f32, f32, f32),
+    ) -> PyResult<()> {
+        let highlight = make_highlight(style, color)?;
+        self.world.highlight_actor(actor_id, highlight);
+        Ok(())
+    }
+
+    pub fn unhighlight_actor(&mut self, actor_id: ActorId) {
+        self.world.unhighlight_actor(actor_id)
+    }
+
+    pub fn clear_highlights(&mut self) {
+        self.world.clear_highlights()
     }
 
-    pub fn set_highlighted_actor_id(&mut self, actor_id: ActorId) {
-        self.world.set_highlighted_actor_id(Some(actor_id))
+    pub fn get_hovered_actor_id(&self) -> Option<ActorId> {
+        self.world.get_hovered_actor_id()
     }
 
-    pub fn remove_highlight(&mut self) {
-        self.world.set_highlighted_actor_id(None)
+    pub fn set_hovered_actor_id(&mut self, actor_id: Option<ActorId>) {
+        self.world.set_hovered_actor_id(actor_id)
+    }
+
+    pub fn set_hover_highlight(
+        &mut self,
+        style: &str,
+        color: (f32, f32, f32, f32),
+    ) -> PyResult<()> {
+        let highlight = make_highlight(style, color)?;
+        self.world.set_hover_highlight(highlight);
+        Ok(())
     }
 
     pub fn zoom_by(&mut self, zoom: Zoom) {
@@ -58,6 +103,14 @@ impl WorldExpositor {
         self.world.tilt_by(angle)
     }
 
+    pub fn set_lighting(&mut self, azimuth: Radian, altitude: Radian, ambient: f32, diffuse: f32) {
+        self.world.set_lighting(azimuth, altitude, ambient, diffuse)
+    }
+
+    pub fn set_movement_actions(&mut self, walk_action: &str, idle_action: &str) {
+        self.world.set_movement_actions(walk_action, idle_action)
+    }
+
     pub fn create_renderers(&mut self, mut actors: Vec<crate::game::Actor>) {
         let actors = actors.drain(..).map(|a| a.actor).collect();
         self.world.create_renderers(&actors)
@@ -67,6 +120,99 @@ impl WorldExpositor {
         self.world.delete_renderers(&ids)
     }
 
+    pub fn create_renderers_with_transition(
+        &mut self,
+        mut actors: Vec<crate::game::Actor>,
+        kind: &str,
+        duration: f32,
+    ) -> PyResult<()> {
+        let transition = crate::game::make_transition(kind, duration)?;
+        let actors = actors.drain(..).map(|a| a.actor).collect();
+        self.world.create_renderers_with_transition(&actors, transition);
+        Ok(())
+    }
+
+    pub fn delete_renderers_with_transition(
+        &mut self,
+        ids: Vec<ActorId>,
+        kind: &str,
+        duration: f32,
+    ) -> PyResult<()> {
+        let transition = crate::game::make_transition(kind, duration)?;
+        self.world.delete_renderers_with_transition(&ids, transition);
+        Ok(())
+    }
+
+    pub fn add_decal(&mut self, decal_id: DecalId, decal: &crate::renderers::Decal) {
+        self.world.add_decal(decal_id, decal.decal.clone())
+    }
+
+    pub fn remove_decal(&mut self, decal_id: DecalId) {
+        self.world.remove_decal(decal_id)
+    }
+
+    pub fn clear_decals(&mut self) {
+        self.world.clear_decals()
+    }
+
+    pub fn get_decal_ids(&self) -> Vec<DecalId> {
+        self.world.get_decal_ids()
+    }
+
+    pub fn add_path(&mut self, path_id: PathId, path: &crate::renderers::Path) {
+        self.world.add_path(path_id, path.path.clone())
+    }
+
+    pub fn remove_path(&mut self, path_id: PathId) {
+        self.world.remove_path(path_id)
+    }
+
+    pub fn clear_paths(&mut self) {
+        self.world.clear_paths()
+    }
+
+    pub fn get_path_ids(&self) -> Vec<PathId> {
+        self.world.get_path_ids()
+    }
+
+    pub fn add_emitter_at_point(
+        &mut self,
+        emitter_id: EmitterId,
+        name: &str,
+        point: &crate::utils::Point,
+    ) -> PyResult<()> {
+        let anchor = edgin_around_rendering::game::EmitterAnchor::Point(point.point.clone());
+        self.world.add_emitter(emitter_id, name, anchor).map_err(PyIOError::new_err)
+    }
+
+    pub fn add_emitter_on_actor(
+        &mut self,
+        emitter_id: EmitterId,
+        name: &str,
+        actor_id: ActorId,
+        hook_name: Option<String>,
+    ) -> PyResult<()> {
+        let anchor = match hook_name {
+            Some(hook_name) => {
+                edgin_around_rendering::game::EmitterAnchor::Hook(actor_id, hook_name)
+            }
+            None => edgin_around_rendering::game::EmitterAnchor::Actor(actor_id),
+        };
+        self.world.add_emitter(emitter_id, name, anchor).map_err(PyIOError::new_err)
+    }
+
+    pub fn remove_emitter(&mut self, emitter_id: EmitterId) {
+        self.world.remove_emitter(emitter_id)
+    }
+
+    pub fn clear_emitters(&mut self) {
+        self.world.clear_emitters()
+    }
+
+    pub fn get_emitter_ids(&self) -> Vec<EmitterId> {
+        self.world.get_emitter_ids()
+    }
+
     pub fn select_variant(&mut self, actor_id: ActorId, variant_name: String) {
         self.world.select_variant(actor_id, &variant_name)
     }
@@ -1,7 +1,21 @@
-use pyo3::prelude::*;
+use pyo3::{
+    exceptions::{PyIOError, PyValueError},
+    prelude::*,
+};
 
 use edgin_around_rendering::utils::ids::ActorId;
 
+/// Makes a transition of the kind with given name lasting `duration` seconds.
+pub(crate) fn make_transition(
+    kind: &str,
+    duration: f32,
+) -> PyResult<edgin_around_rendering::game::Transition> {
+    match edgin_around_rendering::game::TransitionKind::from_name(kind) {
+        Some(kind) => Ok(edgin_around_rendering::game::Transition::new(kind, duration)),
+        None => Err(PyValueError::new_err(format!("Unknown transition '{}'", kind))),
+    }
+}
+
 #[pyclass]
 #[derive(Clone, Debug)]
 pub struct ElevationFunction {
@@ -18,6 +32,38 @@ impl ElevationFunction {
     pub fn add_terrain(&mut self, name: &str, theta: f32, phi: f32) {
         self.elevation_function.add_terrain(name, theta, phi)
     }
+
+    pub fn add_local_terrain(
+        &mut self,
+        name: &str,
+        theta: f32,
+        phi: f32,
+        range: f32,
+        amplitude: f32,
+        falloff: &str,
+    ) {
+        self.elevation_function.add_local_terrain(name, theta, phi, range, amplitude, falloff)
+    }
+
+    pub fn set_sea_level(&mut self, sea_level: f32) {
+        self.elevation_function.set_sea_level(sea_level)
+    }
+
+    pub fn add_noise_terrain(&mut self, seed: u64, octaves: u32, frequency: f32, amplitude: f32) {
+        self.elevation_function.add_noise_terrain(seed, octaves, frequency, amplitude)
+    }
+
+    pub fn add_heightmap_terrain(
+        &mut self,
+        path: &str,
+        min_height: f32,
+        max_height: f32,
+    ) -> PyResult<()> {
+        let path = std::path::Path::new(path);
+        self.elevation_function
+            .add_heightmap_terrain(path, min_height, max_height)
+            .map_err(PyIOError::new_err)
+    }
 }
 
 #[pyclass]
@@ -49,10 +95,30 @@ impl Scene {
         Self { scene: edgin_around_rendering::game::Scene::new() }
     }
 
+    #[staticmethod]
+    pub fn load_from(path: &str) -> PyResult<Self> {
+        let scene = edgin_around_rendering::game::Scene::load_from(std::path::Path::new(path))
+            .map_err(PyIOError::new_err)?;
+        Ok(Self { scene })
+    }
+
+    pub fn save_to(&self, path: &str) -> PyResult<()> {
+        self.scene.save_to(std::path::Path::new(path)).map_err(PyIOError::new_err)
+    }
+
     pub fn configure(&mut self, hero_actor_id: ActorId, elevation: ElevationFunction) {
         self.scene.configure(hero_actor_id, elevation.elevation_function)
     }
 
+    pub fn reconfigure(
+        &mut self,
+        hero_actor_id: ActorId,
+        elevation: ElevationFunction,
+        reset_renderers: bool,
+    ) {
+        self.scene.reconfigure(hero_actor_id, elevation.elevation_function, reset_renderers)
+    }
+
     pub fn get_hero_id(&self) -> ActorId {
         self.scene.get_hero_id()
     }
@@ -70,6 +136,40 @@ impl Scene {
         self.scene.hide_actors(&actor_ids)
     }
 
+    pub fn create_actors_with_transition(
+        &mut self,
+        mut actors: Vec<Actor>,
+        kind: &str,
+        duration: f32,
+    ) -> PyResult<()> {
+        let transition = make_transition(kind, duration)?;
+        let actors = actors.drain(..).map(|a| a.actor).collect();
+        self.scene.create_actors_with_transition(&actors, transition);
+        Ok(())
+    }
+
+    pub fn delete_actors_with_transition(
+        &mut self,
+        actor_ids: Vec<ActorId>,
+        kind: &str,
+        duration: f32,
+    ) -> PyResult<()> {
+        let transition = make_transition(kind, duration)?;
+        self.scene.delete_actors_with_transition(&actor_ids, transition);
+        Ok(())
+    }
+
+    pub fn hide_actors_with_transition(
+        &mut self,
+        actor_ids: Vec<ActorId>,
+        kind: &str,
+        duration: f32,
+    ) -> PyResult<()> {
+        let transition = make_transition(kind, duration)?;
+        self.scene.hide_actors_with_transition(&actor_ids, transition);
+        Ok(())
+    }
+
     pub fn get_radius(&self) -> f32 {
         self.scene.get_radius()
     }
@@ -82,10 +182,111 @@ impl Scene {
         self.scene.find_closest_actors(&reference_position.point, max_distance)
     }
 
+    pub fn find_nearest_actors(
+        &self,
+        reference_position: &crate::utils::Point,
+        count: usize,
+    ) -> Vec<ActorId> {
+        self.scene.find_nearest_actors(&reference_position.point, count)
+    }
+
+    pub fn find_actors_in_cap(&self, center: &crate::utils::Point, distance: f32) -> Vec<ActorId> {
+        self.scene.find_actors_in_cap(&center.point, distance)
+    }
+
+    pub fn raise_terrain(&mut self, center: &crate::utils::Point, range: f32, amount: f32) {
+        self.scene.raise_terrain(&center.point, range, amount)
+    }
+
+    pub fn lower_terrain(&mut self, center: &crate::utils::Point, range: f32, amount: f32) {
+        self.scene.lower_terrain(&center.point, range, amount)
+    }
+
+    pub fn flatten_terrain(&mut self, center: &crate::utils::Point, range: f32, height: f32) {
+        self.scene.flatten_terrain(&center.point, range, height)
+    }
+
     pub fn set_actor_position(&mut self, actor_id: ActorId, position: crate::utils::Point) {
-        if let Some(actor) = self.scene.get_actor_mut(actor_id) {
-            actor.set_position(position.point);
-        }
+        self.scene.set_actor_position(actor_id, position.point)
+    }
+
+    pub fn update_actor_position(
+        &mut self,
+        actor_id: ActorId,
+        position: crate::utils::Point,
+        timestamp: f64,
+    ) {
+        self.scene.update_actor_position(actor_id, position.point, timestamp)
+    }
+
+    pub fn set_time(&mut self, time: f64) {
+        self.scene.set_time(time)
+    }
+
+    pub fn set_motion_smoothing(&mut self, delay: f64, max_extrapolation: f64, snap_distance: f32) {
+        let smoothing = edgin_around_rendering::game::MotionSmoothing::new(
+            delay,
+            max_extrapolation,
+            snap_distance,
+        );
+        self.scene.set_motion_smoothing(smoothing)
+    }
+
+    pub fn get_rendered_position(&self, actor_id: ActorId) -> Option<crate::utils::Point> {
+        self.scene.get_rendered_position(actor_id).map(|point| crate::utils::Point { point })
+    }
+
+    pub fn set_actor_path(
+        &mut self,
+        actor_id: ActorId,
+        waypoints: Vec<crate::utils::Point>,
+        speed: f32,
+    ) -> PyResult<()> {
+        let waypoints = waypoints.into_iter().map(|point| point.point).collect();
+        self.scene.set_actor_path(actor_id, waypoints, speed).map_err(PyValueError::new_err)
+    }
+
+    pub fn is_actor_moving(&self, actor_id: ActorId) -> bool {
+        self.scene.is_actor_moving(actor_id)
+    }
+
+    pub fn take_arrived_actors(&mut self) -> Vec<ActorId> {
+        self.scene.take_arrived_actors()
+    }
+
+    pub fn set_actor_entity_name(&mut self, actor_id: ActorId, entity_name: String) {
+        self.scene.set_actor_entity_name(actor_id, entity_name)
+    }
+
+    pub fn set_actor_bearing(&mut self, actor_id: ActorId, bearing: Option<f32>) {
+        self.scene.set_actor_bearing(actor_id, bearing)
+    }
+
+    pub fn get_actor_bearing(&self, actor_id: ActorId) -> Option<f32> {
+        self.scene.get_actor(actor_id).and_then(|actor| actor.get_bearing())
+    }
+
+    pub fn set_actor_appearance(
+        &mut self,
+        actor_id: ActorId,
+        scale: f32,
+        tint: (f32, f32, f32, f32),
+        opacity: f32,
+        altitude: f32,
+    ) {
+        let tint = [tint.0, tint.1, tint.2, tint.3];
+        let mut appearance =
+            edgin_around_rendering::game::ActorAppearance::new(scale, tint, opacity, altitude);
+        appearance.shadow_radius = self.get_actor_shadow_radius(actor_id);
+        self.scene.set_actor_appearance(actor_id, appearance)
+    }
+
+    pub fn set_actor_shadow_radius(&mut self, actor_id: ActorId, shadow_radius: Option<f32>) {
+        self.scene.set_actor_shadow_radius(actor_id, shadow_radius)
+    }
+
+    pub fn get_actor_shadow_radius(&self, actor_id: ActorId) -> Option<f32> {
+        self.scene.get_actor(actor_id).and_then(|actor| actor.get_appearance().shadow_radius)
     }
 
     pub fn get_actor_position(
@@ -2,6 +2,7 @@ use pyo3::{prelude::*, wrap_pyfunction};
 
 pub mod expositors;
 pub mod game;
+pub mod renderers;
 pub mod utils;
 
 #[pyfunction]
@@ -20,6 +21,9 @@ fn edgin_around_rendering(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<game::ElevationFunction>()?;
     m.add_class::<game::Scene>()?;
 
+    m.add_class::<renderers::Decal>()?;
+    m.add_class::<renderers::Path>()?;
+
     m.add_class::<expositors::PreviewExpositor>()?;
     m.add_class::<expositors::WorldExpositor>()?;
 
@@ -0,0 +1,108 @@
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+use edgin_around_rendering::{
+    renderers::{Dash, DecalAnimation, DecalAnimationKind, DecalShape},
+    utils::errors as err,
+};
+
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct Decal {
+    pub(crate) decal: edgin_around_rendering::renderers::Decal,
+}
+
+impl Decal {
+    fn new(shape: DecalShape) -> PyResult<Self> {
+        if shape.is_valid() {
+            Ok(Self { decal: edgin_around_rendering::renderers::Decal::new(shape) })
+        } else {
+            Err(PyValueError::new_err(err::DECAL_INVALID_SHAPE))
+        }
+    }
+}
+
+#[pymethods]
+impl Decal {
+    #[staticmethod]
+    pub fn circle(center: &crate::utils::Point, radius: f32) -> PyResult<Self> {
+        Self::new(DecalShape::Circle { center: center.point.clone(), radius })
+    }
+
+    #[staticmethod]
+    pub fn ring(
+        center: &crate::utils::Point,
+        inner_radius: f32,
+        outer_radius: f32,
+    ) -> PyResult<Self> {
+        Self::new(DecalShape::Ring { center: center.point.clone(), inner_radius, outer_radius })
+    }
+
+    #[staticmethod]
+    pub fn sector(
+        center: &crate::utils::Point,
+        radius: f32,
+        bearing: f32,
+        angle: f32,
+    ) -> PyResult<Self> {
+        Self::new(DecalShape::Sector { center: center.point.clone(), radius, bearing, angle })
+    }
+
+    #[staticmethod]
+    pub fn polygon(mut vertices: Vec<crate::utils::Point>) -> PyResult<Self> {
+        let vertices = vertices.drain(..).map(|p| p.point).collect();
+        Self::new(DecalShape::Polygon { vertices })
+    }
+
+    pub fn set_color(&mut self, color: (f32, f32, f32, f32)) {
+        self.decal.color = [color.0, color.1, color.2, color.3];
+    }
+
+    pub fn set_texture(&mut self, texture: Option<String>) {
+        self.decal.texture = texture;
+    }
+
+    pub fn set_animation(&mut self, kind: &str, speed: f32) -> PyResult<()> {
+        match DecalAnimationKind::from_name(kind) {
+            Some(kind) => {
+                self.decal.animation = DecalAnimation::new(kind, speed);
+                Ok(())
+            }
+            None => Err(PyValueError::new_err(format!("Unknown decal animation '{}'", kind))),
+        }
+    }
+}
+
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct Path {
+    pub(crate) path: edgin_around_rendering::renderers::Path,
+}
+
+#[pymethods]
+impl Path {
+    #[new]
+    pub fn new(mut points: Vec<crate::utils::Point>, width: f32) -> Self {
+        let points = points.drain(..).map(|p| p.point).collect();
+        Self { path: edgin_around_rendering::renderers::Path::new(points, width) }
+    }
+
+    pub fn set_color(&mut self, color: (f32, f32, f32, f32)) {
+        self.path.colors = vec![[color.0, color.1, color.2, color.3]];
+    }
+
+    pub fn set_colors(&mut self, colors: Vec<(f32, f32, f32, f32)>) {
+        self.path.colors = colors.iter().map(|c| [c.0, c.1, c.2, c.3]).collect();
+    }
+
+    pub fn set_dash(&mut self, dash: Option<(f32, f32)>) {
+        self.path.dash = dash.map(|(length, gap)| Dash::new(length, gap));
+    }
+
+    pub fn set_texture(&mut self, texture: Option<String>) {
+        self.path.texture = texture;
+    }
+
+    pub fn set_scroll_speed(&mut self, scroll_speed: f32) {
+        self.path.scroll_speed = scroll_speed;
+    }
+}
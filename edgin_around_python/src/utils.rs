@@ -1,16 +1,18 @@
 use pyo3::prelude::*;
 
+use edgin_around_rendering::utils::coordinates;
+
 #[pyclass]
 #[derive(Clone, Debug)]
 pub struct Point {
-    pub(crate) point: edgin_around_rendering::utils::coordinates::Point,
+    pub(crate) point: coordinates::Point,
 }
 
 #[pymethods]
 impl Point {
     #[new]
     pub fn new(theta: f32, phi: f32) -> Self {
-        Self { point: edgin_around_rendering::utils::coordinates::Point::new(theta, phi) }
+        Self { point: coordinates::Point::new(theta, phi) }
     }
 
     pub fn get_theta(&self) -> f32 {
@@ -20,4 +22,49 @@ impl Point {
     pub fn get_phi(&self) -> f32 {
         self.point.phi
     }
+
+    pub fn moved_by(&self, distance: f32, bearing: f32, radius: f32) -> Point {
+        Self { point: self.point.moved_by(distance, bearing, radius) }
+    }
+
+    #[staticmethod]
+    pub fn great_circle_distance(point1: &Point, point2: &Point, radius: f32) -> f32 {
+        coordinates::Point::great_circle_distance(&point1.point, &point2.point, radius)
+    }
+
+    #[staticmethod]
+    pub fn initial_bearing(point1: &Point, point2: &Point) -> f32 {
+        coordinates::Point::initial_bearing(&point1.point, &point2.point)
+    }
+
+    #[staticmethod]
+    pub fn final_bearing(point1: &Point, point2: &Point) -> f32 {
+        coordinates::Point::final_bearing(&point1.point, &point2.point)
+    }
+
+    #[staticmethod]
+    pub fn interpolate(point1: &Point, point2: &Point, fraction: f32) -> Point {
+        Self { point: coordinates::Point::interpolate(&point1.point, &point2.point, fraction) }
+    }
+
+    #[staticmethod]
+    pub fn midpoint(point1: &Point, point2: &Point) -> Point {
+        Self { point: coordinates::Point::midpoint(&point1.point, &point2.point) }
+    }
+
+    #[staticmethod]
+    pub fn destination(start: &Point, distance: f32, bearing: f32, radius: f32) -> Point {
+        Self { point: coordinates::Point::destination(&start.point, distance, bearing, radius) }
+    }
+
+    #[staticmethod]
+    pub fn cross_track_distance(point: &Point, start: &Point, end: &Point, radius: f32) -> f32 {
+        coordinates::Point::cross_track_distance(&point.point, &start.point, &end.point, radius)
+    }
+
+    #[staticmethod]
+    pub fn polygon_area(vertices: Vec<Point>, radius: f32) -> f32 {
+        let vertices: Vec<coordinates::Point> = vertices.into_iter().map(|v| v.point).collect();
+        coordinates::Point::polygon_area(&vertices, radius)
+    }
 }
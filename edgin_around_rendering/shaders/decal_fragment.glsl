@@ -0,0 +1,33 @@
+#version 300 es
+
+const highp int animationPulse = 1;
+const highp int animationRotate = 2;
+const highp float pulseMinimum = 0.3;
+
+in highp vec2 shTexCoords;
+
+out highp vec4 outColor;
+uniform sampler2D sampler;
+uniform highp vec4 uniColor;
+uniform highp int uniTextured;
+uniform highp int uniAnimation;
+uniform highp float uniSpeed;
+uniform highp float uniTime;
+
+void main(void) {
+    highp vec2 coords = shTexCoords;
+    highp float opacity = 1.0;
+    if (uniAnimation == animationRotate) {
+        highp float angle = uniSpeed * uniTime;
+        highp mat2 rotation = mat2(cos(angle), sin(angle), -sin(angle), cos(angle));
+        coords = vec2(0.5) + rotation * (coords - vec2(0.5));
+    } else if (uniAnimation == animationPulse) {
+        opacity = mix(pulseMinimum, 1.0, 0.5 + 0.5 * sin(uniSpeed * uniTime));
+    }
+
+    highp vec4 color = uniColor;
+    if (uniTextured == 1) {
+        color *= texture(sampler, coords);
+    }
+    outColor = vec4(color.rgb, opacity * color.a);
+}
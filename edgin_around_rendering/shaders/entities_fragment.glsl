@@ -1,24 +1,84 @@
 #version 300 es
 
-const highp float highlightRatio = 0.5;
 const highp float fullMistDistance = 30.0;
-const highp vec3 highlightColor = vec3(1.0, 1.0, 1.0);
+const highp int highlightNone = 0;
+const highp int highlightPulse = 2;
+const highp int highlightOutline = 3;
+const highp float pulseFrequency = 5.0;
+const highp float outlineWidth = 2.0;
+const highp float outlineThreshold = 0.5;
 const highp vec3 farColor = vec3(0.5, 0.5, 0.5);
+const highp float dissolveFrequency = 12.0;
+const highp float dissolveEdge = 0.05;
+const highp vec3 dissolveEdgeColor = vec3(1.0, 0.6, 0.2);
 
 flat in highp int shHighlight;
+flat in highp vec4 shTint;
 in highp vec2 shTexCoords;
 in highp float shDistance;
+in highp vec2 shLocalPosition;
 
 out highp vec4 outColor;
 uniform sampler2D sampler;
+uniform highp float uniLight;
+uniform highp float uniDissolve;
+uniform highp vec4 uniHighlightColor;
+uniform highp float uniTime;
+
+highp float hash(highp vec2 point) {
+    return fract(sin(dot(point, vec2(12.9898, 78.233))) * 43758.5453);
+}
+
+highp float valueNoise(highp vec2 point) {
+    highp vec2 cell = floor(point);
+    highp vec2 fraction = fract(point);
+    highp vec2 weight = fraction * fraction * (3.0 - 2.0 * fraction);
+    highp float bottom = mix(hash(cell), hash(cell + vec2(1.0, 0.0)), weight.x);
+    highp float top = mix(hash(cell + vec2(0.0, 1.0)), hash(cell + vec2(1.0, 1.0)), weight.x);
+    return mix(bottom, top, weight.y);
+}
+
+highp float neighbourAlpha(highp vec2 offset) {
+    return texture(sampler, shTexCoords + offset).a;
+}
+
+bool isOnOutline(highp float alpha) {
+    // Transparent texels next to opaque ones form the outline
+    highp vec2 spacing = outlineWidth / vec2(textureSize(sampler, 0));
+    highp float neighbours = max(
+        max(neighbourAlpha(vec2(spacing.x, 0.0)), neighbourAlpha(vec2(-spacing.x, 0.0))),
+        max(neighbourAlpha(vec2(0.0, spacing.y)), neighbourAlpha(vec2(0.0, -spacing.y)))
+    );
+    return alpha < outlineThreshold && neighbours >= outlineThreshold;
+}
 
 void main(void) {
-    highp vec4 color = texture(sampler, shTexCoords);
-    if (shHighlight == 1) {
-        outColor = vec4(mix(color.rgb, highlightColor, highlightRatio), color.a);
-    } else {
+    highp vec4 texel = shTint * texture(sampler, shTexCoords);
+    highp vec4 color = vec4(uniLight * texel.rgb, texel.a);
+    if (uniDissolve > 0.0) {
+        // Remove parts of the sprite with noise below the threshold and light up their edges
+        highp float noise = valueNoise(dissolveFrequency * shLocalPosition);
+        if (noise < uniDissolve) {
+            discard;
+        } else if (noise < uniDissolve + dissolveEdge) {
+            color = vec4(dissolveEdgeColor, color.a);
+        }
+    }
+    if (shHighlight == highlightNone) {
         highp float ratio = min(shDistance / fullMistDistance, 1.0);
         outColor = vec4(mix(color.rgb, farColor, ratio), color.a);
+    } else if (shHighlight == highlightOutline) {
+        if (isOnOutline(color.a)) {
+            outColor = vec4(uniHighlightColor.rgb, uniHighlightColor.a * shTint.a);
+        } else {
+            outColor = color;
+        }
+    } else {
+        highp float strength = uniHighlightColor.a;
+        if (shHighlight == highlightPulse) {
+            strength *= 0.5 + 0.5 * sin(pulseFrequency * uniTime);
+        }
+        outColor = vec4(mix(color.rgb, uniHighlightColor.rgb, strength), color.a);
     }
 }
 
@@ -3,18 +3,24 @@
 uniform mat4 uniModel;
 uniform mat4 uniView;
 uniform int uniHighlight;
+uniform vec4 uniTint;
+uniform float uniOpacity;
 
 layout(location = 0) in vec3 inPosition;
 layout(location = 1) in vec2 inTexCoords;
 
 flat out highp int shHighlight;
+flat out highp vec4 shTint;
+out highp vec2 shLocalPosition;
 out highp vec2 shTexCoords;
 out highp float shDistance;
 
 void main(void) {
     gl_Position = uniView * uniModel * vec4(inPosition, 1);
     shHighlight = uniHighlight;
+    shTint = vec4(uniTint.rgb, uniTint.a * uniOpacity);
     shTexCoords = inTexCoords;
+    shLocalPosition = inPosition.xy;
     shDistance = length(gl_Position.xyz);
 }
 
@@ -1,13 +1,37 @@
 #version 300 es
 
 const highp float fullMistDistance = 30.0;
+const highp float minBlend = 0.0001;
 const highp vec3 farColor = vec3(0.5, 0.5, 0.5);
 
 in highp vec3 shColor;
+in highp vec3 shNormal;
 in highp float shDistance;
 
 out highp vec4 outColor;
-uniform sampler2D sampler;
+uniform sampler2D uniLayer0;
+uniform sampler2D uniLayer1;
+uniform sampler2D uniLayer2;
+uniform sampler2D uniLayer3;
+uniform highp vec4 uniLayerRanges[4];
+uniform int uniLayerCount;
+uniform highp vec2 uniLayerBlend;
+uniform highp float uniSeaRadius;
+uniform highp vec3 uniSunDirection;
+uniform highp float uniAmbient;
+uniform highp float uniDiffuse;
+
+// Returns weight of a value within range `(low, high)` fading linearly over `blend`.
+highp float band(highp float value, highp float low, highp float high, highp float blend) {
+    highp float b = max(blend, minBlend);
+    return clamp((value - low) / b + 0.5, 0.0, 1.0) * clamp((high - value) / b + 0.5, 0.0, 1.0);
+}
+
+highp float layerWeight(int index, highp float height, highp float slope) {
+    highp vec4 range = uniLayerRanges[index];
+    return band(height, range.x, range.y, uniLayerBlend.x)
+         * band(slope, range.z, range.w, uniLayerBlend.y);
+}
 
 void main(void) {
     highp float x = abs(shColor.x);
@@ -23,10 +47,37 @@ void main(void) {
         x = shColor.x;
         y = shColor.y;
     }
+    highp vec2 coords = vec2(0.2 * x, 0.2 * y);
+    highp vec3 normal = normalize(shNormal);
+
+    highp float height = length(shColor) - uniSeaRadius;
+    highp float slope = acos(clamp(dot(normal, normalize(shColor)), -1.0, 1.0));
+
+    highp vec4 sum = vec4(0.0);
+    highp float total = 0.0;
+    highp float weight = layerWeight(0, height, slope);
+    sum += weight * texture(uniLayer0, coords);
+    total += weight;
+    if (uniLayerCount > 1) {
+        weight = layerWeight(1, height, slope);
+        sum += weight * texture(uniLayer1, coords);
+        total += weight;
+    }
+    if (uniLayerCount > 2) {
+        weight = layerWeight(2, height, slope);
+        sum += weight * texture(uniLayer2, coords);
+        total += weight;
+    }
+    if (uniLayerCount > 3) {
+        weight = layerWeight(3, height, slope);
+        sum += weight * texture(uniLayer3, coords);
+        total += weight;
+    }
+    highp vec4 color = (total > minBlend) ? (sum / total) : texture(uniLayer0, coords);
 
     highp float ratio = min(shDistance / fullMistDistance, 1.0);
-    highp vec4 color = texture(sampler, vec2(0.2 * x, 0.2 * y));
-    outColor = vec4(mix(color.rgb, farColor, ratio), color.a);
+    highp float light = uniAmbient + uniDiffuse * max(dot(normal, uniSunDirection), 0.0);
+    outColor = vec4(mix(light * color.rgb, farColor, ratio), color.a);
 
     // Uncomment to see elevation
     //highp float red = length(shColor) - 100.0;
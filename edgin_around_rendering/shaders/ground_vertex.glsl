@@ -3,12 +3,15 @@
 uniform mat4 uniView;
 
 layout(location = 0) in vec3 inPosition;
+layout(location = 1) in vec3 inNormal;
 
 out highp vec3 shColor;
+out highp vec3 shNormal;
 out highp float shDistance;
 
 void main(void) {
     gl_Position = uniView * vec4(inPosition, 1);
     shColor = inPosition;
+    shNormal = inNormal;
     shDistance = length(gl_Position.xyz);
 }
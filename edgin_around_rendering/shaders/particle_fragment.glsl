@@ -0,0 +1,22 @@
+#version 300 es
+
+in highp vec2 shTexCoords;
+in highp vec4 shColor;
+
+out highp vec4 outColor;
+uniform sampler2D sampler;
+uniform highp int uniTextured;
+
+void main(void) {
+    highp vec4 color = shColor;
+    if (uniTextured == 1) {
+        color *= texture(sampler, shTexCoords);
+    } else {
+        highp float distance = 2.0 * length(shTexCoords - vec2(0.5));
+        color.a *= 1.0 - smoothstep(0.5, 1.0, distance);
+    }
+    if (color.a <= 0.0) {
+        discard;
+    }
+    outColor = color;
+}
@@ -0,0 +1,20 @@
+#version 300 es
+
+uniform mat4 uniView;
+uniform vec2 uniScale;
+
+layout(location = 0) in vec3 inPosition;
+layout(location = 1) in vec2 inCorner;
+layout(location = 2) in float inSize;
+layout(location = 3) in vec4 inColor;
+
+out highp vec2 shTexCoords;
+out highp vec4 shColor;
+
+void main(void) {
+    // Offsetting in the clip space keeps the square facing the camera
+    gl_Position = uniView * vec4(inPosition, 1.0);
+    gl_Position.xy += 0.5 * inSize * inCorner * uniScale;
+    shTexCoords = vec2(0.5 + 0.5 * inCorner.x, 0.5 - 0.5 * inCorner.y);
+    shColor = inColor;
+}
@@ -0,0 +1,25 @@
+#version 300 es
+
+in highp vec2 shTexCoords;
+in highp vec4 shColor;
+
+out highp vec4 outColor;
+uniform sampler2D sampler;
+uniform highp int uniTextured;
+uniform highp vec2 uniDash;
+uniform highp float uniWidth;
+uniform highp float uniSpeed;
+uniform highp float uniTime;
+
+void main(void) {
+    highp float along = shTexCoords.x - uniSpeed * uniTime;
+    if (uniDash.x > 0.0 && mod(along, uniDash.x + uniDash.y) > uniDash.x) {
+        discard;
+    }
+
+    highp vec4 color = shColor;
+    if (uniTextured == 1) {
+        color *= texture(sampler, vec2(along / uniWidth, shTexCoords.y));
+    }
+    outColor = color;
+}
@@ -0,0 +1,15 @@
+#version 300 es
+
+const highp float shadowDarkness = 0.4;
+const highp float shadowSoftness = 0.6;
+
+in highp vec2 shPosition;
+
+out highp vec4 outColor;
+uniform highp float uniOpacity;
+
+void main(void) {
+    highp float radius = length(shPosition);
+    highp float alpha = 1.0 - smoothstep(1.0 - shadowSoftness, 1.0, radius);
+    outColor = vec4(0.0, 0.0, 0.0, shadowDarkness * uniOpacity * alpha);
+}
@@ -0,0 +1,13 @@
+#version 300 es
+
+uniform mat4 uniModel;
+uniform mat4 uniView;
+
+layout(location = 0) in vec2 inPosition;
+
+out highp vec2 shPosition;
+
+void main(void) {
+    gl_Position = uniView * uniModel * vec4(inPosition, 0.0, 1.0);
+    shPosition = inPosition;
+}
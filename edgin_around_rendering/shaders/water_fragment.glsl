@@ -0,0 +1,63 @@
+#version 300 es
+
+const highp float fullMistDistance = 30.0;
+const highp float fullDepth = 5.0;
+const highp float foamDepth = 0.3;
+const highp float waveScale = 0.7;
+const highp float waveSpeed = 0.8;
+const highp float waveStrength = 0.08;
+const highp vec2 scrollSpeed = vec2(0.02, 0.013);
+const highp vec3 farColor = vec3(0.5, 0.5, 0.5);
+const highp vec3 shallowColor = vec3(0.35, 0.75, 0.75);
+const highp vec3 deepColor = vec3(0.05, 0.2, 0.45);
+const highp vec3 foamColor = vec3(0.95, 0.97, 1.0);
+
+in highp vec3 shColor;
+in highp float shDepth;
+in highp float shDistance;
+
+out highp vec4 outColor;
+uniform sampler2D sampler;
+uniform highp float uniTime;
+uniform highp vec3 uniSunDirection;
+uniform highp float uniAmbient;
+uniform highp float uniDiffuse;
+
+void main(void) {
+    highp float x = abs(shColor.x);
+    highp float y = abs(shColor.y);
+    highp float z = abs(shColor.z);
+    if (x > y && x > z) {
+        x = shColor.z;
+        y = shColor.y;
+    } else if (y > x && y > z) {
+        x = shColor.x;
+        y = shColor.z;
+    } else {
+        x = shColor.x;
+        y = shColor.y;
+    }
+    highp vec2 coords = vec2(0.2 * x, 0.2 * y) + uniTime * scrollSpeed;
+
+    // Perturb the normal with two moving wave trains
+    highp vec3 p = waveScale * shColor;
+    highp float t = waveSpeed * uniTime;
+    highp vec3 wave = vec3(
+        sin(p.x + p.y + t) + 0.5 * sin(2.3 * p.z - 1.7 * t),
+        sin(p.y - p.z + 1.3 * t) + 0.5 * sin(2.1 * p.x + 1.9 * t),
+        sin(p.z + p.x - 0.9 * t) + 0.5 * sin(2.7 * p.y - 1.1 * t)
+    );
+    highp vec3 normal = normalize(normalize(shColor) + waveStrength * wave);
+
+    highp float depthRatio = clamp(shDepth / fullDepth, 0.0, 1.0);
+    highp vec4 texel = texture(sampler, coords);
+    highp vec3 color = texel.rgb * mix(shallowColor, deepColor, depthRatio);
+
+    highp float foam = 1.0 - smoothstep(0.0, foamDepth, shDepth);
+    foam *= 0.75 + 0.25 * sin(8.0 * shDepth - 3.0 * uniTime);
+    color = mix(color, foamColor, clamp(foam, 0.0, 1.0));
+
+    highp float light = uniAmbient + uniDiffuse * max(dot(normal, uniSunDirection), 0.0);
+    highp float ratio = min(shDistance / fullMistDistance, 1.0);
+    outColor = vec4(mix(light * color, farColor, ratio), 1.0);
+}
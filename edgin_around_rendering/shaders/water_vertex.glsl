@@ -0,0 +1,19 @@
+#version 300 es
+
+uniform mat4 uniView;
+uniform highp float uniSeaRadius;
+
+layout(location = 0) in vec3 inPosition;
+layout(location = 1) in vec3 inNormal;
+layout(location = 2) in float inValue;
+
+out highp vec3 shColor;
+out highp float shDepth;
+out highp float shDistance;
+
+void main(void) {
+    gl_Position = uniView * vec4(inPosition, 1);
+    shColor = inPosition;
+    shDepth = uniSeaRadius - inValue;
+    shDistance = length(gl_Position.xyz);
+}
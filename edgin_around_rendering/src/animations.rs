@@ -1,9 +1,13 @@
+mod facing;
 mod saml;
 mod skeleton;
 mod sprite;
 mod stock;
 
+pub use facing::Facing;
 pub use saml::Parser;
-pub use skeleton::{ACTION_NAME_DEFAULT, VARIANT_NAME_DEFAULT, VARIANT_NAME_HELD};
+pub use skeleton::{
+    ACTION_NAME_DEFAULT, ACTION_NAME_WALK, VARIANT_NAME_DEFAULT, VARIANT_NAME_HELD,
+};
 pub use sprite::Sprite;
 pub use stock::Stock;
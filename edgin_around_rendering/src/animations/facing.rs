@@ -0,0 +1,65 @@
+use std::f32::consts::PI;
+
+use serde::{Deserialize, Serialize};
+
+/// Direction in which a sprite is drawn, as seen by the camera.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Facing {
+    Back,
+    BackRight,
+    Right,
+    FrontRight,
+    Front,
+    FrontLeft,
+    Left,
+    BackLeft,
+}
+
+impl Facing {
+    /// Facings ordered clockwise starting from the one turned away from the camera.
+    const CLOCKWISE: [Facing; 8] = [
+        Facing::Back,
+        Facing::BackRight,
+        Facing::Right,
+        Facing::FrontRight,
+        Facing::Front,
+        Facing::FrontLeft,
+        Facing::Left,
+        Facing::BackLeft,
+    ];
+
+    /// Returns the facing closest to the `heading` out of the given number of `directions`, which
+    /// may be 2, 4 or 8. The heading is the bearing of the actor relative to the bearing of the
+    /// camera, so zero means walking away from the camera. With two directions only left and right
+    /// are distinguished. For other numbers of directions no facing is chosen.
+    pub fn from_heading(heading: f32, directions: usize) -> Option<Facing> {
+        let heading = heading.rem_euclid(2.0 * PI);
+        match directions {
+            2 => Some(if heading < PI { Facing::Right } else { Facing::Left }),
+            4 | 8 => {
+                let sector = 2.0 * PI / directions as f32;
+                let index = ((heading + 0.5 * sector) / sector) as usize % directions;
+                Some(Self::CLOCKWISE[index * (8 / directions)])
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the facing mirrored horizontally.
+    pub fn mirrored(&self) -> Facing {
+        match self {
+            Facing::BackRight => Facing::BackLeft,
+            Facing::Right => Facing::Left,
+            Facing::FrontRight => Facing::FrontLeft,
+            Facing::FrontLeft => Facing::FrontRight,
+            Facing::Left => Facing::Right,
+            Facing::BackLeft => Facing::BackRight,
+            Facing::Back | Facing::Front => *self,
+        }
+    }
+
+    pub fn is_left(&self) -> bool {
+        matches!(self, Facing::FrontLeft | Facing::Left | Facing::BackLeft)
+    }
+}
@@ -2,17 +2,28 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
-use super::{skeleton, stock};
+use super::{facing::Facing, skeleton, stock};
 use crate::utils::errors as err;
 
 const DEFAULT_SCALE: f32 = 1.0;
 const DEFAULT_ANGLE: f32 = 0.0;
 const DEFAULT_POSITION: f32 = 0.0;
+const DEFAULT_DIRECTIONS: usize = 1;
+const SUPPORTED_DIRECTIONS: [usize; 4] = [1, 2, 4, 8];
+const DEFAULT_SHADOW_RADIUS: f32 = 0.3;
 
 fn default_false() -> bool {
     false
 }
 
+fn default_directions() -> usize {
+    DEFAULT_DIRECTIONS
+}
+
+fn default_shadow_radius() -> f32 {
+    DEFAULT_SHADOW_RADIUS
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 struct SamlHoverArea {
     left: f32,
@@ -105,6 +116,11 @@ struct SamlSelection {
 
     /// Corresponding animation ID.
     animation: String,
+
+    /// Side from which the animation shows the object. Animations without a direction are used
+    /// when no directed one matches and are assumed to face right.
+    #[serde(default)]
+    direction: Option<Facing>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -113,6 +129,16 @@ struct SamlSpec {
     skeletons: Vec<SamlSkeleton>,
     animations: Vec<SamlAnimation>,
     selection: Vec<SamlSelection>,
+
+    /// Number of directions (1, 2, 4 or 8) the object can be drawn facing depending on where it
+    /// heads. With one direction the object is never turned.
+    #[serde(default = "default_directions")]
+    directions: usize,
+
+    /// Radius in world units of the shadow cast by the object on the ground. Zero means no
+    /// shadow.
+    #[serde(default = "default_shadow_radius")]
+    shadow_radius: f32,
 }
 
 struct SkeletonInfo {
@@ -132,12 +158,17 @@ pub struct Parser {
     skeletons: HashMap<String, SkeletonInfo>,
     animations: Vec<SamlAnimation>,
     selection: Vec<SamlSelection>,
+    directions: usize,
+    shadow_radius: f32,
 }
 
 impl Parser {
     pub fn new(path: &std::path::Path) -> Self {
         let file = std::fs::File::open(path).expect(&format!("{}: {:?}", err::FILE_FAILED, path));
         let spec: SamlSpec = serde_yaml::from_reader(&file).expect(err::YAML_FAILED);
+        if !SUPPORTED_DIRECTIONS.contains(&spec.directions) {
+            panic!("{}: {}", err::SAML_UNSUPPORTED_DIRECTIONS, spec.directions);
+        }
 
         let mut source_ids = HashMap::new();
         for (index, source) in spec.sources.iter().enumerate() {
@@ -156,6 +187,8 @@ impl Parser {
             skeletons: skeletons,
             animations: spec.animations,
             selection: spec.selection,
+            directions: spec.directions,
+            shadow_radius: spec.shadow_radius,
         }
     }
 
@@ -174,8 +207,13 @@ impl Parser {
             animations.insert(animation.id.clone(), self.prepare_animation(&animation));
         }
 
-        let mut selection: HashMap<String, HashMap<String, String>> = HashMap::new();
+        let mut selection = stock::Selection::new();
+        let mut directed_selection: HashMap<Facing, stock::Selection> = HashMap::new();
         for s in self.selection.iter() {
+            let selection = match s.direction {
+                Some(direction) => directed_selection.entry(direction).or_default(),
+                None => &mut selection,
+            };
             if let Some(animations) = selection.get_mut(&s.variant) {
                 animations.insert(s.action.clone(), s.animation.clone());
             } else {
@@ -185,7 +223,14 @@ impl Parser {
             }
         }
 
-        stock::Stock::new(animations, selection, images)
+        stock::Stock::new(
+            animations,
+            selection,
+            directed_selection,
+            self.directions,
+            self.shadow_radius,
+            images,
+        )
     }
 }
 
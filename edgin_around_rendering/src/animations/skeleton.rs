@@ -7,6 +7,7 @@ type BoneIndex = usize;
 pub const VARIANT_NAME_DEFAULT: &str = "default";
 pub const VARIANT_NAME_HELD: &str = "held";
 pub const ACTION_NAME_DEFAULT: &str = "idle";
+pub const ACTION_NAME_WALK: &str = "walk";
 
 #[derive(Clone, Debug)]
 pub struct Image {
@@ -2,12 +2,13 @@ use std::collections::HashMap;
 
 use crate::{
     animations::{
+        facing::Facing,
         skeleton::{Animation, Image, ACTION_NAME_DEFAULT, VARIANT_NAME_DEFAULT},
         stock::Stock,
     },
     utils::{
         errors as err,
-        geometry::Matrix2D,
+        geometry::{Matrix2D, Vector2D},
         ids::{MediumId, ResourceId},
         tile::Tile,
     },
@@ -35,6 +36,8 @@ pub struct Sprite {
     subsprites: Subsprites,
     selected_variant_name: String,
     selected_action_name: String,
+    facing: Option<Facing>,
+    mirrored: bool,
 }
 
 impl Sprite {
@@ -53,6 +56,8 @@ impl Sprite {
             subsprites: Subsprites::new(),
             selected_variant_name: VARIANT_NAME_DEFAULT.to_string(),
             selected_action_name: ACTION_NAME_DEFAULT.to_string(),
+            facing: None,
+            mirrored: false,
         }
     }
 
@@ -68,6 +73,10 @@ impl Sprite {
         self.stock.get_max_num_layers()
     }
 
+    pub fn get_shadow_radius(&self) -> f32 {
+        self.stock.get_shadow_radius()
+    }
+
     pub fn attach_sprite(&mut self, hook_name: String, sprite: Sprite) {
         self.subsprites.insert(hook_name, sprite);
     }
@@ -84,15 +93,50 @@ impl Sprite {
         &self.selected_action_name
     }
 
+    pub fn get_facing(&self) -> Option<Facing> {
+        self.facing
+    }
+
+    /// Tells if the selected animation has to be drawn mirrored horizontally.
+    pub fn is_mirrored(&self) -> bool {
+        self.mirrored
+    }
+
+    /// Turns the sprite according to the heading of its actor relative to the camera (see
+    /// `Facing::from_heading`). Sprites declaring only one direction are never turned. The
+    /// animation is kept if none matches the new facing.
+    pub fn set_heading(&mut self, heading: Option<f32>) {
+        let directions = self.stock.get_directions();
+        let facing = heading.and_then(|heading| Facing::from_heading(heading, directions));
+        if self.facing == facing {
+            return;
+        }
+
+        self.facing = facing;
+        let selected = self.stock.select_facing(
+            &self.selected_variant_name,
+            &self.selected_action_name,
+            facing,
+        );
+        if let Some((animation_id, mirrored)) = selected {
+            if let Some(animation) = self.stock.get_animation(animation_id) {
+                self.selected_animation = animation.clone();
+                self.mirrored = mirrored;
+            }
+        }
+    }
+
     pub fn select_variant(&mut self, variant_name: &str) -> Result<(), ()> {
         if self.selected_variant_name == variant_name {
             return Ok(());
         }
 
-        let animation_id = self.stock.select(variant_name, &self.selected_action_name);
-        if let Some(animation_id) = animation_id {
+        let selected =
+            self.stock.select_facing(variant_name, &self.selected_action_name, self.facing);
+        if let Some((animation_id, mirrored)) = selected {
             if let Some(animation) = self.stock.get_animation(animation_id) {
                 self.selected_animation = animation.clone();
+                self.mirrored = mirrored;
                 self.selected_variant_name = variant_name.to_string();
                 Ok(())
             } else {
@@ -108,14 +152,16 @@ impl Sprite {
             return Ok(());
         }
 
-        let mut animation_id = self.stock.select(variant_name, &self.selected_action_name);
-        if animation_id.is_none() {
-            animation_id = self.stock.select(VARIANT_NAME_DEFAULT, &self.selected_action_name);
+        let action_name = &self.selected_action_name;
+        let mut selected = self.stock.select_facing(variant_name, action_name, self.facing);
+        if selected.is_none() {
+            selected = self.stock.select_facing(VARIANT_NAME_DEFAULT, action_name, self.facing);
         }
 
-        if let Some(animation_id) = animation_id {
+        if let Some((animation_id, mirrored)) = selected {
             if let Some(animation) = self.stock.get_animation(animation_id) {
                 self.selected_animation = animation.clone();
+                self.mirrored = mirrored;
                 self.selected_variant_name = variant_name.to_string();
                 Ok(())
             } else {
@@ -131,10 +177,12 @@ impl Sprite {
             return Ok(());
         }
 
-        let animation_id = self.stock.select(&self.selected_variant_name, action_name);
-        if let Some(animation_id) = animation_id {
+        let selected =
+            self.stock.select_facing(&self.selected_variant_name, action_name, self.facing);
+        if let Some((animation_id, mirrored)) = selected {
             if let Some(animation) = self.stock.get_animation(animation_id) {
                 self.selected_animation = animation.clone();
+                self.mirrored = mirrored;
                 self.selected_action_name = action_name.to_string();
                 Ok(())
             } else {
@@ -150,14 +198,16 @@ impl Sprite {
             return Ok(());
         }
 
-        let mut animation_id = self.stock.select(&self.selected_variant_name, action_name);
-        if animation_id.is_none() {
-            animation_id = self.stock.select(&self.selected_variant_name, ACTION_NAME_DEFAULT);
+        let variant_name = &self.selected_variant_name;
+        let mut selected = self.stock.select_facing(variant_name, action_name, self.facing);
+        if selected.is_none() {
+            selected = self.stock.select_facing(variant_name, ACTION_NAME_DEFAULT, self.facing);
         }
 
-        if let Some(animation_id) = animation_id {
+        if let Some((animation_id, mirrored)) = selected {
             if let Some(animation) = self.stock.get_animation(animation_id) {
                 self.selected_animation = animation.clone();
+                self.mirrored = mirrored;
                 self.selected_action_name = action_name.to_string();
                 Ok(())
             } else {
@@ -213,6 +263,28 @@ impl Sprite {
     }
 }
 
+impl Sprite {
+    /// Returns the position of the origin of the bone with the given name at the given moment of
+    /// the selected animation, in the same space as tiles. Hooks are bones of sprites too.
+    pub fn get_hook_position(&self, hook_name: &str, moment: f32) -> Option<(f32, f32)> {
+        let animation = &self.selected_animation;
+        let mut transformations = Vec::<Matrix2D>::with_capacity(animation.get_num_layers());
+        for bone in animation.get_bones() {
+            let (mut trans, _) = bone.calc_state_at(moment, animation.get_duration());
+            if let Some(parent_index) = bone.get_parent_index() {
+                trans = &transformations[parent_index] * trans;
+            }
+            if bone.get_name() == hook_name {
+                let position =
+                    Matrix2D::scale(animation.get_scale()) * trans * Vector2D::new(0.0, 0.0);
+                return Some((position.get_x(), position.get_y()));
+            }
+            transformations.push(trans);
+        }
+        None
+    }
+}
+
 impl Sprite {
     fn make_tile(&self, resource_id: ResourceId, image: &Image, trans: &Matrix2D) -> Tile {
         let image_pivot = image.get_pivot();
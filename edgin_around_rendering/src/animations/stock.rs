@@ -1,16 +1,22 @@
 use std::collections::HashMap;
 
 use crate::{
-    animations::skeleton::{Animation, Image, ACTION_NAME_DEFAULT, VARIANT_NAME_DEFAULT},
+    animations::{
+        facing::Facing,
+        skeleton::{Animation, Image, ACTION_NAME_DEFAULT, VARIANT_NAME_DEFAULT},
+    },
     utils::ids::MediumId,
 };
 
-type Selection = HashMap<String, HashMap<String, String>>;
+pub type Selection = HashMap<String, HashMap<String, String>>;
 
 #[derive(Clone, Debug)]
 pub struct Stock {
     animations: HashMap<String, Animation>,
     selection: Selection,
+    directed_selection: HashMap<Facing, Selection>,
+    directions: usize,
+    shadow_radius: f32,
     images: Vec<Image>,
     max_num_layers: usize,
 }
@@ -19,12 +25,33 @@ impl Stock {
     pub fn new(
         animations: HashMap<String, Animation>,
         selection: Selection,
+        directed_selection: HashMap<Facing, Selection>,
+        directions: usize,
+        shadow_radius: f32,
         images: Vec<Image>,
     ) -> Self {
         let max_num_layers =
             animations.values().map(|animation| animation.get_num_layers()).max().unwrap_or(0);
 
-        Self { animations, selection, images, max_num_layers }
+        Self {
+            animations,
+            selection,
+            directed_selection,
+            directions,
+            shadow_radius,
+            images,
+            max_num_layers,
+        }
+    }
+
+    /// Number of directions the sprite can be drawn facing. One means the sprite is never turned.
+    pub fn get_directions(&self) -> usize {
+        self.directions
+    }
+
+    /// Radius of the shadow cast on the ground in world units. Zero means no shadow.
+    pub fn get_shadow_radius(&self) -> f32 {
+        self.shadow_radius
     }
 
     pub fn get_animation(&self, animation_id: &str) -> Option<&Animation> {
@@ -54,6 +81,37 @@ impl Stock {
         action_id
     }
 
+    /// Selects the animation for the variant and action as seen from the given side. Returns also
+    /// whether the animation has to be mirrored horizontally. Animations declared for the facing
+    /// are preferred, then the ones declared for the mirrored facing and finally the ones without
+    /// a direction, which are assumed to face right.
+    pub fn select_facing(
+        &self,
+        variant_name: &str,
+        action_name: &str,
+        facing: Option<Facing>,
+    ) -> Option<(&String, bool)> {
+        let facing = match facing {
+            Some(facing) => facing,
+            None => return self.select(variant_name, action_name).map(|id| (id, false)),
+        };
+
+        let find = |facing: Facing| {
+            self.directed_selection
+                .get(&facing)
+                .and_then(|variants| variants.get(variant_name))
+                .and_then(|actions| actions.get(action_name))
+        };
+
+        if let Some(animation_id) = find(facing) {
+            Some((animation_id, false))
+        } else if let Some(animation_id) = find(facing.mirrored()) {
+            Some((animation_id, facing != facing.mirrored()))
+        } else {
+            self.select(variant_name, action_name).map(|id| (id, facing.is_left()))
+        }
+    }
+
     pub fn get_image(&self, image_id: MediumId) -> Option<&Image> {
         self.images.get(image_id)
     }
@@ -3,6 +3,8 @@ use crate::{
     utils::{errors as err, geometry, graphics},
 };
 
+const PREVIEW_LIGHT: f32 = 1.0;
+
 pub struct PreviewExpositor {
     sprites: game::Sprites,
     size: (usize, usize),
@@ -10,6 +12,10 @@ pub struct PreviewExpositor {
     program: gl::types::GLuint,
     loc_view: gl::types::GLint,
     loc_model: gl::types::GLint,
+    loc_light: gl::types::GLint,
+    loc_tint: gl::types::GLint,
+    loc_opacity: gl::types::GLint,
+    loc_dissolve: gl::types::GLint,
     view: geometry::Matrix3D,
     model: geometry::Matrix3D,
 }
@@ -40,11 +46,32 @@ impl PreviewExpositor {
             .expect(err::GL_LOCATION_FAILED);
         let loc_model = graphics::get_uniform_location(program, "uniModel".to_string())
             .expect(err::GL_LOCATION_FAILED);
+        let loc_light = graphics::get_uniform_location(program, "uniLight".to_string())
+            .expect(err::GL_LOCATION_FAILED);
+        let loc_tint = graphics::get_uniform_location(program, "uniTint".to_string())
+            .expect(err::GL_LOCATION_FAILED);
+        let loc_opacity = graphics::get_uniform_location(program, "uniOpacity".to_string())
+            .expect(err::GL_LOCATION_FAILED);
+        let loc_dissolve = graphics::get_uniform_location(program, "uniDissolve".to_string())
+            .expect(err::GL_LOCATION_FAILED);
 
         let view = geometry::Matrix3D::identity();
         let model = geometry::Matrix3D::identity();
 
-        Self { sprites, size, renderer, program, loc_view, loc_model, view, model }
+        Self {
+            sprites,
+            size,
+            renderer,
+            program,
+            loc_view,
+            loc_model,
+            loc_light,
+            loc_tint,
+            loc_opacity,
+            loc_dissolve,
+            view,
+            model,
+        }
     }
 
     pub fn resize(&mut self, width: usize, height: usize) {
@@ -93,6 +120,10 @@ impl PreviewExpositor {
             gl::UseProgram(self.program);
             gl::UniformMatrix4fv(self.loc_view, 1, gl::TRUE, self.view.as_ptr());
             gl::UniformMatrix4fv(self.loc_model, 1, gl::TRUE, self.model.as_ptr());
+            gl::Uniform1f(self.loc_light, PREVIEW_LIGHT);
+            gl::Uniform4f(self.loc_tint, 1.0, 1.0, 1.0, 1.0);
+            gl::Uniform1f(self.loc_opacity, 1.0);
+            gl::Uniform1f(self.loc_dissolve, 0.0);
         }
 
         self.renderer.render(&self.sprites);
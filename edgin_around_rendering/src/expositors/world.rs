@@ -1,8 +1,16 @@
-use std::{cmp::Ordering, f32::consts::PI};
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    f32::consts::PI,
+    time::Instant,
+};
 
 use crate::{
     animations, game, renderers,
-    utils::{coordinates, defs, errors as err, figures, geometry, graphics, ids::ActorId},
+    utils::{
+        coordinates, defs, errors as err, geometry, graphics,
+        ids::{ActorId, DecalId, EmitterId, PathId, NO_TEXTURE},
+    },
 };
 
 const INITIAL_THETA: f32 = 0.0;
@@ -14,7 +22,22 @@ const INITIAL_BEARING: defs::Radian = 0.0 * PI;
 const INITIAL_TILT: defs::Radian = 0.4 * PI;
 const ZOOM_BOUNDS: (defs::Zoom, defs::Zoom) = (0.0, 1000.0);
 const TILT_BOUNDS: (defs::Radian, defs::Radian) = (0.1 * PI, 1.5 * PI);
-const GROUND_QUALITY: u32 = 6;
+const INITIAL_SUN_AZIMUTH: defs::Radian = 0.75 * PI;
+const INITIAL_SUN_ALTITUDE: defs::Radian = 0.25 * PI;
+const INITIAL_AMBIENT: f32 = 0.5;
+const INITIAL_DIFFUSE: f32 = 0.6;
+const VIEW_ANGLE: defs::Radian = 0.25 * PI;
+const VIEW_NEAR: f32 = 1.0;
+const VIEW_FAR: f32 = 100.0;
+const GROUND_BASE_LEVEL: u32 = 2;
+const GROUND_MIN_LEVEL: u32 = 1;
+const GROUND_MAX_LEVEL: u32 = 6;
+const GROUND_DETAIL_DISTANCE: f32 = 20.0;
+const DECAL_STEP: f32 = 1.0;
+const PATH_STEP: f32 = 1.0;
+
+/// Longest time step of particles, so that they do not jump after a pause in rendering.
+const PARTICLES_MAX_STEP: f32 = 0.1;
 
 pub struct WorldExpositor {
     resource_path: std::path::PathBuf,
@@ -25,28 +48,90 @@ pub struct WorldExpositor {
     theta: f32,
     phi: f32,
     radius: f32,
+    sea_radius: f32,
     elevation: f32,
     zoom: defs::Zoom,
     bearing: defs::Radian,
     tilt: defs::Radian,
 
+    sun_azimuth: defs::Radian,
+    sun_altitude: defs::Radian,
+    ambient: f32,
+    diffuse: f32,
+
     size: (usize, usize),
-    highlighted_actor_id: Option<ActorId>,
+    highlights: HashMap<ActorId, renderers::Highlight>,
+    hovered_actor_id: Option<ActorId>,
+    hover_highlight: renderers::Highlight,
+    walk_action: String,
+    idle_action: String,
+    walking_actors: HashSet<ActorId>,
+    removed_actors: HashSet<ActorId>,
+    decals: HashMap<DecalId, renderers::Decal>,
+    paths: HashMap<PathId, renderers::Path>,
+    emitters: HashMap<EmitterId, game::ParticleEmitter>,
 
     program_ground: gl::types::GLuint,
+    program_water: gl::types::GLuint,
     program_entities: gl::types::GLuint,
+    program_shadows: gl::types::GLuint,
+    program_decals: gl::types::GLuint,
+    program_paths: gl::types::GLuint,
+    program_particles: gl::types::GLuint,
     loc_ground_view: gl::types::GLint,
+    loc_ground_sun_direction: gl::types::GLint,
+    loc_ground_ambient: gl::types::GLint,
+    loc_ground_diffuse: gl::types::GLint,
+    loc_ground_sea_radius: gl::types::GLint,
+    loc_ground_layer_count: gl::types::GLint,
+    loc_ground_layer_ranges: gl::types::GLint,
+    loc_ground_layer_blend: gl::types::GLint,
+    loc_water_view: gl::types::GLint,
+    loc_water_sea_radius: gl::types::GLint,
+    loc_water_time: gl::types::GLint,
+    loc_water_sun_direction: gl::types::GLint,
+    loc_water_ambient: gl::types::GLint,
+    loc_water_diffuse: gl::types::GLint,
     loc_entities_view: gl::types::GLint,
-    loc_entities_model: gl::types::GLint,
-    loc_entities_highlight: gl::types::GLint,
+    loc_entities_light: gl::types::GLint,
+    loc_entities_time: gl::types::GLint,
+    loc_entities: renderers::EntityUniforms,
+    loc_shadows_view: gl::types::GLint,
+    loc_shadows_model: gl::types::GLint,
+    loc_shadows_opacity: gl::types::GLint,
+    loc_decals_view: gl::types::GLint,
+    loc_decals_time: gl::types::GLint,
+    loc_decals_color: gl::types::GLint,
+    loc_decals_textured: gl::types::GLint,
+    loc_decals_animation: gl::types::GLint,
+    loc_decals_speed: gl::types::GLint,
+    loc_paths_view: gl::types::GLint,
+    loc_paths_time: gl::types::GLint,
+    loc_paths_textured: gl::types::GLint,
+    loc_paths_dash: gl::types::GLint,
+    loc_paths_width: gl::types::GLint,
+    loc_paths_speed: gl::types::GLint,
+    loc_particles_view: gl::types::GLint,
+    loc_particles_scale: gl::types::GLint,
+    loc_particles_textured: gl::types::GLint,
 
-    renderer_ground: renderers::PolyhedronRenderer,
-    renderer_water: renderers::PolyhedronRenderer,
+    renderer_ground: Option<renderers::GroundRenderer>,
+    renderer_water: Option<renderers::GroundRenderer>,
+    renderer_shadow: Option<renderers::ShadowRenderer>,
     renderers_entities: Vec<renderers::PositionedRenderer>,
+    renderers_decals: HashMap<DecalId, renderers::DecalRenderer>,
+    renderers_paths: HashMap<PathId, renderers::PathRenderer>,
+    renderers_particles: HashMap<EmitterId, renderers::ParticleRenderer>,
+    particles_time: f32,
+    num_handled_edits: usize,
+    scene_id: usize,
+    actor_revision: usize,
 
     view: geometry::Matrix3D,
+    start_instant: Instant,
 
     ready: bool,
+    generation: u64,
 }
 
 impl WorldExpositor {
@@ -59,23 +144,87 @@ impl WorldExpositor {
             theta: INITIAL_THETA,
             phi: INITIAL_PHI,
             radius: INITIAL_RADIUS,
+            sea_radius: INITIAL_RADIUS,
             elevation: INITIAL_ELEVATION,
             zoom: INITIAL_ZOOM,
             bearing: INITIAL_BEARING,
             tilt: INITIAL_TILT,
+            sun_azimuth: INITIAL_SUN_AZIMUTH,
+            sun_altitude: INITIAL_SUN_ALTITUDE,
+            ambient: INITIAL_AMBIENT,
+            diffuse: INITIAL_DIFFUSE,
             size,
-            highlighted_actor_id: None,
+            highlights: HashMap::new(),
+            hovered_actor_id: None,
+            hover_highlight: renderers::Highlight::new(
+                renderers::HighlightStyle::Outline,
+                [1.0, 1.0, 1.0, 1.0],
+            ),
+            walk_action: animations::ACTION_NAME_WALK.to_owned(),
+            idle_action: animations::ACTION_NAME_DEFAULT.to_owned(),
+            walking_actors: HashSet::new(),
+            removed_actors: HashSet::new(),
+            decals: HashMap::new(),
+            paths: HashMap::new(),
+            emitters: HashMap::new(),
             program_ground: defs::UNONE,
+            program_water: defs::UNONE,
             program_entities: defs::UNONE,
+            program_shadows: defs::UNONE,
+            program_decals: defs::UNONE,
+            program_paths: defs::UNONE,
+            program_particles: defs::UNONE,
             loc_ground_view: defs::INONE,
+            loc_ground_sun_direction: defs::INONE,
+            loc_ground_ambient: defs::INONE,
+            loc_ground_diffuse: defs::INONE,
+            loc_ground_sea_radius: defs::INONE,
+            loc_ground_layer_count: defs::INONE,
+            loc_ground_layer_ranges: defs::INONE,
+            loc_ground_layer_blend: defs::INONE,
+            loc_water_view: defs::INONE,
+            loc_water_sea_radius: defs::INONE,
+            loc_water_time: defs::INONE,
+            loc_water_sun_direction: defs::INONE,
+            loc_water_ambient: defs::INONE,
+            loc_water_diffuse: defs::INONE,
             loc_entities_view: defs::INONE,
-            loc_entities_model: defs::INONE,
-            loc_entities_highlight: defs::INONE,
-            renderer_ground: renderers::PolyhedronRenderer::default(),
-            renderer_water: renderers::PolyhedronRenderer::default(),
+            loc_entities_light: defs::INONE,
+            loc_entities_time: defs::INONE,
+            loc_entities: renderers::EntityUniforms::default(),
+            loc_shadows_view: defs::INONE,
+            loc_shadows_model: defs::INONE,
+            loc_shadows_opacity: defs::INONE,
+            loc_decals_view: defs::INONE,
+            loc_decals_time: defs::INONE,
+            loc_decals_color: defs::INONE,
+            loc_decals_textured: defs::INONE,
+            loc_decals_animation: defs::INONE,
+            loc_decals_speed: defs::INONE,
+            loc_paths_view: defs::INONE,
+            loc_paths_time: defs::INONE,
+            loc_paths_textured: defs::INONE,
+            loc_paths_dash: defs::INONE,
+            loc_paths_width: defs::INONE,
+            loc_paths_speed: defs::INONE,
+            loc_particles_view: defs::INONE,
+            loc_particles_scale: defs::INONE,
+            loc_particles_textured: defs::INONE,
+            renderer_ground: None,
+            renderer_water: None,
+            renderer_shadow: None,
             renderers_entities: Vec::new(),
+            renderers_decals: HashMap::new(),
+            renderers_paths: HashMap::new(),
+            renderers_particles: HashMap::new(),
+            particles_time: 0.0,
+            num_handled_edits: 0,
+            scene_id: 0,
+            actor_revision: 0,
             view: geometry::Matrix3D::identity(),
+            start_instant: Instant::now(),
             ready: false,
+            generation: 0,
         }
     }
 
@@ -86,11 +235,24 @@ impl WorldExpositor {
     pub fn render(&mut self, scene: &game::Scene) {
         if !self.ready && scene.is_ready() {
             self.init_gl();
-            self.load_data(scene);
             self.ready = true;
         }
 
+        if self.ready && (self.generation != scene.get_generation()) {
+            if scene.should_reset_renderers() {
+                self.renderers_entities.clear();
+                self.walking_actors.clear();
+                self.removed_actors.clear();
+                self.highlights.clear();
+                self.hovered_actor_id = None;
+                self.synchronize_all_renderers(scene);
+            }
+            self.load_data(scene);
+            self.generation = scene.get_generation();
+        }
+
         if self.ready {
+            self.synchronize_renderers(scene);
             self.setup();
             self.draw(scene);
             self.teardown();
@@ -103,12 +265,166 @@ impl WorldExpositor {
         self.bearing
     }
 
-    pub fn get_highlighted_actor_id(&self) -> Option<ActorId> {
-        self.highlighted_actor_id
+    /// Returns IDs of all highlighted actors in ascending order. The hovered actor is not
+    /// included unless it is highlighted as well.
+    pub fn get_highlighted_actor_ids(&self) -> Vec<ActorId> {
+        let mut actor_ids: Vec<ActorId> = self.highlights.keys().cloned().collect();
+        actor_ids.sort();
+        actor_ids
+    }
+
+    pub fn get_highlight(&self, actor_id: ActorId) -> Option<&renderers::Highlight> {
+        self.highlights.get(&actor_id)
+    }
+
+    /// Returns the highlight with which the actor is drawn, taking hovering into account.
+    pub fn get_shown_highlight(&self, actor_id: ActorId) -> Option<&renderers::Highlight> {
+        let (highlights, hover_highlight) = (&self.highlights, &self.hover_highlight);
+        Self::select_highlight(highlights, self.hovered_actor_id, hover_highlight, actor_id)
+    }
+
+    /// Highlights the actor in addition to the already highlighted ones, replacing its previous
+    /// highlight if any.
+    pub fn highlight_actor(&mut self, actor_id: ActorId, highlight: renderers::Highlight) {
+        self.highlights.insert(actor_id, highlight);
+    }
+
+    pub fn unhighlight_actor(&mut self, actor_id: ActorId) {
+        self.highlights.remove(&actor_id);
+    }
+
+    pub fn clear_highlights(&mut self) {
+        self.highlights.clear();
+    }
+
+    pub fn get_hovered_actor_id(&self) -> Option<ActorId> {
+        self.hovered_actor_id
+    }
+
+    /// Marks the actor under the pointer. The hover highlight takes precedence over the regular
+    /// highlight of the same actor.
+    pub fn set_hovered_actor_id(&mut self, actor_id: Option<ActorId>) {
+        self.hovered_actor_id = actor_id;
+    }
+
+    pub fn set_hover_highlight(&mut self, highlight: renderers::Highlight) {
+        self.hover_highlight = highlight;
+    }
+
+    /// Draws the decal on the ground, replacing the decal with the same ID if any. Decals with
+    /// invalid shapes are ignored.
+    pub fn add_decal(&mut self, decal_id: DecalId, decal: renderers::Decal) {
+        if !decal.shape.is_valid() {
+            log::warn!("{}: {:?}", err::DECAL_INVALID_SHAPE, decal.shape);
+            return;
+        }
+        self.decals.insert(decal_id, decal);
+        self.renderers_decals.remove(&decal_id);
+    }
+
+    pub fn remove_decal(&mut self, decal_id: DecalId) {
+        self.decals.remove(&decal_id);
+        self.renderers_decals.remove(&decal_id);
+    }
+
+    pub fn clear_decals(&mut self) {
+        self.decals.clear();
+        self.renderers_decals.clear();
+    }
+
+    pub fn get_decal(&self, decal_id: DecalId) -> Option<&renderers::Decal> {
+        self.decals.get(&decal_id)
+    }
+
+    /// Returns IDs of all decals in ascending order, which is also the order of drawing them.
+    pub fn get_decal_ids(&self) -> Vec<DecalId> {
+        let mut decal_ids: Vec<DecalId> = self.decals.keys().cloned().collect();
+        decal_ids.sort();
+        decal_ids
+    }
+
+    /// Draws the path on the ground, replacing the path with the same ID if any.
+    pub fn add_path(&mut self, path_id: PathId, path: renderers::Path) {
+        self.paths.insert(path_id, path);
+        self.renderers_paths.remove(&path_id);
+    }
+
+    pub fn remove_path(&mut self, path_id: PathId) {
+        self.paths.remove(&path_id);
+        self.renderers_paths.remove(&path_id);
+    }
+
+    pub fn clear_paths(&mut self) {
+        self.paths.clear();
+        self.renderers_paths.clear();
+    }
+
+    pub fn get_path(&self, path_id: PathId) -> Option<&renderers::Path> {
+        self.paths.get(&path_id)
+    }
+
+    /// Returns IDs of all paths in ascending order, which is also the order of drawing them.
+    pub fn get_path_ids(&self) -> Vec<PathId> {
+        let mut path_ids: Vec<PathId> = self.paths.keys().cloned().collect();
+        path_ids.sort();
+        path_ids
+    }
+
+    /// Starts the emitter with the given name from the particles directory of the resources,
+    /// replacing the emitter with the same ID if any. Fails without changing anything if the
+    /// emitter does not exist or cannot be parsed.
+    pub fn add_emitter(
+        &mut self,
+        emitter_id: EmitterId,
+        name: &str,
+        anchor: game::EmitterAnchor,
+    ) -> Result<(), String> {
+        let dir = self.resource_path.join(game::PARTICLES_DIR);
+        let spec = game::EmitterSpec::load(&dir, name)?;
+        self.add_emitter_with_spec(emitter_id, spec, anchor);
+        Ok(())
+    }
+
+    /// Like `add_emitter`, but with the emitter defined by the caller.
+    pub fn add_emitter_with_spec(
+        &mut self,
+        emitter_id: EmitterId,
+        spec: game::EmitterSpec,
+        anchor: game::EmitterAnchor,
+    ) {
+        let emitter = game::ParticleEmitter::new(spec, anchor, emitter_id as u64);
+        self.emitters.insert(emitter_id, emitter);
+        self.renderers_particles.remove(&emitter_id);
+    }
+
+    /// Removes the emitter together with its particles.
+    pub fn remove_emitter(&mut self, emitter_id: EmitterId) {
+        self.emitters.remove(&emitter_id);
+        self.renderers_particles.remove(&emitter_id);
+    }
+
+    pub fn clear_emitters(&mut self) {
+        self.emitters.clear();
+        self.renderers_particles.clear();
     }
 
-    pub fn set_highlighted_actor_id(&mut self, actor_id: Option<ActorId>) {
-        self.highlighted_actor_id = actor_id;
+    pub fn get_emitter(&self, emitter_id: EmitterId) -> Option<&game::ParticleEmitter> {
+        self.emitters.get(&emitter_id)
+    }
+
+    /// Returns IDs of all emitters in ascending order. Emitters which stopped and whose particles
+    /// all died are removed automatically.
+    pub fn get_emitter_ids(&self) -> Vec<EmitterId> {
+        let mut emitter_ids: Vec<EmitterId> = self.emitters.keys().cloned().collect();
+        emitter_ids.sort();
+        emitter_ids
+    }
+
+    /// Sets names of actions played automatically when actors start and stop walking along their
+    /// paths.
+    pub fn set_movement_actions(&mut self, walk_action: &str, idle_action: &str) {
+        self.walk_action = walk_action.to_owned();
+        self.idle_action = idle_action.to_owned();
     }
 
     pub fn zoom_by(&mut self, zoom: defs::Zoom) {
@@ -119,6 +435,23 @@ impl WorldExpositor {
         }
     }
 
+    /// Configures the sun. The direction is given relative to the horizon at the focus point:
+    /// `azimuth` is measured clockwise from the north and `altitude` upwards from the horizon.
+    /// The `ambient` and `diffuse` factors scale the texture colors of the ground, while sprites
+    /// are scaled as flat ground would be.
+    pub fn set_lighting(
+        &mut self,
+        azimuth: defs::Radian,
+        altitude: defs::Radian,
+        ambient: f32,
+        diffuse: f32,
+    ) {
+        self.sun_azimuth = azimuth;
+        self.sun_altitude = altitude;
+        self.ambient = ambient;
+        self.diffuse = diffuse;
+    }
+
     pub fn rotate_by(&mut self, angle: defs::Radian) {
         self.bearing += angle;
         while self.bearing > PI {
@@ -137,8 +470,12 @@ impl WorldExpositor {
         }
     }
 
+    /// Creates renderers for the given actors, replacing existing ones. Renderers are also created
+    /// automatically for actors added to the scene, so calling this is needed only to reload
+    /// sprites.
     pub fn create_renderers(&mut self, actors: &Vec<game::Actor>) {
         for actor in actors.iter() {
+            self.delete_renderers(&vec![actor.get_id()]);
             let sprite = self.load_sprite(actor.get_entity_name());
 
             // TODO: Cache loaded skeletons.
@@ -159,8 +496,38 @@ impl WorldExpositor {
         }
     }
 
+    /// Like `create_renderers`, but reveals the actors with the given transition.
+    pub fn create_renderers_with_transition(
+        &mut self,
+        actors: &Vec<game::Actor>,
+        transition: game::Transition,
+    ) {
+        self.create_renderers(actors);
+        let time = self.get_time();
+        for actor in actors.iter() {
+            if let Some(renderer) = self.find_renderer(actor.get_id()) {
+                renderer.start_transition(transition.clone(), true, time);
+            }
+        }
+    }
+
     pub fn delete_renderers(&mut self, ids: &Vec<ActorId>) {
         self.renderers_entities.retain(|renderer| !ids.contains(&renderer.get_actor_id()));
+        self.walking_actors.retain(|id| !ids.contains(id));
+        self.removed_actors.retain(|id| !ids.contains(id));
+    }
+
+    /// Like `delete_renderers`, but the renderers are kept until the given transition conceals
+    /// their sprites.
+    pub fn delete_renderers_with_transition(
+        &mut self,
+        ids: &[ActorId],
+        transition: game::Transition,
+    ) {
+        let time = self.get_time();
+        for id in ids.iter() {
+            self.conceal_and_remove(*id, &transition, time);
+        }
     }
 
     pub fn play_animation(&mut self, actor_id: ActorId, action_name: &str) {
@@ -221,30 +588,172 @@ impl WorldExpositor {
         // Refresh transformation
         self.view = self.prepare_view();
 
+        let sun = self.prepare_sun_direction();
+        self.update_ground(scene);
+
+        // Draw water
+        if let Some(renderer) = &self.renderer_water {
+            let time = self.get_time();
+            unsafe {
+                gl::UseProgram(self.program_water);
+                gl::UniformMatrix4fv(self.loc_water_view, 1, gl::TRUE, self.view.as_ptr());
+                gl::Uniform1f(self.loc_water_sea_radius, self.sea_radius);
+                gl::Uniform1f(self.loc_water_time, time);
+                gl::Uniform3f(self.loc_water_sun_direction, sun.x, sun.y, sun.z);
+                gl::Uniform1f(self.loc_water_ambient, self.ambient);
+                gl::Uniform1f(self.loc_water_diffuse, self.diffuse);
+            }
+            renderer.render();
+        }
+
         // Draw ground
+        if let Some(renderer) = &self.renderer_ground {
+            unsafe {
+                gl::UseProgram(self.program_ground);
+                gl::UniformMatrix4fv(self.loc_ground_view, 1, gl::TRUE, self.view.as_ptr());
+                gl::Uniform3f(self.loc_ground_sun_direction, sun.x, sun.y, sun.z);
+                gl::Uniform1f(self.loc_ground_ambient, self.ambient);
+                gl::Uniform1f(self.loc_ground_diffuse, self.diffuse);
+                gl::Uniform1f(self.loc_ground_sea_radius, self.sea_radius);
+
+                for (i, texture_id) in self.textures.layer_textures.iter().enumerate() {
+                    gl::ActiveTexture(gl::TEXTURE1 + i as gl::types::GLenum);
+                    gl::BindTexture(gl::TEXTURE_2D, *texture_id);
+                }
+                gl::ActiveTexture(gl::TEXTURE0);
+                let count = self.textures.layer_textures.len() as gl::types::GLint;
+                gl::Uniform1i(self.loc_ground_layer_count, count);
+            }
+            renderer.render();
+        }
+
+        // Draw decals on top of the ground without writing depth, so that they do not fight with it
+        self.update_decals(scene);
         unsafe {
-            gl::UseProgram(self.program_ground);
-            gl::UniformMatrix4fv(self.loc_ground_view, 1, gl::TRUE, self.view.as_ptr());
+            gl::UseProgram(self.program_decals);
+            gl::UniformMatrix4fv(self.loc_decals_view, 1, gl::TRUE, self.view.as_ptr());
+            gl::Uniform1f(self.loc_decals_time, self.get_time());
+            gl::DepthMask(gl::FALSE);
+            gl::Enable(gl::POLYGON_OFFSET_FILL);
+            gl::PolygonOffset(-1.0, -1.0);
+        }
+        for decal_id in self.get_decal_ids() {
+            if let (Some(decal), Some(renderer)) =
+                (self.decals.get(&decal_id), self.renderers_decals.get(&decal_id))
+            {
+                let [red, green, blue, alpha] = decal.color;
+                let textured = renderer.get_texture_id() != NO_TEXTURE;
+                unsafe {
+                    gl::Uniform4f(self.loc_decals_color, red, green, blue, alpha);
+                    gl::Uniform1i(self.loc_decals_textured, textured as gl::types::GLint);
+                    gl::Uniform1i(self.loc_decals_animation, decal.animation.kind.get_shader_id());
+                    gl::Uniform1f(self.loc_decals_speed, decal.animation.speed);
+                }
+                renderer.render();
+            }
+        }
+
+        // Draw paths over the decals the same way
+        self.update_paths(scene);
+        unsafe {
+            gl::UseProgram(self.program_paths);
+            gl::UniformMatrix4fv(self.loc_paths_view, 1, gl::TRUE, self.view.as_ptr());
+            gl::Uniform1f(self.loc_paths_time, self.get_time());
+        }
+        for path_id in self.get_path_ids() {
+            if let (Some(path), Some(renderer)) =
+                (self.paths.get(&path_id), self.renderers_paths.get(&path_id))
+            {
+                let dash = path.dash.unwrap_or(renderers::Dash::new(0.0, 0.0));
+                let textured = renderer.get_texture_id() != NO_TEXTURE;
+                unsafe {
+                    gl::Uniform1i(self.loc_paths_textured, textured as gl::types::GLint);
+                    gl::Uniform2f(self.loc_paths_dash, dash.length, dash.gap);
+                    gl::Uniform1f(self.loc_paths_width, path.width);
+                    gl::Uniform1f(self.loc_paths_speed, path.scroll_speed);
+                }
+                renderer.render();
+            }
+        }
+        unsafe {
+            gl::Disable(gl::POLYGON_OFFSET_FILL);
+            gl::DepthMask(gl::TRUE);
         }
 
-        self.renderer_water.render();
-        self.renderer_ground.render();
+        // Drop renderers of removed actors once they are concealed and renderers of actors which
+        // do not exist, e.g. created by hand for a wrong ID
+        let removed_actors = &mut self.removed_actors;
+        self.renderers_entities.retain(|renderer| {
+            let actor_id = renderer.get_actor_id();
+            if removed_actors.contains(&actor_id) {
+                return !renderer.is_concealed();
+            }
+            let exists = scene.get_actor(actor_id).is_some();
+            if !exists {
+                log::warn!("{}: {}", err::NOT_EXISTING_ACTOR, actor_id);
+            }
+            exists
+        });
+        self.forget_dropped_renderers();
+
+        // Switch between walking and idle actions
+        for renderer in self.renderers_entities.iter_mut() {
+            let actor_id = renderer.get_actor_id();
+            let walking = scene.is_actor_moving(actor_id);
+            if walking && self.walking_actors.insert(actor_id) {
+                renderer.select_action(&self.walk_action);
+            } else if !walking && self.walking_actors.remove(&actor_id) {
+                renderer.select_action(&self.idle_action);
+            }
+        }
 
-        // Update entities with bearing and sort them by distance from the camera
+        // Update entities with bearing and sort them by distance from the camera. Actors heading
+        // somewhere are turned relative to the camera; the difference between the north at the
+        // actor and at the focus point is negligible at visible distances. Sprites being concealed
+        // stay where their actors were last seen.
+        let camera_bearing = self.bearing;
+        let time = self.get_time();
+        let (highlights, hover_highlight) = (&self.highlights, &self.hover_highlight);
+        let hovered_actor_id = self.hovered_actor_id;
         for renderer in self.renderers_entities.iter_mut() {
-            let actor = scene.get_actor(renderer.get_actor_id()).expect(err::NOT_EXISTING_ACTOR);
-            renderer.set_highlight(Some(renderer.get_actor_id()) == self.highlighted_actor_id);
+            let actor_id = renderer.get_actor_id();
+            let highlight =
+                Self::select_highlight(highlights, hovered_actor_id, hover_highlight, actor_id);
+            renderer.set_highlight(highlight);
+            renderer.update_transition(time);
 
-            if let Some(position) = actor.get_position() {
-                let elevation = scene.get_elevation(position);
+            let removed = self.removed_actors.contains(&actor_id);
+            let actor = match scene.get_actor(actor_id) {
+                Some(actor) if !removed => actor,
+                _ => {
+                    renderer.change_view(self.view.clone());
+                    continue;
+                }
+            };
+            let appearance = actor.get_appearance();
+            renderer.set_appearance(appearance);
+            renderer.set_heading(actor.get_bearing().map(|bearing| bearing - camera_bearing));
+
+            if let Some(position) = scene.get_rendered_position(actor_id) {
+                if renderer.is_concealing() {
+                    // The actor reappeared before its hiding finished
+                    renderer.stop_transition();
+                }
+                let ground = scene.get_elevation(&position);
+                let normal = scene.get_ground_normal(&position);
+                renderer.place_shadow(&position.to_point3d(ground), &normal, appearance.altitude);
+                let altitude = ground + appearance.altitude;
                 let position = coordinates::Position::new(
                     position.theta,
                     position.phi,
                     self.bearing,
-                    elevation,
+                    altitude,
                 );
                 renderer.change_position_and_view(position, self.view.clone());
+            } else if renderer.is_concealing() && !renderer.is_concealed() {
+                renderer.change_view(self.view.clone());
             } else {
+                renderer.stop_transition();
                 renderer.unset_position();
             }
         }
@@ -252,21 +761,80 @@ impl WorldExpositor {
             b.get_camera_distance().partial_cmp(&a.get_camera_distance()).unwrap_or(Ordering::Equal)
         });
 
-        // Draw entities
+        // Draw shadows on top of the ground without writing depth, so that they neither fight with
+        // the ground nor hide entities
+        if let Some(shadow) = &self.renderer_shadow {
+            unsafe {
+                gl::UseProgram(self.program_shadows);
+                gl::UniformMatrix4fv(self.loc_shadows_view, 1, gl::TRUE, self.view.as_ptr());
+                gl::DepthMask(gl::FALSE);
+                gl::Enable(gl::POLYGON_OFFSET_FILL);
+                gl::PolygonOffset(-1.0, -1.0);
+            }
+            for renderer in self.renderers_entities.iter() {
+                if renderer.has_position() {
+                    renderer.render_shadow(
+                        self.loc_shadows_model,
+                        self.loc_shadows_opacity,
+                        shadow,
+                    );
+                }
+            }
+            unsafe {
+                gl::Disable(gl::POLYGON_OFFSET_FILL);
+                gl::DepthMask(gl::TRUE);
+            }
+        }
+
+        // Draw entities together with particles, from the farthest to the nearest. Particles do
+        // not write depth, so that they do not hide each other.
+        let emitter_order = self.update_particles(scene, time);
+        let scale = 1.0 / (0.5 * VIEW_ANGLE).tan();
         unsafe {
+            gl::UseProgram(self.program_particles);
+            gl::UniformMatrix4fv(self.loc_particles_view, 1, gl::TRUE, self.view.as_ptr());
+            let aspect = self.size.1 as f32 / self.size.0 as f32;
+            gl::Uniform2f(self.loc_particles_scale, scale * aspect, scale);
+
             gl::UseProgram(self.program_entities);
             gl::UniformMatrix4fv(self.loc_entities_view, 1, gl::TRUE, self.view.as_ptr());
+            let light = self.ambient + self.diffuse * self.sun_altitude.sin().max(0.0);
+            gl::Uniform1f(self.loc_entities_light, light);
+            gl::Uniform1f(self.loc_entities_time, time);
         }
 
+        let renderers_particles = &self.renderers_particles;
+        let (program_particles, program_entities) = (self.program_particles, self.program_entities);
+        let loc_particles_textured = self.loc_particles_textured;
+        let draw_particles = |emitter_id: &EmitterId| {
+            if let Some(renderer) = renderers_particles.get(emitter_id) {
+                let textured = renderer.get_texture_id() != NO_TEXTURE;
+                unsafe {
+                    gl::UseProgram(program_particles);
+                    gl::Uniform1i(loc_particles_textured, textured as gl::types::GLint);
+                    gl::DepthMask(gl::FALSE);
+                }
+                renderer.render();
+                unsafe {
+                    gl::DepthMask(gl::TRUE);
+                    gl::UseProgram(program_entities);
+                }
+            }
+        };
+
+        let mut emitters = emitter_order.iter().peekable();
         for renderer in self.renderers_entities.iter_mut() {
             if renderer.has_position() {
-                renderer.render(
-                    self.loc_entities_highlight,
-                    self.loc_entities_model,
-                    &self.sprites,
-                );
+                let distance = renderer.get_camera_distance();
+                while let Some((_, emitter_id)) = emitters.next_if(|(d, _)| *d > distance) {
+                    draw_particles(emitter_id);
+                }
+                renderer.render(&self.loc_entities, &self.sprites);
             }
         }
+        for (_, emitter_id) in emitters {
+            draw_particles(emitter_id);
+        }
 
         unsafe { gl::UseProgram(0) };
     }
@@ -283,25 +851,163 @@ impl WorldExpositor {
     fn init_gl(&mut self) {
         self.program_ground =
             graphics::prepare_ground_shader_program().expect(err::GL_SHADER_FAILED);
+        self.program_water = graphics::prepare_water_shader_program().expect(err::GL_SHADER_FAILED);
         self.program_entities =
             graphics::prepare_entities_shader_program().expect(err::GL_SHADER_FAILED);
+        self.program_shadows =
+            graphics::prepare_shadow_shader_program().expect(err::GL_SHADER_FAILED);
+        self.program_decals =
+            graphics::prepare_decal_shader_program().expect(err::GL_SHADER_FAILED);
+        self.program_paths = graphics::prepare_path_shader_program().expect(err::GL_SHADER_FAILED);
+        self.program_particles =
+            graphics::prepare_particle_shader_program().expect(err::GL_SHADER_FAILED);
+        self.renderer_shadow = Some(renderers::ShadowRenderer::default());
 
         unsafe {
             gl::UseProgram(self.program_ground);
             self.loc_ground_view =
                 graphics::get_uniform_location(self.program_ground, "uniView".to_owned())
                     .expect(err::GL_LOCATION_FAILED);
+            self.loc_ground_sun_direction =
+                graphics::get_uniform_location(self.program_ground, "uniSunDirection".to_owned())
+                    .expect(err::GL_LOCATION_FAILED);
+            self.loc_ground_ambient =
+                graphics::get_uniform_location(self.program_ground, "uniAmbient".to_owned())
+                    .expect(err::GL_LOCATION_FAILED);
+            self.loc_ground_diffuse =
+                graphics::get_uniform_location(self.program_ground, "uniDiffuse".to_owned())
+                    .expect(err::GL_LOCATION_FAILED);
+            self.loc_ground_sea_radius =
+                graphics::get_uniform_location(self.program_ground, "uniSeaRadius".to_owned())
+                    .expect(err::GL_LOCATION_FAILED);
+            self.loc_ground_layer_count =
+                graphics::get_uniform_location(self.program_ground, "uniLayerCount".to_owned())
+                    .expect(err::GL_LOCATION_FAILED);
+            self.loc_ground_layer_ranges =
+                graphics::get_uniform_location(self.program_ground, "uniLayerRanges".to_owned())
+                    .expect(err::GL_LOCATION_FAILED);
+            self.loc_ground_layer_blend =
+                graphics::get_uniform_location(self.program_ground, "uniLayerBlend".to_owned())
+                    .expect(err::GL_LOCATION_FAILED);
+            for i in 0..game::MAX_LAYERS {
+                let name = format!("uniLayer{}", i);
+                let loc = graphics::get_uniform_location(self.program_ground, name)
+                    .expect(err::GL_LOCATION_FAILED);
+                gl::Uniform1i(loc, 1 + i as gl::types::GLint);
+            }
+
+            gl::UseProgram(self.program_water);
+            self.loc_water_view =
+                graphics::get_uniform_location(self.program_water, "uniView".to_owned())
+                    .expect(err::GL_LOCATION_FAILED);
+            self.loc_water_sea_radius =
+                graphics::get_uniform_location(self.program_water, "uniSeaRadius".to_owned())
+                    .expect(err::GL_LOCATION_FAILED);
+            self.loc_water_time =
+                graphics::get_uniform_location(self.program_water, "uniTime".to_owned())
+                    .expect(err::GL_LOCATION_FAILED);
+            self.loc_water_sun_direction =
+                graphics::get_uniform_location(self.program_water, "uniSunDirection".to_owned())
+                    .expect(err::GL_LOCATION_FAILED);
+            self.loc_water_ambient =
+                graphics::get_uniform_location(self.program_water, "uniAmbient".to_owned())
+                    .expect(err::GL_LOCATION_FAILED);
+            self.loc_water_diffuse =
+                graphics::get_uniform_location(self.program_water, "uniDiffuse".to_owned())
+                    .expect(err::GL_LOCATION_FAILED);
 
             gl::UseProgram(self.program_entities);
             self.loc_entities_view =
                 graphics::get_uniform_location(self.program_entities, "uniView".to_owned())
                     .expect(err::GL_LOCATION_FAILED);
-            self.loc_entities_model =
+            self.loc_entities.model =
                 graphics::get_uniform_location(self.program_entities, "uniModel".to_owned())
                     .expect(err::GL_LOCATION_FAILED);
-            self.loc_entities_highlight =
+            self.loc_entities.highlight =
                 graphics::get_uniform_location(self.program_entities, "uniHighlight".to_owned())
                     .expect(err::GL_LOCATION_FAILED);
+            self.loc_entities_light =
+                graphics::get_uniform_location(self.program_entities, "uniLight".to_owned())
+                    .expect(err::GL_LOCATION_FAILED);
+            self.loc_entities.highlight_color = graphics::get_uniform_location(
+                self.program_entities,
+                "uniHighlightColor".to_owned(),
+            )
+            .expect(err::GL_LOCATION_FAILED);
+            self.loc_entities_time =
+                graphics::get_uniform_location(self.program_entities, "uniTime".to_owned())
+                    .expect(err::GL_LOCATION_FAILED);
+            self.loc_entities.tint =
+                graphics::get_uniform_location(self.program_entities, "uniTint".to_owned())
+                    .expect(err::GL_LOCATION_FAILED);
+            self.loc_entities.opacity =
+                graphics::get_uniform_location(self.program_entities, "uniOpacity".to_owned())
+                    .expect(err::GL_LOCATION_FAILED);
+            self.loc_entities.dissolve =
+                graphics::get_uniform_location(self.program_entities, "uniDissolve".to_owned())
+                    .expect(err::GL_LOCATION_FAILED);
+
+            gl::UseProgram(self.program_shadows);
+            self.loc_shadows_view =
+                graphics::get_uniform_location(self.program_shadows, "uniView".to_owned())
+                    .expect(err::GL_LOCATION_FAILED);
+            self.loc_shadows_model =
+                graphics::get_uniform_location(self.program_shadows, "uniModel".to_owned())
+                    .expect(err::GL_LOCATION_FAILED);
+            self.loc_shadows_opacity =
+                graphics::get_uniform_location(self.program_shadows, "uniOpacity".to_owned())
+                    .expect(err::GL_LOCATION_FAILED);
+
+            gl::UseProgram(self.program_decals);
+            self.loc_decals_view =
+                graphics::get_uniform_location(self.program_decals, "uniView".to_owned())
+                    .expect(err::GL_LOCATION_FAILED);
+            self.loc_decals_time =
+                graphics::get_uniform_location(self.program_decals, "uniTime".to_owned())
+                    .expect(err::GL_LOCATION_FAILED);
+            self.loc_decals_color =
+                graphics::get_uniform_location(self.program_decals, "uniColor".to_owned())
+                    .expect(err::GL_LOCATION_FAILED);
+            self.loc_decals_textured =
+                graphics::get_uniform_location(self.program_decals, "uniTextured".to_owned())
+                    .expect(err::GL_LOCATION_FAILED);
+            self.loc_decals_animation =
+                graphics::get_uniform_location(self.program_decals, "uniAnimation".to_owned())
+                    .expect(err::GL_LOCATION_FAILED);
+            self.loc_decals_speed =
+                graphics::get_uniform_location(self.program_decals, "uniSpeed".to_owned())
+                    .expect(err::GL_LOCATION_FAILED);
+
+            gl::UseProgram(self.program_paths);
+            self.loc_paths_view =
+                graphics::get_uniform_location(self.program_paths, "uniView".to_owned())
+                    .expect(err::GL_LOCATION_FAILED);
+            self.loc_paths_time =
+                graphics::get_uniform_location(self.program_paths, "uniTime".to_owned())
+                    .expect(err::GL_LOCATION_FAILED);
+            self.loc_paths_textured =
+                graphics::get_uniform_location(self.program_paths, "uniTextured".to_owned())
+                    .expect(err::GL_LOCATION_FAILED);
+            self.loc_paths_dash =
+                graphics::get_uniform_location(self.program_paths, "uniDash".to_owned())
+                    .expect(err::GL_LOCATION_FAILED);
+            self.loc_paths_width =
+                graphics::get_uniform_location(self.program_paths, "uniWidth".to_owned())
+                    .expect(err::GL_LOCATION_FAILED);
+            self.loc_paths_speed =
+                graphics::get_uniform_location(self.program_paths, "uniSpeed".to_owned())
+                    .expect(err::GL_LOCATION_FAILED);
+
+            gl::UseProgram(self.program_particles);
+            self.loc_particles_view =
+                graphics::get_uniform_location(self.program_particles, "uniView".to_owned())
+                    .expect(err::GL_LOCATION_FAILED);
+            self.loc_particles_scale =
+                graphics::get_uniform_location(self.program_particles, "uniScale".to_owned())
+                    .expect(err::GL_LOCATION_FAILED);
+            self.loc_particles_textured =
+                graphics::get_uniform_location(self.program_particles, "uniTextured".to_owned())
+                    .expect(err::GL_LOCATION_FAILED);
 
             gl::UseProgram(0)
         }
@@ -309,22 +1015,60 @@ impl WorldExpositor {
 
     fn load_data(&mut self, scene: &game::Scene) {
         self.radius = scene.get_radius();
+        self.sea_radius = scene.get_sea_radius();
+        self.num_handled_edits = scene.get_terrain_edits().len();
         self.elevation = scene.get_elevation(&coordinates::Point::new(self.theta, self.phi));
 
-        let mut ground = figures::sphere(GROUND_QUALITY, self.radius);
-        let water = ground.clone();
-        ground.rescale(|x, y| {
-            let point = coordinates::Point::new(x, y);
-            scene.get_elevation(&point)
-        });
+        let lod = renderers::LevelOfDetail {
+            base_level: GROUND_BASE_LEVEL,
+            min_level: GROUND_MIN_LEVEL,
+            max_level: GROUND_MAX_LEVEL,
+            detail_distance: GROUND_DETAIL_DISTANCE,
+        };
 
         self.textures = game::Textures::load(&self.resource_path);
-        self.renderer_water = renderers::PolyhedronRenderer::new(self.textures.water, water);
-        self.renderer_ground = renderers::PolyhedronRenderer::new(self.textures.grass, ground);
+        self.renderers_decals.clear();
+        self.renderers_paths.clear();
+        self.renderers_particles.clear();
+        self.renderer_water =
+            Some(renderers::GroundRenderer::new(self.textures.water, lod.clone(), self.radius));
+        self.renderer_ground =
+            Some(renderers::GroundRenderer::new(self.textures.layer_textures[0], lod, self.radius));
+
+        let layers = &self.textures.layers;
+        let mut ranges = Vec::<gl::types::GLfloat>::with_capacity(4 * layers.layers.len());
+        for layer in layers.layers.iter() {
+            let (min_height, max_height, min_slope, max_slope) = layer.get_ranges();
+            ranges.extend_from_slice(&[min_height, max_height, min_slope, max_slope]);
+        }
+
+        unsafe {
+            gl::UseProgram(self.program_ground);
+            gl::Uniform4fv(
+                self.loc_ground_layer_ranges,
+                layers.layers.len() as gl::types::GLsizei,
+                ranges.as_ptr(),
+            );
+            gl::Uniform2f(self.loc_ground_layer_blend, layers.height_blend, layers.slope_blend);
+            gl::UseProgram(0);
+        }
     }
 }
 
 impl WorldExpositor {
+    fn select_highlight<'a>(
+        highlights: &'a HashMap<ActorId, renderers::Highlight>,
+        hovered_actor_id: Option<ActorId>,
+        hover_highlight: &'a renderers::Highlight,
+        actor_id: ActorId,
+    ) -> Option<&'a renderers::Highlight> {
+        if Some(actor_id) == hovered_actor_id {
+            Some(hover_highlight)
+        } else {
+            highlights.get(&actor_id)
+        }
+    }
+
     fn load_sprite(&mut self, name: &str) -> animations::Sprite {
         // TODO: Load only if needed.
         let saml_path = self.sprites.get_sprites_dir().join(name).join(name).with_extension("saml");
@@ -333,6 +1077,117 @@ impl WorldExpositor {
         animations::Sprite::new(skin_id, parser.to_stock())
     }
 
+    /// Applies actor changes recorded by the scene since the last frame to the renderers. Positions
+    /// are read from the scene on every frame, so moves need no handling here.
+    fn synchronize_renderers(&mut self, scene: &game::Scene) {
+        let time = self.get_time();
+        let changes = if self.scene_id == scene.get_id() {
+            scene.get_actor_changes_since(self.actor_revision)
+        } else {
+            None
+        };
+        if let Some(changes) = changes {
+            for (i, change) in changes.iter().enumerate() {
+                let actor_id = change.get_actor_id();
+                let transition = scene.get_actor_transition(self.actor_revision + i);
+                match (change, transition) {
+                    (game::ActorChange::Added(_), _) => {
+                        let removed = self.removed_actors.contains(&actor_id);
+                        if removed || self.find_renderer(actor_id).is_none() {
+                            self.create_renderer_for(scene, actor_id);
+                        }
+                    }
+                    (game::ActorChange::Renamed(_), _) => self.create_renderer_for(scene, actor_id),
+                    (game::ActorChange::Removed(_), Some(transition)) => {
+                        self.conceal_and_remove(actor_id, transition, time)
+                    }
+                    (game::ActorChange::Removed(_), None) => self.delete_renderers(&vec![actor_id]),
+                    (game::ActorChange::Moved(_), _) => {}
+                }
+
+                let removal = matches!(change, game::ActorChange::Removed(_));
+                if let (false, Some(transition)) = (removal, transition) {
+                    self.start_transition_for(scene, *change, transition, time);
+                }
+            }
+        } else {
+            self.synchronize_all_renderers(scene);
+        }
+        self.actor_revision = scene.get_actor_revision();
+    }
+
+    /// Makes renderers match actors in the scene when the recorded changes are not available.
+    fn synchronize_all_renderers(&mut self, scene: &game::Scene) {
+        self.renderers_entities
+            .retain(|renderer| scene.get_actor(renderer.get_actor_id()).is_some());
+        self.forget_dropped_renderers();
+        let missing: Vec<game::Actor> = scene
+            .get_actors()
+            .filter(|actor| {
+                self.renderers_entities.iter().all(|r| r.get_actor_id() != actor.get_id())
+            })
+            .cloned()
+            .collect();
+        self.create_renderers(&missing);
+        self.scene_id = scene.get_id();
+        self.actor_revision = scene.get_actor_revision();
+    }
+
+    /// Forgets the state kept for actors whose renderers were dropped.
+    fn forget_dropped_renderers(&mut self) {
+        let renderers = &self.renderers_entities;
+        let has_renderer = |id: &ActorId| renderers.iter().any(|r| r.get_actor_id() == *id);
+        self.removed_actors.retain(has_renderer);
+        self.walking_actors.retain(has_renderer);
+    }
+
+    /// Starts the transition requested for a change of an actor which is not being removed:
+    /// reveals new and reappearing actors and conceals the hidden ones.
+    fn start_transition_for(
+        &mut self,
+        scene: &game::Scene,
+        change: game::ActorChange,
+        transition: &game::Transition,
+        time: f32,
+    ) {
+        let actor_id = change.get_actor_id();
+        let visible = scene.get_actor(actor_id).map(|actor| actor.is_visible()).unwrap_or(false);
+        if let Some(renderer) = self.find_renderer(actor_id) {
+            let shown = renderer.has_position() && !renderer.is_concealing();
+            match (visible, change) {
+                (true, game::ActorChange::Moved(_)) if shown => {}
+                (true, _) => renderer.start_transition(transition.clone(), true, time),
+                (false, _) if shown => renderer.start_transition(transition.clone(), false, time),
+                (false, _) => {}
+            }
+        }
+    }
+
+    /// Conceals the sprite of the actor with the transition and removes the renderer afterwards.
+    /// Renderers of actors which are not shown are removed immediately.
+    fn conceal_and_remove(&mut self, actor_id: ActorId, transition: &game::Transition, time: f32) {
+        match self.find_renderer(actor_id) {
+            Some(renderer) if renderer.has_position() => {
+                renderer.start_transition(transition.clone(), false, time);
+                self.removed_actors.insert(actor_id);
+            }
+            _ => self.delete_renderers(&vec![actor_id]),
+        }
+    }
+
+    /// Returns time in seconds elapsed since the expositor was created.
+    fn get_time(&self) -> f32 {
+        (Instant::now() - self.start_instant).as_secs_f32()
+    }
+
+    fn create_renderer_for(&mut self, scene: &game::Scene, actor_id: ActorId) {
+        if let Some(actor) = scene.get_actor(actor_id) {
+            self.create_renderers(&vec![actor.clone()]);
+        } else {
+            self.delete_renderers(&vec![actor_id]);
+        }
+    }
+
     fn update_lookat(&mut self, scene: &game::Scene) {
         let position = scene.get_focus_point();
         self.theta = position.theta;
@@ -340,13 +1195,163 @@ impl WorldExpositor {
         self.elevation = scene.get_elevation(&position);
     }
 
+    /// Rebuilds ground and water chunks whose level of detail changed since the last frame.
+    /// Nothing farther than the far plane can be visible, so the chunks behind it are dropped.
+    /// Water vertices carry the elevation of the ground below them to let the shader compute the
+    /// depth.
+    fn update_ground(&mut self, scene: &game::Scene) {
+        let focus = coordinates::Point::new(self.theta, self.phi);
+        let visible_distance = VIEW_FAR + self.zoom;
+        let sea_radius = self.sea_radius;
+        let elevation =
+            |theta: f32, phi: f32| scene.get_elevation(&coordinates::Point::new(theta, phi));
+
+        // Refresh chunks changed by terrain edits made since the last frame. Decals and paths are
+        // draped anew.
+        let edits = scene.get_terrain_edits();
+        if edits.len() > self.num_handled_edits {
+            self.renderers_decals.clear();
+            self.renderers_paths.clear();
+        }
+        for edit in edits.iter().skip(self.num_handled_edits) {
+            let (center, range) = (edit.get_center(), edit.get_range());
+            if let Some(renderer) = &mut self.renderer_water {
+                renderer.refresh(center, range, |_, _| sea_radius, elevation);
+            }
+            if let Some(renderer) = &mut self.renderer_ground {
+                renderer.refresh(center, range, elevation, |_, _| 0.0);
+            }
+        }
+        self.num_handled_edits = edits.len();
+
+        if let Some(renderer) = &mut self.renderer_water {
+            renderer.update(&focus, visible_distance, |_, _| sea_radius, elevation);
+        }
+        if let Some(renderer) = &mut self.renderer_ground {
+            renderer.update(&focus, visible_distance, elevation, |_, _| 0.0);
+        }
+    }
+
+    /// Creates renderers for decals added since the last frame or invalidated by changes of the
+    /// ground.
+    fn update_decals(&mut self, scene: &game::Scene) {
+        let radius = scene.get_radius();
+        for (decal_id, decal) in self.decals.iter() {
+            if self.renderers_decals.contains_key(decal_id) {
+                continue;
+            }
+            let texture_id = match &decal.texture {
+                Some(name) => self.textures.get_decal_texture(&self.resource_path, name),
+                None => NO_TEXTURE,
+            };
+            let mesh = renderers::DecalMesh::new(&decal.shape, radius, DECAL_STEP);
+            let elevation = |point: &coordinates::Point| scene.get_elevation(point);
+            let renderer = renderers::DecalRenderer::new(&mesh, elevation, texture_id);
+            self.renderers_decals.insert(*decal_id, renderer);
+        }
+    }
+
+    /// Creates renderers for paths added since the last frame or invalidated by changes of the
+    /// ground.
+    fn update_paths(&mut self, scene: &game::Scene) {
+        let radius = scene.get_radius();
+        for (path_id, path) in self.paths.iter() {
+            if self.renderers_paths.contains_key(path_id) {
+                continue;
+            }
+            let texture_id = match &path.texture {
+                Some(name) => self.textures.get_path_texture(&self.resource_path, name),
+                None => NO_TEXTURE,
+            };
+            let mesh = renderers::PathMesh::new(path, radius, PATH_STEP);
+            let elevation = |point: &coordinates::Point| scene.get_elevation(point);
+            let renderer = renderers::PathRenderer::new(&mesh, elevation, texture_id);
+            self.renderers_paths.insert(*path_id, renderer);
+        }
+    }
+
+    /// Moves particles to the given time and spawns new ones where the anchors of their emitters
+    /// currently are. Returns IDs of emitters with particles to draw, from the farthest to the
+    /// nearest to the camera.
+    fn update_particles(&mut self, scene: &game::Scene, time: f32) -> Vec<(f32, EmitterId)> {
+        let dt = (time - self.particles_time).clamp(0.0, PARTICLES_MAX_STEP);
+        self.particles_time = time;
+
+        let entities = &self.renderers_entities;
+        for emitter in self.emitters.values_mut() {
+            let origin = match emitter.get_anchor() {
+                game::EmitterAnchor::Point(point) => {
+                    Some(point.to_point3d(scene.get_elevation(point)))
+                }
+                game::EmitterAnchor::Actor(actor_id) => {
+                    let altitude = scene
+                        .get_actor(*actor_id)
+                        .map(|actor| actor.get_appearance().altitude)
+                        .unwrap_or(0.0);
+                    scene.get_rendered_position(*actor_id).map(|position| {
+                        position.to_point3d(scene.get_elevation(&position) + altitude)
+                    })
+                }
+                game::EmitterAnchor::Hook(actor_id, hook_name) => entities
+                    .iter()
+                    .find(|renderer| renderer.get_actor_id() == *actor_id)
+                    .filter(|renderer| !renderer.is_concealing())
+                    .and_then(|renderer| renderer.get_hook_point(hook_name)),
+            };
+            let up = origin.as_ref().map(|origin| origin.enlongated(1.0));
+            emitter.update(dt, origin.as_ref().zip(up.as_ref()));
+        }
+
+        self.emitters.retain(|_, emitter| !emitter.is_finished());
+        let emitters = &self.emitters;
+        self.renderers_particles.retain(|emitter_id, _| emitters.contains_key(emitter_id));
+
+        let mut order = Vec::with_capacity(self.emitters.len());
+        for (emitter_id, emitter) in self.emitters.iter() {
+            if emitter.get_particles().is_empty() {
+                continue;
+            }
+            let textures = &mut self.textures;
+            let resource_path = &self.resource_path;
+            let renderer = self.renderers_particles.entry(*emitter_id).or_insert_with(|| {
+                let texture_id = match &emitter.get_spec().texture {
+                    Some(name) => textures.get_particle_texture(resource_path, name),
+                    None => NO_TEXTURE,
+                };
+                renderers::ParticleRenderer::new(texture_id)
+            });
+            renderer.load(emitter, &self.view);
+            order.push((renderer.get_camera_distance(), *emitter_id));
+        }
+        order.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(Ordering::Equal));
+        order
+    }
+
+    /// Converts the sun azimuth and altitude at the focus point to a direction in world space.
+    fn prepare_sun_direction(&self) -> coordinates::Point3D {
+        let (st, ct, sp, cp) = (self.theta.sin(), self.theta.cos(), self.phi.sin(), self.phi.cos());
+        let up = coordinates::Point3D::new(st * sp, ct, st * cp);
+        let north = coordinates::Point3D::new(-ct * sp, st, -ct * cp);
+        let east = coordinates::Point3D::new(cp, 0.0, -sp);
+
+        let horizontal = self.sun_altitude.cos();
+        let (h_north, h_east) =
+            (horizontal * self.sun_azimuth.cos(), horizontal * self.sun_azimuth.sin());
+        let vertical = self.sun_altitude.sin();
+        coordinates::Point3D::new(
+            h_north * north.x + h_east * east.x + vertical * up.x,
+            h_north * north.y + h_east * east.y + vertical * up.y,
+            h_north * north.z + h_east * east.z + vertical * up.z,
+        )
+    }
+
     fn prepare_view(&self) -> geometry::Matrix3D {
         geometry::Matrix3D::perspective(
-            0.25 * PI,
+            VIEW_ANGLE,
             self.size.0 as f32,
             self.size.1 as f32,
-            1.0,
-            100.0,
+            VIEW_NEAR,
+            VIEW_FAR,
         ) * geometry::Matrix3D::translation((0.0, 0.0, -self.zoom))
             * geometry::Matrix3D::rotation_x(-self.tilt)
             * geometry::Matrix3D::translation((0.0, 0.0, -self.elevation))
@@ -1,5 +1,26 @@
+mod heightmap;
+mod ktx;
+mod layers;
 mod media;
+mod motion;
+mod particles;
+mod sampling;
 mod scene;
+mod spatial;
+mod transition;
 
+pub use heightmap::Heightmap;
+pub use ktx::{CompressedImage, Level, KTX2_EXTENSION};
+pub use layers::{TerrainLayer, TerrainLayers, LAYERS_FILE, MAX_LAYERS, UNBOUNDED};
 pub use media::{sprites_path, Sprites, Textures};
-pub use scene::{Actor, ElevationFunction, Scene};
+pub use motion::{ActorMotion, ActorPath, MotionSmoothing};
+pub use particles::{
+    Blend, Curve, CurveKey, EmitterAnchor, EmitterSpec, Particle, ParticleEmitter, PARTICLES_DIR,
+};
+pub use sampling::{Filter, Sampling, Wrap, SAMPLING_FILE};
+pub use scene::{
+    Actor, ActorAppearance, ActorChange, EditKind, ElevationFunction, Falloff, HeightmapTerrain,
+    NoiseTerrain, Scene, SceneDescription, TerrainData, TerrainEdit, TerrainVariant,
+};
+pub use spatial::SpatialIndex;
+pub use transition::{Transition, TransitionEffect, TransitionKind};
@@ -0,0 +1,80 @@
+use std::{f32::consts::PI, fs::File, path::Path};
+
+use crate::utils::{coordinates::Point, errors as err};
+
+/// Grayscale image in equirectangular projection describing relative heights of the terrain.
+///
+/// The first row corresponds to the north pole and the first column to the longitude of -180
+/// degrees. Samples are normalized to range `[0, 1]`.
+#[derive(Clone, Debug)]
+pub struct Heightmap {
+    width: usize,
+    height: usize,
+    samples: Vec<f32>,
+}
+
+impl Heightmap {
+    pub fn new(width: usize, height: usize, samples: Vec<f32>) -> Self {
+        let is_valid = (width > 0) && (height > 0) && (width * height == samples.len());
+        assert!(is_valid, "{}", err::HEIGHTMAP_INVALID_SIZE);
+        Self { width, height, samples }
+    }
+
+    /// Loads a heightmap from a 8-bit or 16-bit PNG file. Only the first channel is used if the
+    /// image is not grayscale.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let file =
+            File::open(path).map_err(|e| format!("{}: {:?}: {}", err::FILE_FAILED, path, e))?;
+        let png_failed = |e| format!("{}: {:?}: {}", err::PNG_FAILED, path, e);
+        let mut decoder = png::Decoder::new(file);
+        decoder.set_transformations(png::Transformations::EXPAND);
+        let (info, mut reader) = decoder.read_info().map_err(png_failed)?;
+        let mut buf = vec![0; info.buffer_size()];
+        reader.next_frame(&mut buf).map_err(png_failed)?;
+
+        let channels = info.color_type.samples();
+        let (width, height) = (info.width as usize, info.height as usize);
+        let samples = match info.bit_depth {
+            png::BitDepth::Sixteen => buf
+                .chunks(2 * channels)
+                .map(|c| u16::from_be_bytes([c[0], c[1]]) as f32 / u16::MAX as f32)
+                .collect(),
+            _ => buf.chunks(channels).map(|c| c[0] as f32 / u8::MAX as f32).collect(),
+        };
+
+        Ok(Self::new(width, height, samples))
+    }
+
+    pub fn get_size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    /// Returns bilinearly filtered sample at the given point. Columns wrap around the antimeridian
+    /// while rows are clamped at the poles.
+    pub fn sample(&self, point: &Point) -> f32 {
+        let lon = if point.phi <= PI { point.phi } else { point.phi - 2.0 * PI };
+        let u = (lon + PI) / (2.0 * PI);
+        let v = point.theta / PI;
+
+        let x = u * self.width as f32 - 0.5;
+        let y = (v * self.height as f32 - 0.5).clamp(0.0, (self.height - 1) as f32);
+        let (x0, y0) = (x.floor(), y.floor());
+        let (fx, fy) = (x - x0, y - y0);
+
+        let width = self.width as i64;
+        let column0 = (x0 as i64).rem_euclid(width) as usize;
+        let column1 = (x0 as i64 + 1).rem_euclid(width) as usize;
+        let row0 = y0 as usize;
+        let row1 = (row0 + 1).min(self.height - 1);
+
+        let top = self.get(column0, row0) * (1.0 - fx) + self.get(column1, row0) * fx;
+        let bottom = self.get(column0, row1) * (1.0 - fx) + self.get(column1, row1) * fx;
+        top * (1.0 - fy) + bottom * fy
+    }
+}
+
+impl Heightmap {
+    fn get(&self, column: usize, row: usize) -> f32 {
+        self.samples[row * self.width + column]
+    }
+}
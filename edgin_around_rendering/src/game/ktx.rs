@@ -0,0 +1,143 @@
+use std::convert::TryInto;
+
+/// File extension of KTX2 containers.
+pub const KTX2_EXTENSION: &str = "ktx2";
+
+const KTX2_IDENTIFIER: [u8; 12] =
+    [0xAB, 0x4B, 0x54, 0x58, 0x20, 0x32, 0x30, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A];
+const KTX2_HEADER_SIZE: usize = 80;
+const KTX2_LEVEL_INDEX_ENTRY_SIZE: usize = 24;
+const KTX2_SUPERCOMPRESSION_NONE: u32 = 0;
+
+const COMPRESSED_RGBA_ASTC_4X4_KHR: gl::types::GLenum = 0x93B0;
+const COMPRESSED_RGBA_ASTC_5X4_KHR: gl::types::GLenum = 0x93B1;
+const COMPRESSED_RGBA_ASTC_5X5_KHR: gl::types::GLenum = 0x93B2;
+const COMPRESSED_RGBA_ASTC_6X5_KHR: gl::types::GLenum = 0x93B3;
+const COMPRESSED_RGBA_ASTC_6X6_KHR: gl::types::GLenum = 0x93B4;
+const COMPRESSED_RGBA_ASTC_8X5_KHR: gl::types::GLenum = 0x93B5;
+const COMPRESSED_RGBA_ASTC_8X6_KHR: gl::types::GLenum = 0x93B6;
+const COMPRESSED_RGBA_ASTC_8X8_KHR: gl::types::GLenum = 0x93B7;
+const COMPRESSED_SRGB8_ALPHA8_ASTC_4X4_KHR: gl::types::GLenum = 0x93D0;
+const COMPRESSED_SRGB8_ALPHA8_ASTC_5X4_KHR: gl::types::GLenum = 0x93D1;
+const COMPRESSED_SRGB8_ALPHA8_ASTC_5X5_KHR: gl::types::GLenum = 0x93D2;
+const COMPRESSED_SRGB8_ALPHA8_ASTC_6X5_KHR: gl::types::GLenum = 0x93D3;
+const COMPRESSED_SRGB8_ALPHA8_ASTC_6X6_KHR: gl::types::GLenum = 0x93D4;
+const COMPRESSED_SRGB8_ALPHA8_ASTC_8X5_KHR: gl::types::GLenum = 0x93D5;
+const COMPRESSED_SRGB8_ALPHA8_ASTC_8X6_KHR: gl::types::GLenum = 0x93D6;
+const COMPRESSED_SRGB8_ALPHA8_ASTC_8X8_KHR: gl::types::GLenum = 0x93D7;
+
+/// Translates a Vulkan format used in KTX2 headers to a compressed OpenGL format.
+fn vk_format_to_gl(vk_format: u32) -> Option<gl::types::GLenum> {
+    match vk_format {
+        147 => Some(gl::COMPRESSED_RGB8_ETC2),
+        148 => Some(gl::COMPRESSED_SRGB8_ETC2),
+        149 => Some(gl::COMPRESSED_RGB8_PUNCHTHROUGH_ALPHA1_ETC2),
+        150 => Some(gl::COMPRESSED_SRGB8_PUNCHTHROUGH_ALPHA1_ETC2),
+        151 => Some(gl::COMPRESSED_RGBA8_ETC2_EAC),
+        152 => Some(gl::COMPRESSED_SRGB8_ALPHA8_ETC2_EAC),
+        157 => Some(COMPRESSED_RGBA_ASTC_4X4_KHR),
+        158 => Some(COMPRESSED_SRGB8_ALPHA8_ASTC_4X4_KHR),
+        159 => Some(COMPRESSED_RGBA_ASTC_5X4_KHR),
+        160 => Some(COMPRESSED_SRGB8_ALPHA8_ASTC_5X4_KHR),
+        161 => Some(COMPRESSED_RGBA_ASTC_5X5_KHR),
+        162 => Some(COMPRESSED_SRGB8_ALPHA8_ASTC_5X5_KHR),
+        163 => Some(COMPRESSED_RGBA_ASTC_6X5_KHR),
+        164 => Some(COMPRESSED_SRGB8_ALPHA8_ASTC_6X5_KHR),
+        165 => Some(COMPRESSED_RGBA_ASTC_6X6_KHR),
+        166 => Some(COMPRESSED_SRGB8_ALPHA8_ASTC_6X6_KHR),
+        167 => Some(COMPRESSED_RGBA_ASTC_8X5_KHR),
+        168 => Some(COMPRESSED_SRGB8_ALPHA8_ASTC_8X5_KHR),
+        169 => Some(COMPRESSED_RGBA_ASTC_8X6_KHR),
+        170 => Some(COMPRESSED_SRGB8_ALPHA8_ASTC_8X6_KHR),
+        171 => Some(COMPRESSED_RGBA_ASTC_8X8_KHR),
+        172 => Some(COMPRESSED_SRGB8_ALPHA8_ASTC_8X8_KHR),
+        _ => None,
+    }
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32, String> {
+    data.get(offset..offset + 4)
+        .map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()))
+        .ok_or_else(|| "KTX2: Unexpected end of data".to_owned())
+}
+
+fn read_u64(data: &[u8], offset: usize) -> Result<u64, String> {
+    data.get(offset..offset + 8)
+        .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+        .ok_or_else(|| "KTX2: Unexpected end of data".to_owned())
+}
+
+/// Single mipmap level of a compressed image.
+#[derive(Clone, Debug)]
+pub struct Level {
+    pub width: u32,
+    pub height: u32,
+    pub data: Vec<u8>,
+}
+
+/// Two-dimensional image holding a GPU-compressed payload read from a KTX2 container.
+#[derive(Clone, Debug)]
+pub struct CompressedImage {
+    format: gl::types::GLenum,
+    levels: Vec<Level>,
+}
+
+impl CompressedImage {
+    /// Parses contents of a KTX2 file. Only 2D images without supercompression holding ETC2 or
+    /// ASTC payloads are supported.
+    pub fn parse(data: &[u8]) -> Result<Self, String> {
+        if data.len() < KTX2_HEADER_SIZE || data[0..12] != KTX2_IDENTIFIER {
+            return Err("KTX2: Invalid identifier".to_owned());
+        }
+
+        let vk_format = read_u32(data, 12)?;
+        let width = read_u32(data, 20)?;
+        let height = read_u32(data, 24)?;
+        let depth = read_u32(data, 28)?;
+        let layer_count = read_u32(data, 32)?;
+        let face_count = read_u32(data, 36)?;
+        let level_count = read_u32(data, 40)?.max(1);
+        let supercompression = read_u32(data, 44)?;
+
+        let format = vk_format_to_gl(vk_format)
+            .ok_or_else(|| format!("KTX2: Unsupported format {}", vk_format))?;
+        if depth > 0 || layer_count > 0 || face_count != 1 {
+            return Err("KTX2: Only 2D images are supported".to_owned());
+        }
+        if supercompression != KTX2_SUPERCOMPRESSION_NONE {
+            return Err("KTX2: Supercompression is not supported".to_owned());
+        }
+
+        // The level index has to fit in the data, which also bounds the number of levels
+        let max_level_count = (data.len() - KTX2_HEADER_SIZE) / KTX2_LEVEL_INDEX_ENTRY_SIZE;
+        if level_count as usize > max_level_count {
+            return Err("KTX2: Level index out of bounds".to_owned());
+        }
+
+        let mut levels = Vec::with_capacity(level_count as usize);
+        for level in 0..level_count {
+            let entry = KTX2_HEADER_SIZE + KTX2_LEVEL_INDEX_ENTRY_SIZE * level as usize;
+            let offset = read_u64(data, entry)? as usize;
+            let length = read_u64(data, entry + 8)? as usize;
+            let level_data = offset
+                .checked_add(length)
+                .and_then(|end| data.get(offset..end))
+                .ok_or_else(|| "KTX2: Level data out of bounds".to_owned())?;
+            levels.push(Level {
+                width: width.checked_shr(level).unwrap_or(0).max(1),
+                height: height.checked_shr(level).unwrap_or(0).max(1),
+                data: level_data.to_vec(),
+            });
+        }
+
+        Ok(Self { format, levels })
+    }
+
+    pub fn get_format(&self) -> gl::types::GLenum {
+        self.format
+    }
+
+    pub fn get_levels(&self) -> &Vec<Level> {
+        &self.levels
+    }
+}
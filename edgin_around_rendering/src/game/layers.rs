@@ -0,0 +1,139 @@
+use std::{fs::File, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::utils::errors as err;
+
+/// Name of the file in the tiles directory declaring the terrain layers.
+pub const LAYERS_FILE: &str = "layers.yaml";
+
+/// Maximal number of layers the ground shader can blend.
+pub const MAX_LAYERS: usize = 4;
+
+/// Bound used in place of unspecified height and slope limits.
+pub const UNBOUNDED: f32 = 1.0e9;
+
+const DEFAULT_TEXTURE: &str = "grass";
+const DEFAULT_HEIGHT_BLEND: f32 = 0.5;
+const DEFAULT_SLOPE_BLEND: f32 = 0.1;
+
+fn default_height_blend() -> f32 {
+    DEFAULT_HEIGHT_BLEND
+}
+
+fn default_slope_blend() -> f32 {
+    DEFAULT_SLOPE_BLEND
+}
+
+/// Single texture of the ground used within given ranges of height and slope.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TerrainLayer {
+    /// Name of the texture file in the tiles directory, without the extension.
+    pub texture: String,
+
+    /// Lowest height above the sea level where the layer is used.
+    #[serde(default)]
+    pub min_height: Option<f32>,
+
+    /// Highest height above the sea level where the layer is used.
+    #[serde(default)]
+    pub max_height: Option<f32>,
+
+    /// Lowest angle in radians between the surface and the horizontal plane.
+    #[serde(default)]
+    pub min_slope: Option<f32>,
+
+    /// Highest angle in radians between the surface and the horizontal plane.
+    #[serde(default)]
+    pub max_slope: Option<f32>,
+}
+
+impl TerrainLayer {
+    pub fn new(texture: &str) -> Self {
+        Self {
+            texture: texture.to_owned(),
+            min_height: None,
+            max_height: None,
+            min_slope: None,
+            max_slope: None,
+        }
+    }
+
+    /// Returns the height and slope ranges as `(min_height, max_height, min_slope, max_slope)`
+    /// with unspecified limits replaced by `UNBOUNDED`.
+    pub fn get_ranges(&self) -> (f32, f32, f32, f32) {
+        (
+            self.min_height.unwrap_or(-UNBOUNDED),
+            self.max_height.unwrap_or(UNBOUNDED),
+            self.min_slope.unwrap_or(-UNBOUNDED),
+            self.max_slope.unwrap_or(UNBOUNDED),
+        )
+    }
+}
+
+/// List of ground textures blended depending on the height and slope of the terrain.
+///
+/// The layers are declared in a `layers.yaml` file in the tiles directory. Heights are measured
+/// from the sea level (see `ElevationFunction::get_sea_level`) and slopes in radians. Weights of
+/// layers fade linearly within `height_blend` and `slope_blend` around their limits, e.g.:
+///
+/// ```yaml
+/// height_blend: 0.5
+/// slope_blend: 0.1
+/// layers:
+///   - texture: sand
+///     max_height: 0.5
+///   - texture: grass
+///     min_height: 0.5
+///     max_height: 6.0
+///     max_slope: 0.6
+///   - texture: rock
+///     min_slope: 0.6
+///   - texture: snow
+///     min_height: 6.0
+/// ```
+///
+/// Without the file the whole ground is covered with grass.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TerrainLayers {
+    #[serde(default = "default_height_blend")]
+    pub height_blend: f32,
+
+    #[serde(default = "default_slope_blend")]
+    pub slope_blend: f32,
+
+    pub layers: Vec<TerrainLayer>,
+}
+
+impl TerrainLayers {
+    /// Reads layers from the file in the given directory if the file exists.
+    pub fn load(dir: &Path) -> Option<Self> {
+        let path = dir.join(LAYERS_FILE);
+        if path.is_file() {
+            let file =
+                File::open(&path).unwrap_or_else(|_| panic!("{}: {:?}", err::FILE_FAILED, path));
+            let mut layers: Self = serde_yaml::from_reader(&file).expect(err::YAML_FAILED);
+            if layers.layers.is_empty() {
+                log::warn!("No terrain layers declared: {:?}", path);
+                return None;
+            }
+            if layers.layers.len() > MAX_LAYERS {
+                log::warn!("Only {} terrain layers are supported: {:?}", MAX_LAYERS, path);
+                layers.layers.truncate(MAX_LAYERS);
+            }
+            Some(layers)
+        } else {
+            None
+        }
+    }
+}
+
+impl Default for TerrainLayers {
+    fn default() -> Self {
+        Self {
+            height_blend: DEFAULT_HEIGHT_BLEND,
+            slope_blend: DEFAULT_SLOPE_BLEND,
+            layers: vec![TerrainLayer::new(DEFAULT_TEXTURE)],
+        }
+    }
+}
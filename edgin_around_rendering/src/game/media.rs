@@ -4,17 +4,26 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use crate::utils::{
-    errors as err,
-    ids::{MediumId, ResourceId, ResourcePath, TextureId, NO_TEXTURE},
+use crate::{
+    game::{
+        ktx::{CompressedImage, KTX2_EXTENSION},
+        layers::TerrainLayers,
+        sampling::{self, Sampling},
+    },
+    utils::{
+        errors as err, graphics,
+        ids::{MediumId, ResourceId, ResourcePath, TextureId, NO_TEXTURE},
+    },
 };
 
 const TILES_DIR: &str = "tiles";
 const SPRITES_DIR: &str = "sprites";
-const GRASS_FILE: &str = "grass.png";
+const DECALS_DIR: &str = "decals";
+const PATHS_DIR: &str = "paths";
 const WATER_FILE: &str = "water.png";
+const PNG_EXTENSION: &str = "png";
 
-fn load_image(path: &Path) -> TextureId {
+fn load_image(path: &Path, sampling: &Sampling, max_anisotropy: Option<f32>) -> TextureId {
     let file = File::open(path).expect(&format!("{}: {:?}", err::FILE_FAILED, path));
     let decoder = png::Decoder::new(file);
     let (info, mut reader) = decoder.read_info().expect(err::PNG_FAILED);
@@ -25,8 +34,6 @@ fn load_image(path: &Path) -> TextureId {
     unsafe {
         gl::GenTextures(1, &mut texture_id);
         gl::BindTexture(gl::TEXTURE_2D, texture_id);
-        gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MAG_FILTER, gl::LINEAR as gl::types::GLint);
-        gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MIN_FILTER, gl::LINEAR as gl::types::GLint);
         gl::TexImage2D(
             gl::TEXTURE_2D,
             0,
@@ -38,36 +45,197 @@ fn load_image(path: &Path) -> TextureId {
             gl::UNSIGNED_BYTE,
             buf.as_ptr() as *const _,
         );
+        sampling.apply(max_anisotropy);
     }
     texture_id
 }
 
+fn load_compressed_image(
+    path: &Path,
+    sampling: &Sampling,
+    max_anisotropy: Option<f32>,
+) -> Option<TextureId> {
+    let data = match std::fs::read(path) {
+        Ok(data) => data,
+        Err(error) => {
+            log::warn!("{}: {:?}: {}", err::FILE_FAILED, path, error);
+            return None;
+        }
+    };
+    let image = match CompressedImage::parse(&data) {
+        Ok(image) => image,
+        Err(error) => {
+            log::warn!("{}: {:?}", error, path);
+            return None;
+        }
+    };
+
+    if !graphics::has_compressed_format(image.get_format()) {
+        log::info!("Compressed format {:#x} not supported: {:?}", image.get_format(), path);
+        return None;
+    }
+
+    let mut texture_id = 0;
+    unsafe {
+        gl::GenTextures(1, &mut texture_id);
+        gl::BindTexture(gl::TEXTURE_2D, texture_id);
+        for (i, level) in image.get_levels().iter().enumerate() {
+            gl::CompressedTexImage2D(
+                gl::TEXTURE_2D,
+                i as gl::types::GLint,
+                image.get_format(),
+                level.width as gl::types::GLsizei,
+                level.height as gl::types::GLsizei,
+                0,
+                level.data.len() as gl::types::GLsizei,
+                level.data.as_ptr() as *const _,
+            );
+        }
+        sampling.apply_to_levels(image.get_levels().len(), max_anisotropy);
+    }
+    Some(texture_id)
+}
+
+/// Loads a texture preferring a KTX2 container placed next to the PNG file. Falls back to the PNG
+/// file if the container does not exist or the driver does not support its format.
+fn load_texture(path: &Path, sampling: &Sampling, max_anisotropy: Option<f32>) -> TextureId {
+    let compressed_path = path.with_extension(KTX2_EXTENSION);
+    if compressed_path.is_file() {
+        if let Some(texture_id) = load_compressed_image(&compressed_path, sampling, max_anisotropy)
+        {
+            return texture_id;
+        }
+    }
+    load_image(&path.with_extension(PNG_EXTENSION), sampling, max_anisotropy)
+}
+
+fn texture_exists(path: &Path) -> bool {
+    path.with_extension(PNG_EXTENSION).is_file() || path.with_extension(KTX2_EXTENSION).is_file()
+}
+
 pub fn sprites_path(base: &Path) -> PathBuf {
     base.join(SPRITES_DIR)
 }
 
 pub struct Textures {
     pub water: TextureId,
-    pub grass: TextureId,
+    pub layers: TerrainLayers,
+    pub layer_textures: Vec<TextureId>,
+    decal_textures: HashMap<String, TextureId>,
+    path_textures: HashMap<String, TextureId>,
+    particle_textures: HashMap<String, TextureId>,
+    max_anisotropy: Option<f32>,
 }
 
 impl Textures {
     pub fn load(resource_dir: &Path) -> Self {
-        let water_path = resource_dir.join(TILES_DIR).join(WATER_FILE);
-        let grass_path = resource_dir.join(TILES_DIR).join(GRASS_FILE);
+        let tiles_dir = resource_dir.join(TILES_DIR);
+        let water_path = tiles_dir.join(WATER_FILE);
+
+        let pack_sampling = Sampling::load(resource_dir).unwrap_or_default();
+        let sampling = Sampling::load_or(&tiles_dir, &pack_sampling);
+        let max_anisotropy = sampling::query_max_anisotropy();
+
+        let layers = TerrainLayers::load(&tiles_dir).unwrap_or_default();
+        let layer_textures = layers
+            .layers
+            .iter()
+            .map(|layer| load_texture(&tiles_dir.join(&layer.texture), &sampling, max_anisotropy))
+            .collect();
 
-        Self { water: load_image(water_path.as_path()), grass: load_image(grass_path.as_path()) }
+        Self {
+            water: load_texture(water_path.as_path(), &sampling, max_anisotropy),
+            layers,
+            layer_textures,
+            decal_textures: HashMap::new(),
+            path_textures: HashMap::new(),
+            particle_textures: HashMap::new(),
+            max_anisotropy,
+        }
+    }
+
+    /// Returns the texture of decals with the given name, loading it from the decals directory
+    /// of the resources when needed. Returns `NO_TEXTURE` if the texture does not exist.
+    pub fn get_decal_texture(&mut self, resource_dir: &Path, name: &str) -> TextureId {
+        let cache = &mut self.decal_textures;
+        get_overlay_texture(cache, resource_dir, DECALS_DIR, name, self.max_anisotropy)
+    }
+
+    /// Returns the texture of paths with the given name, loading it from the paths directory of
+    /// the resources when needed. Returns `NO_TEXTURE` if the texture does not exist.
+    pub fn get_path_texture(&mut self, resource_dir: &Path, name: &str) -> TextureId {
+        let cache = &mut self.path_textures;
+        get_overlay_texture(cache, resource_dir, PATHS_DIR, name, self.max_anisotropy)
+    }
+
+    /// Returns the texture of particles with the given name, loading it from the sprites
+    /// directory of the resources when needed. Returns `NO_TEXTURE` if the texture does not exist.
+    pub fn get_particle_texture(&mut self, resource_dir: &Path, name: &str) -> TextureId {
+        let cache = &mut self.particle_textures;
+        get_overlay_texture(cache, resource_dir, SPRITES_DIR, name, self.max_anisotropy)
     }
 }
 
+/// Returns the texture with the given name from the cache, loading it from the directory of the
+/// resources with the given name when needed.
+fn get_overlay_texture(
+    cache: &mut HashMap<String, TextureId>,
+    resource_dir: &Path,
+    dir_name: &str,
+    name: &str,
+    max_anisotropy: Option<f32>,
+) -> TextureId {
+    if let Some(texture_id) = cache.get(name) {
+        return *texture_id;
+    }
+
+    let dir = resource_dir.join(dir_name);
+    let path = dir.join(name);
+    let texture_id = if texture_exists(&path) {
+        let pack_sampling = Sampling::load(resource_dir).unwrap_or_default();
+        load_texture(&path, &Sampling::load_or(&dir, &pack_sampling), max_anisotropy)
+    } else {
+        log::warn!("Texture '{}' not found in '{}'", name, dir_name);
+        NO_TEXTURE
+    };
+    cache.insert(name.to_owned(), texture_id);
+    texture_id
+}
+
 impl Default for Textures {
     fn default() -> Self {
-        Self { water: NO_TEXTURE, grass: NO_TEXTURE }
+        Self {
+            water: NO_TEXTURE,
+            layers: TerrainLayers::default(),
+            layer_textures: Vec::new(),
+            decal_textures: HashMap::new(),
+            path_textures: HashMap::new(),
+            particle_textures: HashMap::new(),
+            max_anisotropy: None,
+        }
+    }
+}
+
+impl Drop for Textures {
+    fn drop(&mut self) {
+        let ids = std::iter::once(&self.water)
+            .chain(self.layer_textures.iter())
+            .chain(self.decal_textures.values())
+            .chain(self.path_textures.values())
+            .chain(self.particle_textures.values());
+        for texture_id in ids.filter(|id| **id != NO_TEXTURE) {
+            unsafe { gl::DeleteTextures(1, texture_id) };
+        }
     }
 }
 
 pub struct Sprites {
     sprites_dir: PathBuf,
+    sampling: Sampling,
+
+    /// Queried when the first skin is loaded, as the GL context may not exist earlier.
+    max_anisotropy: Option<Option<f32>>,
+
     id_map: HashMap<ResourcePath, ResourceId>,
     skins: Vec<Vec<TextureId>>,
     loaded_skins: HashMap<String, MediumId>,
@@ -75,8 +243,12 @@ pub struct Sprites {
 
 impl Sprites {
     pub fn new(sprites_dir: PathBuf) -> Self {
+        let pack_sampling = sprites_dir.parent().and_then(Sampling::load).unwrap_or_default();
+        let sampling = Sampling::load_or(&sprites_dir, &pack_sampling);
         Self {
             sprites_dir: sprites_dir,
+            sampling,
+            max_anisotropy: None,
             id_map: HashMap::new(),
             skins: Vec::new(),
             loaded_skins: HashMap::new(),
@@ -103,11 +275,14 @@ impl Sprites {
         let skin_dir = Path::new(skin_name);
         let skin_path = self.sprites_dir.join(skin_dir);
         let skin_id = self.skins.len();
+        let sampling = Sampling::load_or(&skin_path, &self.sampling);
+        let max_anisotropy =
+            *self.max_anisotropy.get_or_insert_with(sampling::query_max_anisotropy);
         let mut skin = Vec::new();
         for (image_id, &image_name) in image_names.iter().enumerate() {
-            let image_path = skin_path.join(image_name).with_extension("png");
-            if image_path.is_file() {
-                let texture_id = load_image(&image_path);
+            let image_path = skin_path.join(image_name).with_extension(PNG_EXTENSION);
+            if texture_exists(&image_path) {
+                let texture_id = load_texture(&image_path, &sampling, max_anisotropy);
                 let resource_id = ResourceId::new(skin_id, image_id);
                 let key = (skin_name.to_string(), image_name.to_string());
                 skin.push(texture_id);
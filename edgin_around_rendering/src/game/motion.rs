@@ -0,0 +1,192 @@
+use std::collections::VecDeque;
+
+use crate::{game::Actor, utils::coordinates::Point};
+
+const DEFAULT_DELAY: f64 = 0.2;
+const DEFAULT_MAX_EXTRAPOLATION: f64 = 0.0;
+const DEFAULT_SNAP_DISTANCE: f32 = 10.0;
+
+/// Parameters of smoothing of actor movement driven by timestamped position updates.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MotionSmoothing {
+    /// Time in seconds by which rendering lags behind the latest update, so that there usually
+    /// are two states to interpolate between.
+    pub delay: f64,
+
+    /// Longest time in seconds for which movement is extrapolated from the last velocity when no
+    /// newer update arrived. Zero disables the extrapolation.
+    pub max_extrapolation: f64,
+
+    /// Distance in world units beyond which a new position is treated as a teleport and shown
+    /// immediately.
+    pub snap_distance: f32,
+}
+
+impl MotionSmoothing {
+    pub fn new(delay: f64, max_extrapolation: f64, snap_distance: f32) -> Self {
+        Self { delay, max_extrapolation, snap_distance }
+    }
+}
+
+impl Default for MotionSmoothing {
+    fn default() -> Self {
+        Self::new(DEFAULT_DELAY, DEFAULT_MAX_EXTRAPOLATION, DEFAULT_SNAP_DISTANCE)
+    }
+}
+
+#[derive(Clone, Debug)]
+struct MotionState {
+    position: Point,
+    timestamp: f64,
+}
+
+/// Known states of a moving actor ordered by time. Enough states are kept to cover the smoothing
+/// delay, so that the rendered position can be interpolated even if updates come more often than
+/// the delay.
+#[derive(Clone, Debug)]
+pub struct ActorMotion {
+    states: VecDeque<MotionState>,
+}
+
+impl ActorMotion {
+    pub fn new(position: Point, timestamp: f64) -> Self {
+        let mut states = VecDeque::new();
+        states.push_back(MotionState { position, timestamp });
+        Self { states }
+    }
+
+    /// Adds a new state. Updates older than the last one are ignored. Returns false if the new
+    /// position is farther than the snap distance, in which case the motion should be restarted.
+    /// States older than needed to render the actor `delay` seconds behind the new one are
+    /// dropped.
+    pub fn push(
+        &mut self,
+        position: Point,
+        timestamp: f64,
+        smoothing: &MotionSmoothing,
+        radius: f32,
+    ) -> bool {
+        let last = self.get_last_state();
+        if timestamp <= last.timestamp {
+            return true;
+        }
+
+        let distance = Point::great_circle_distance(&last.position, &position, radius);
+        if distance > smoothing.snap_distance {
+            return false;
+        }
+
+        self.states.push_back(MotionState { position, timestamp });
+
+        // Keep the last state not newer than the oldest time which may still be rendered
+        let oldest = timestamp - smoothing.delay;
+        while self.states.len() > 2 && self.states[1].timestamp <= oldest {
+            self.states.pop_front();
+        }
+        true
+    }
+
+    pub fn get_last_position(&self) -> &Point {
+        &self.get_last_state().position
+    }
+
+    /// Returns the position at the given time, interpolated along the great circle between the
+    /// states around it or extrapolated beyond the last one.
+    pub fn evaluate(&self, time: f64, smoothing: &MotionSmoothing, radius: f32) -> Point {
+        let first = &self.states[0];
+        let last = self.get_last_state();
+        if time <= first.timestamp {
+            return first.position.clone();
+        }
+
+        if time <= last.timestamp {
+            let next = self.states.iter().position(|state| state.timestamp >= time).unwrap_or(0);
+            let (previous, next) = (&self.states[next - 1], &self.states[next]);
+            let fraction =
+                ((time - previous.timestamp) / (next.timestamp - previous.timestamp)) as f32;
+            return Point::interpolate(&previous.position, &next.position, fraction);
+        }
+
+        let count = self.states.len();
+        if count > 1 && smoothing.max_extrapolation > 0.0 {
+            let previous = &self.states[count - 2];
+            let duration = last.timestamp - previous.timestamp;
+            let elapsed = (time - last.timestamp).min(smoothing.max_extrapolation);
+            let distance = Point::great_circle_distance(&previous.position, &last.position, radius);
+            let bearing = Point::final_bearing(&previous.position, &last.position);
+            let travelled = distance * (elapsed / duration) as f32;
+            Point::destination(&last.position, travelled, bearing, radius)
+        } else {
+            last.position.clone()
+        }
+    }
+}
+
+impl ActorMotion {
+    fn get_last_state(&self) -> &MotionState {
+        &self.states[self.states.len() - 1]
+    }
+}
+
+/// List of points an actor walks through with a constant speed along great-circle segments.
+#[derive(Clone, Debug)]
+pub struct ActorPath {
+    waypoints: VecDeque<Point>,
+    speed: f32,
+    time: Option<f64>,
+}
+
+impl ActorPath {
+    /// Creates a path with `speed` given in world units per second.
+    pub fn new(waypoints: Vec<Point>, speed: f32) -> Self {
+        Self { waypoints: waypoints.into(), speed, time: None }
+    }
+
+    pub fn get_waypoints(&self) -> &VecDeque<Point> {
+        &self.waypoints
+    }
+
+    pub fn get_speed(&self) -> f32 {
+        self.speed
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.waypoints.is_empty()
+    }
+
+    /// Moves the actor towards the next waypoints by the distance covered since the previous
+    /// call. The first call only remembers the time. Returns the bearing of the last step, if the
+    /// actor moved.
+    pub fn advance(&mut self, actor: &mut Actor, time: f64, radius: f32) -> Option<f32> {
+        let elapsed = match self.time.replace(time) {
+            Some(previous) => (time - previous).max(0.0) as f32,
+            None => 0.0,
+        };
+
+        let mut remaining = self.speed * elapsed;
+        let mut bearing = None;
+        while remaining > 0.0 {
+            let (position, waypoint) = match (actor.get_position(), self.waypoints.front()) {
+                (Some(position), Some(waypoint)) => (position.clone(), waypoint.clone()),
+                _ => break,
+            };
+
+            let distance = Point::great_circle_distance(&position, &waypoint, radius);
+            let step_bearing = Point::initial_bearing(&position, &waypoint);
+            if distance > 0.0 {
+                bearing = Some(step_bearing);
+            }
+
+            if distance <= remaining {
+                actor.face_along(&position, &waypoint);
+                actor.set_position(waypoint);
+                self.waypoints.pop_front();
+                remaining -= distance;
+            } else {
+                actor.move_by(remaining, step_bearing, radius);
+                remaining = 0.0;
+            }
+        }
+        bearing
+    }
+}
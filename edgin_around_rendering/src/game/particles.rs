@@ -0,0 +1,309 @@
+use std::{f32::consts::PI, fs::File, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::utils::{
+    coordinates::{Point, Point3D},
+    errors as err,
+    ids::ActorId,
+};
+
+/// Name of the directory in the resources containing emitter definitions.
+pub const PARTICLES_DIR: &str = "particles";
+
+const EMITTER_EXTENSION: &str = "yaml";
+const DEFAULT_MAX_PARTICLES: usize = 256;
+const DEFAULT_SIZE: f32 = 0.2;
+const DEFAULT_COLOR: [f32; 4] = [1.0, 1.0, 1.0, 1.0];
+const RANDOM_SEED: u32 = 0x9e37_79b9;
+
+fn default_max_particles() -> usize {
+    DEFAULT_MAX_PARTICLES
+}
+
+fn default_size() -> Curve<f32> {
+    Curve::constant(DEFAULT_SIZE)
+}
+
+fn default_color() -> Curve<[f32; 4]> {
+    Curve::constant(DEFAULT_COLOR)
+}
+
+/// Value which can be blended linearly with another one.
+pub trait Blend: Clone + Default {
+    fn blend(&self, other: &Self, fraction: f32) -> Self;
+}
+
+impl Blend for f32 {
+    fn blend(&self, other: &Self, fraction: f32) -> Self {
+        self + (other - self) * fraction
+    }
+}
+
+impl Blend for [f32; 4] {
+    fn blend(&self, other: &Self, fraction: f32) -> Self {
+        let mut result = [0.0; 4];
+        for (i, component) in result.iter_mut().enumerate() {
+            *component = self[i].blend(&other[i], fraction);
+        }
+        result
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CurveKey<T> {
+    /// Fraction of the lifetime of a particle within `[0, 1]`.
+    pub at: f32,
+    pub value: T,
+}
+
+/// Value changing over the lifetime of a particle. Keys have to be sorted by their moments. The
+/// value is blended between neighbouring keys and kept constant before the first and after the
+/// last one.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Curve<T> {
+    pub keys: Vec<CurveKey<T>>,
+}
+
+impl<T: Blend> Curve<T> {
+    pub fn constant(value: T) -> Self {
+        Self { keys: vec![CurveKey { at: 0.0, value }] }
+    }
+
+    /// Returns the value at the given fraction of the lifetime, or the default value if the curve
+    /// has no keys.
+    pub fn sample(&self, at: f32) -> T {
+        let next = self.keys.iter().position(|key| key.at > at);
+        match next {
+            Some(0) => self.keys[0].value.clone(),
+            Some(i) => {
+                let (previous, next) = (&self.keys[i - 1], &self.keys[i]);
+                let fraction = (at - previous.at) / (next.at - previous.at);
+                previous.value.blend(&next.value, fraction)
+            }
+            None => self.keys.last().map(|key| key.value.clone()).unwrap_or_default(),
+        }
+    }
+}
+
+/// Definition of particles spawned by an emitter, like smoke, sparks, dust or magic.
+///
+/// Emitters are declared in YAML files in the particles directory of the resources. Distances are
+/// given in world units and times in seconds, e.g.:
+///
+/// ```yaml
+/// texture: smoke
+/// spawn_rate: 10.0
+/// lifetime: 2.0
+/// speed: 0.5
+/// spread: 0.3
+/// gravity: -0.2
+/// size:
+///   - { at: 0.0, value: 0.2 }
+///   - { at: 1.0, value: 0.8 }
+/// color:
+///   - { at: 0.0, value: [0.5, 0.5, 0.5, 0.8] }
+///   - { at: 1.0, value: [0.5, 0.5, 0.5, 0.0] }
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EmitterSpec {
+    /// Name of the texture in the sprites directory of the resources, without extension.
+    /// Particles without a texture are drawn as soft round dots.
+    #[serde(default)]
+    pub texture: Option<String>,
+
+    /// Number of particles spawned per second.
+    pub spawn_rate: f32,
+
+    /// Number of particles spawned at once when the emitter starts.
+    #[serde(default)]
+    pub burst: usize,
+
+    /// Time after which the emitter stops spawning particles. It never stops if not given.
+    #[serde(default)]
+    pub duration: Option<f32>,
+
+    pub lifetime: f32,
+
+    /// Initial speed of particles.
+    pub speed: f32,
+
+    /// Half of the apex angle in radians of the cone around the upward direction in which
+    /// particles start moving.
+    #[serde(default)]
+    pub spread: f32,
+
+    /// Acceleration towards the center of the planet. Negative gravity makes particles rise.
+    #[serde(default)]
+    pub gravity: f32,
+
+    /// Diameter of particles over their lifetime.
+    #[serde(default = "default_size")]
+    pub size: Curve<f32>,
+
+    /// Color of particles over their lifetime, given as RGBA components within `[0, 1]`.
+    #[serde(default = "default_color")]
+    pub color: Curve<[f32; 4]>,
+
+    /// Limit of particles alive at once.
+    #[serde(default = "default_max_particles")]
+    pub max_particles: usize,
+}
+
+impl EmitterSpec {
+    /// Reads the emitter with the given name from the particles directory. Fails if the file does
+    /// not exist or is not a valid emitter definition.
+    pub fn load(dir: &Path, name: &str) -> Result<Self, String> {
+        let path = dir.join(name).with_extension(EMITTER_EXTENSION);
+        let file =
+            File::open(&path).map_err(|e| format!("{}: {:?}: {}", err::FILE_FAILED, path, e))?;
+        serde_yaml::from_reader(&file)
+            .map_err(|e| format!("{}: {:?}: {}", err::YAML_FAILED, path, e))
+    }
+}
+
+/// Place from which an emitter spawns particles.
+#[derive(Clone, Debug)]
+pub enum EmitterAnchor {
+    /// Point on the ground.
+    Point(Point),
+
+    /// Feet of the sprite of the actor.
+    Actor(ActorId),
+
+    /// Bone or hook of the sprite of the actor with the given name, e.g. the tip of a torch.
+    Hook(ActorId, String),
+}
+
+#[derive(Clone, Debug)]
+pub struct Particle {
+    pub position: Point3D,
+    pub velocity: Point3D,
+    pub age: f32,
+}
+
+/// Spawns particles and moves them. Positions are given in world space.
+#[derive(Clone, Debug)]
+pub struct ParticleEmitter {
+    spec: EmitterSpec,
+    anchor: EmitterAnchor,
+    particles: Vec<Particle>,
+    elapsed: f32,
+    pending: f32,
+    started: bool,
+    random: u32,
+}
+
+impl ParticleEmitter {
+    /// Creates a new emitter. Emitters with different seeds spread their particles differently.
+    pub fn new(spec: EmitterSpec, anchor: EmitterAnchor, seed: u64) -> Self {
+        // Mix all bits of the seed into the state, which must never be zero
+        let mixed = seed.wrapping_mul(0x9e37_79b9_7f4a_7c15);
+        let random = match (mixed ^ (mixed >> 32)) as u32 ^ RANDOM_SEED {
+            0 => RANDOM_SEED,
+            random => random,
+        };
+        Self {
+            spec,
+            anchor,
+            particles: Vec::new(),
+            elapsed: 0.0,
+            pending: 0.0,
+            started: false,
+            random,
+        }
+    }
+
+    pub fn get_spec(&self) -> &EmitterSpec {
+        &self.spec
+    }
+
+    pub fn get_anchor(&self) -> &EmitterAnchor {
+        &self.anchor
+    }
+
+    pub fn get_particles(&self) -> &[Particle] {
+        &self.particles
+    }
+
+    /// Returns the size and color of the particle according to its age.
+    pub fn get_look(&self, particle: &Particle) -> (f32, [f32; 4]) {
+        let at = if self.spec.lifetime > 0.0 { particle.age / self.spec.lifetime } else { 1.0 };
+        (self.spec.size.sample(at), self.spec.color.sample(at))
+    }
+
+    /// Tells if the emitter stopped spawning particles and all of them died.
+    pub fn is_finished(&self) -> bool {
+        let stopped = self.spec.duration.is_some_and(|duration| self.elapsed >= duration);
+        stopped && self.particles.is_empty()
+    }
+
+    /// Advances the particles by `dt` seconds and spawns new ones at the `origin` moving around
+    /// the `up` direction, which has to be a unit vector. Nothing is spawned without an origin,
+    /// e.g. while the anchor is not shown; the time still passes.
+    pub fn update(&mut self, dt: f32, origin: Option<(&Point3D, &Point3D)>) {
+        let gravity = self.spec.gravity;
+        for particle in self.particles.iter_mut() {
+            particle.age += dt;
+            if gravity != 0.0 && particle.position.length() > 0.0 {
+                let acceleration = particle.position.enlongated(-gravity * dt);
+                particle.velocity = &particle.velocity + &acceleration;
+            }
+            particle.position = &particle.position + &particle.velocity.scaled(dt);
+        }
+        let lifetime = self.spec.lifetime;
+        self.particles.retain(|particle| particle.age < lifetime);
+
+        // Spawn particles for the part of the step before the emitter stops
+        let active = match self.spec.duration {
+            Some(duration) => (duration - self.elapsed).clamp(0.0, dt),
+            None => dt,
+        };
+        self.elapsed += dt;
+        self.pending += self.spec.spawn_rate * active;
+        let mut count = self.pending.floor();
+        self.pending -= count;
+
+        if let Some((origin, up)) = origin {
+            if !self.started {
+                self.started = true;
+                count += self.spec.burst as f32;
+            }
+            for _ in 0..count as usize {
+                if self.particles.len() >= self.spec.max_particles {
+                    break;
+                }
+                let velocity = self.prepare_velocity(up);
+                self.particles.push(Particle { position: origin.clone(), velocity, age: 0.0 });
+            }
+        }
+    }
+}
+
+impl ParticleEmitter {
+    /// Picks a direction uniformly from the cone around `up` and scales it to the speed.
+    fn prepare_velocity(&mut self, up: &Point3D) -> Point3D {
+        let axis = if up.x.abs() < 0.5 {
+            Point3D::new(1.0, 0.0, 0.0)
+        } else {
+            Point3D::new(0.0, 1.0, 0.0)
+        };
+        let u = up.cross(&axis).enlongated(1.0);
+        let v = up.cross(&u);
+
+        let cos_polar = 1.0 - self.next_random() * (1.0 - self.spec.spread.cos());
+        let sin_polar = (1.0 - cos_polar * cos_polar).max(0.0).sqrt();
+        let (sin, cos) = (2.0 * PI * self.next_random()).sin_cos();
+        let sideways = &u.scaled(sin_polar * cos) + &v.scaled(sin_polar * sin);
+        (&up.scaled(cos_polar) + &sideways).scaled(self.spec.speed)
+    }
+
+    /// Returns a pseudo-random number within `[0, 1)`.
+    fn next_random(&mut self) -> f32 {
+        self.random ^= self.random << 13;
+        self.random ^= self.random >> 17;
+        self.random ^= self.random << 5;
+        (self.random >> 8) as f32 / (1 << 24) as f32
+    }
+}
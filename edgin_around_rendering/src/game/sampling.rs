@@ -0,0 +1,153 @@
+use std::{fs::File, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::utils::{errors as err, graphics};
+
+/// Name of the sidecar file describing sampling of textures in a directory.
+pub const SAMPLING_FILE: &str = "sampling.yaml";
+
+const EXTENSION_ANISOTROPIC: &str = "GL_EXT_texture_filter_anisotropic";
+const TEXTURE_MAX_ANISOTROPY_EXT: gl::types::GLenum = 0x84FE;
+const MAX_TEXTURE_MAX_ANISOTROPY_EXT: gl::types::GLenum = 0x84FF;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Filter {
+    Nearest,
+    Linear,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Wrap {
+    Repeat,
+    MirroredRepeat,
+    ClampToEdge,
+}
+
+/// Describes how textures are sampled.
+///
+/// Sampling can be declared in a `sampling.yaml` file placed in the resource directory, the sprites
+/// directory, the tiles directory or a skin directory. All fields are optional, e.g.:
+///
+/// ```yaml
+/// filter: nearest
+/// mipmaps: true
+/// anisotropy: 4.0
+/// wrap: clamp_to_edge
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Sampling {
+    /// Filter used for both minification and magnification.
+    pub filter: Filter,
+
+    /// Tells if mipmaps should be generated and used for minification.
+    pub mipmaps: bool,
+
+    /// Requested level of anisotropic filtering. Ignored if not supported by the driver.
+    pub anisotropy: Option<f32>,
+
+    /// Wrap mode used in both texture directions.
+    pub wrap: Wrap,
+}
+
+impl Sampling {
+    /// Reads sampling from the sidecar file in the given directory if the file exists. Files which
+    /// cannot be read or parsed are reported and ignored.
+    pub fn load(dir: &Path) -> Option<Self> {
+        let path = dir.join(SAMPLING_FILE);
+        if !path.is_file() {
+            return None;
+        }
+        let result = File::open(&path)
+            .map_err(|e| format!("{}: {}", err::FILE_FAILED, e))
+            .and_then(|file| {
+                serde_yaml::from_reader(&file).map_err(|e| format!("{}: {}", err::YAML_FAILED, e))
+            });
+        match result {
+            Ok(sampling) => Some(sampling),
+            Err(error) => {
+                log::warn!("{}: {:?}", error, path);
+                None
+            }
+        }
+    }
+
+    /// Reads sampling from the sidecar file in the given directory or returns the fallback.
+    pub fn load_or(dir: &Path, fallback: &Sampling) -> Self {
+        Self::load(dir).unwrap_or_else(|| fallback.clone())
+    }
+
+    /// Sets up sampling parameters of the currently bound texture. Should be called after the
+    /// texture image was uploaded so that mipmaps can be generated. `max_anisotropy` is the result
+    /// of `query_max_anisotropy`.
+    pub fn apply(&self, max_anisotropy: Option<f32>) {
+        if self.mipmaps {
+            unsafe { gl::GenerateMipmap(gl::TEXTURE_2D) };
+        }
+        self.set_parameters(self.mipmaps, max_anisotropy);
+    }
+
+    /// Sets up sampling parameters of the currently bound texture which had `num_levels` mipmap
+    /// levels uploaded explicitly. Mipmaps are not generated, so mipmap filtering is used only if
+    /// more than one level is available.
+    pub fn apply_to_levels(&self, num_levels: usize, max_anisotropy: Option<f32>) {
+        let max_level = (num_levels.max(1) - 1) as gl::types::GLint;
+        unsafe { gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MAX_LEVEL, max_level) };
+        self.set_parameters(self.mipmaps && num_levels > 1, max_anisotropy);
+    }
+}
+
+/// Returns the highest level of anisotropic filtering supported by the current GL context or
+/// `None` if it is not supported. Enumerating extensions is slow, so the result should be cached.
+pub fn query_max_anisotropy() -> Option<f32> {
+    if graphics::has_extension(EXTENSION_ANISOTROPIC) {
+        let mut max_anisotropy: gl::types::GLfloat = 1.0;
+        unsafe { gl::GetFloatv(MAX_TEXTURE_MAX_ANISOTROPY_EXT, &mut max_anisotropy) };
+        Some(max_anisotropy)
+    } else {
+        None
+    }
+}
+
+impl Sampling {
+    fn set_parameters(&self, mipmaps: bool, max_anisotropy: Option<f32>) {
+        let (min_filter, mag_filter) = match (self.filter, mipmaps) {
+            (Filter::Nearest, false) => (gl::NEAREST, gl::NEAREST),
+            (Filter::Nearest, true) => (gl::NEAREST_MIPMAP_NEAREST, gl::NEAREST),
+            (Filter::Linear, false) => (gl::LINEAR, gl::LINEAR),
+            (Filter::Linear, true) => (gl::LINEAR_MIPMAP_LINEAR, gl::LINEAR),
+        };
+        let wrap = match self.wrap {
+            Wrap::Repeat => gl::REPEAT,
+            Wrap::MirroredRepeat => gl::MIRRORED_REPEAT,
+            Wrap::ClampToEdge => gl::CLAMP_TO_EDGE,
+        };
+
+        unsafe {
+            let min_filter = min_filter as gl::types::GLint;
+            let mag_filter = mag_filter as gl::types::GLint;
+            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MIN_FILTER, min_filter);
+            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MAG_FILTER, mag_filter);
+            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_S, wrap as gl::types::GLint);
+            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_T, wrap as gl::types::GLint);
+
+            match (self.anisotropy, max_anisotropy) {
+                (Some(anisotropy), Some(max_anisotropy)) => {
+                    let anisotropy = anisotropy.max(1.0).min(max_anisotropy);
+                    gl::TexParameterf(gl::TEXTURE_2D, TEXTURE_MAX_ANISOTROPY_EXT, anisotropy);
+                }
+                (Some(_), None) => log::warn!("Anisotropic filtering is not supported"),
+                (None, _) => {}
+            }
+        }
+    }
+}
+
+impl Default for Sampling {
+    fn default() -> Self {
+        Self { filter: Filter::Linear, mipmaps: false, anisotropy: None, wrap: Wrap::Repeat }
+    }
+}
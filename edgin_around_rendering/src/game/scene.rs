@@ -1,22 +1,324 @@
-use std::{cmp::Ordering, collections::HashMap, f32::consts::PI};
+use std::{
+    cell::Cell,
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    convert::TryFrom,
+    f32::consts::PI,
+    fs::File,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering as AtomicOrdering},
+        Arc,
+    },
+};
 
-use crate::utils::{coordinates::Point, errors as err, ids::ActorId};
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug)]
+use crate::{
+    game::{
+        heightmap::Heightmap,
+        motion::{ActorMotion, ActorPath, MotionSmoothing},
+        spatial::SpatialIndex,
+        transition::Transition,
+    },
+    utils::{
+        coordinates::{Point, Point3D},
+        errors as err,
+        ids::ActorId,
+        noise::GradientNoise,
+    },
+};
+
+const JSON_EXTENSION: &str = "json";
+
+/// Approximate size of cells of the spatial index of actors in world units.
+const ACTOR_INDEX_CELL_SIZE: f32 = 20.0;
+
+/// Approximate size of cells of the spatial index of terrain edits in world units.
+const EDIT_INDEX_CELL_SIZE: f32 = 20.0;
+
+/// Distance in world units between points sampled to estimate the slope of the ground.
+const GROUND_NORMAL_SAMPLE_DISTANCE: f32 = 0.5;
+
+/// Number of actor changes kept in the scene. Older changes are dropped, so expositors which did
+/// not catch up have to synchronize with the whole scene.
+const MAX_ACTOR_CHANGES: usize = 1024;
+
+/// Source of identifiers of scenes, unique within the process.
+static NEXT_SCENE_ID: AtomicUsize = AtomicUsize::new(1);
+
+/// Source of generations of scenes, shared by all scenes so that a configuration of one scene
+/// never looks like a configuration of another.
+static NEXT_GENERATION: AtomicU64 = AtomicU64::new(1);
+
+fn default_origin() -> Point {
+    Point::new(0.0, 0.0)
+}
+
+fn default_amplitude() -> f32 {
+    1.0
+}
+
+fn default_falloff() -> Falloff {
+    Falloff::Smooth
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum TerrainVariant {
     Hills,
     Ranges,
     Continents,
+    Bump,
+    Noise(NoiseTerrain),
+    Heightmap(HeightmapTerrain),
 }
 
-#[derive(Clone, Debug)]
+impl TerrainVariant {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "hills" => Some(TerrainVariant::Hills),
+            "ranges" => Some(TerrainVariant::Ranges),
+            "continents" => Some(TerrainVariant::Continents),
+            "bump" => Some(TerrainVariant::Bump),
+            _ => None,
+        }
+    }
+}
+
+/// Serialized form of `NoiseTerrain`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct NoiseTerrainSpec {
+    seed: u64,
+    octaves: u32,
+    frequency: f32,
+    amplitude: f32,
+}
+
+/// Serialized form of `ElevationFunction`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct ElevationFunctionSpec {
+    radius: f32,
+
+    #[serde(default)]
+    sea_level: f32,
+
+    #[serde(default)]
+    terrain: Vec<TerrainData>,
+
+    #[serde(default)]
+    edits: Vec<TerrainEdit>,
+}
+
+/// Serialized form of `HeightmapTerrain`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct HeightmapTerrainSpec {
+    path: Option<PathBuf>,
+    min_height: f32,
+    max_height: f32,
+}
+
+/// Multi-octave gradient noise evaluated on the unit sphere, so it has no seams at the poles or the
+/// antimeridian.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(from = "NoiseTerrainSpec", into = "NoiseTerrainSpec")]
+pub struct NoiseTerrain {
+    noise: GradientNoise,
+    seed: u64,
+    octaves: u32,
+    frequency: f32,
+    amplitude: f32,
+}
+
+impl NoiseTerrain {
+    pub fn new(seed: u64, octaves: u32, frequency: f32, amplitude: f32) -> Self {
+        Self { noise: GradientNoise::new(seed), seed, octaves, frequency, amplitude }
+    }
+
+    pub fn evaluate(&self, point: &Point) -> f32 {
+        let unit = point.to_point3d(1.0);
+        self.amplitude * self.noise.fractal(unit.x, unit.y, unit.z, self.octaves, self.frequency)
+    }
+}
+
+impl From<NoiseTerrainSpec> for NoiseTerrain {
+    fn from(spec: NoiseTerrainSpec) -> Self {
+        Self::new(spec.seed, spec.octaves, spec.frequency, spec.amplitude)
+    }
+}
+
+impl From<NoiseTerrain> for NoiseTerrainSpec {
+    fn from(terrain: NoiseTerrain) -> Self {
+        Self {
+            seed: terrain.seed,
+            octaves: terrain.octaves,
+            frequency: terrain.frequency,
+            amplitude: terrain.amplitude,
+        }
+    }
+}
+
+/// Heights read from a heightmap image and mapped to range between `min_height` and `max_height`.
+///
+/// Only heightmaps loaded from a file can be serialized and deserialized. Deserialization only reads
+/// the path, the heightmap itself is read by `load_heightmap`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(try_from = "HeightmapTerrainSpec", into = "HeightmapTerrainSpec")]
+pub struct HeightmapTerrain {
+    heightmap: Option<Arc<Heightmap>>,
+    path: Option<PathBuf>,
+    min_height: f32,
+    max_height: f32,
+}
+
+impl HeightmapTerrain {
+    pub fn new(heightmap: Heightmap, min_height: f32, max_height: f32) -> Self {
+        Self { heightmap: Some(Arc::new(heightmap)), path: None, min_height, max_height }
+    }
+
+    pub fn load(path: &Path, min_height: f32, max_height: f32) -> Result<Self, String> {
+        let heightmap = Some(Arc::new(Heightmap::load(path)?));
+        Ok(Self { heightmap, path: Some(path.to_owned()), min_height, max_height })
+    }
+
+    /// Reads the heightmap if it was not read yet. A relative path is resolved against `dir`.
+    pub fn load_heightmap(&mut self, dir: &Path) -> Result<(), String> {
+        if let (None, Some(path)) = (&self.heightmap, &self.path) {
+            self.heightmap = Some(Arc::new(Heightmap::load(&dir.join(path))?));
+        }
+        Ok(())
+    }
+
+    /// Tells if the terrain can be serialized, i.e. if the heightmap was loaded from a file.
+    pub fn has_path(&self) -> bool {
+        self.path.is_some()
+    }
+
+    /// Returns zero until the heightmap is read.
+    pub fn evaluate(&self, point: &Point) -> f32 {
+        match self.heightmap {
+            Some(ref heightmap) => {
+                self.min_height + (self.max_height - self.min_height) * heightmap.sample(point)
+            }
+            None => 0.0,
+        }
+    }
+}
+
+impl TryFrom<HeightmapTerrainSpec> for HeightmapTerrain {
+    type Error = String;
+
+    fn try_from(spec: HeightmapTerrainSpec) -> Result<Self, Self::Error> {
+        if let Some(path) = spec.path {
+            Ok(Self {
+                heightmap: None,
+                path: Some(path),
+                min_height: spec.min_height,
+                max_height: spec.max_height,
+            })
+        } else {
+            Err(err::HEIGHTMAP_WITHOUT_PATH.to_owned())
+        }
+    }
+}
+
+impl From<HeightmapTerrain> for HeightmapTerrainSpec {
+    fn from(terrain: HeightmapTerrain) -> Self {
+        Self { path: terrain.path, min_height: terrain.min_height, max_height: terrain.max_height }
+    }
+}
+
+/// Describes how the influence of a local terrain feature fades with distance from its origin.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Falloff {
+    Linear,
+    Smooth,
+    Gaussian,
+}
+
+impl Falloff {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "linear" => Some(Falloff::Linear),
+            "smooth" => Some(Falloff::Smooth),
+            "gaussian" => Some(Falloff::Gaussian),
+            _ => None,
+        }
+    }
+
+    /// Returns weight of the feature for the given ratio of distance from the origin to the
+    /// feature radius. The weight is one at the origin and zero at and beyond the radius.
+    pub fn weight(&self, ratio: f32) -> f32 {
+        if ratio >= 1.0 {
+            return 0.0;
+        }
+
+        match self {
+            Falloff::Linear => 1.0 - ratio,
+            Falloff::Smooth => 1.0 - ratio * ratio * (3.0 - 2.0 * ratio),
+            Falloff::Gaussian => (-4.0 * ratio * ratio).exp() * (1.0 - ratio * ratio),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TerrainData {
     variant: TerrainVariant,
+
+    #[serde(default = "default_origin")]
     origin: Point,
+
+    /// Distance on the surface of the planet within which the feature is present. `None` means
+    /// the feature covers the whole planet.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    range: Option<f32>,
+
+    /// Multiplier of the height of the feature. Negative values turn hills into valleys.
+    #[serde(default = "default_amplitude")]
+    amplitude: f32,
+
+    /// Describes how the feature fades towards the edge of its range.
+    #[serde(default = "default_falloff")]
+    falloff: Falloff,
 }
 
 impl TerrainData {
+    pub fn new_global(variant: TerrainVariant, origin: Point) -> Self {
+        Self { variant, origin, range: None, amplitude: 1.0, falloff: Falloff::Smooth }
+    }
+
+    pub fn new_local(
+        variant: TerrainVariant,
+        origin: Point,
+        range: f32,
+        amplitude: f32,
+        falloff: Falloff,
+    ) -> Self {
+        Self { variant, origin, range: Some(range), amplitude, falloff }
+    }
+
+    /// Features with a range which is not positive have no effect.
     pub fn evaluate(&self, point: &Point, radius: f32) -> f32 {
+        let weight = match self.range {
+            Some(range) if range > 0.0 => {
+                let distance = Point::great_circle_distance(&self.origin, point, radius);
+                self.falloff.weight(distance / range)
+            }
+            Some(_) => 0.0,
+            None => 1.0,
+        };
+
+        if weight > 0.0 {
+            self.amplitude * weight * self.evaluate_variant(point, radius)
+        } else {
+            0.0
+        }
+    }
+}
+
+impl TerrainData {
+    fn evaluate_variant(&self, point: &Point, radius: f32) -> f32 {
         match self.variant {
             TerrainVariant::Hills => {
                 0.006
@@ -30,35 +332,213 @@ impl TerrainData {
                 0.012 * radius * (10.0 * point.theta + PI).cos() * (10.0 * point.phi).cos()
             }
             TerrainVariant::Continents => 0.018 * radius * point.theta.sin() * point.phi.sin(),
+            TerrainVariant::Bump => 0.01 * radius,
+            TerrainVariant::Noise(ref noise) => noise.evaluate(point),
+            TerrainVariant::Heightmap(ref heightmap) => heightmap.evaluate(point),
         }
     }
 }
 
-#[derive(Clone, Debug)]
+/// Kind of a local change of the terrain.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EditKind {
+    /// Adds `amount` to the height.
+    Raise,
+
+    /// Subtracts `amount` from the height.
+    Lower,
+
+    /// Moves the height towards `amount` measured from the radius.
+    Flatten,
+}
+
+/// Local change of the terrain applied on top of the terrain features, e.g. after digging.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TerrainEdit {
+    kind: EditKind,
+    center: Point,
+
+    /// Distance on the surface of the planet within which the edit has effect.
+    range: f32,
+
+    amount: f32,
+
+    #[serde(default = "default_falloff")]
+    falloff: Falloff,
+}
+
+impl TerrainEdit {
+    pub fn new(kind: EditKind, center: Point, range: f32, amount: f32) -> Self {
+        Self { kind, center, range, amount, falloff: Falloff::Smooth }
+    }
+
+    pub fn get_center(&self) -> &Point {
+        &self.center
+    }
+
+    pub fn get_range(&self) -> f32 {
+        self.range
+    }
+
+    /// Applies the edit to `elevation` evaluated at `point`. Edits with a range which is not
+    /// positive have no effect.
+    pub fn apply(&self, point: &Point, elevation: f32, radius: f32) -> f32 {
+        if self.range.is_nan() || self.range <= 0.0 {
+            return elevation;
+        }
+
+        let distance = Point::great_circle_distance(&self.center, point, radius);
+        let weight = self.falloff.weight(distance / self.range);
+        if weight <= 0.0 {
+            return elevation;
+        }
+
+        match self.kind {
+            EditKind::Raise => elevation + weight * self.amount,
+            EditKind::Lower => elevation - weight * self.amount,
+            EditKind::Flatten => elevation + weight * (radius + self.amount - elevation),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(from = "ElevationFunctionSpec", into = "ElevationFunctionSpec")]
 pub struct ElevationFunction {
     radius: f32,
+    sea_level: f32,
     terrain: Vec<TerrainData>,
+    edits: Vec<TerrainEdit>,
+
+    /// Centers of the edits indexed by their positions in `edits`, so that evaluation does not
+    /// have to go through edits far away.
+    edit_index: SpatialIndex<usize>,
+
+    /// Largest range of the edits in world units.
+    max_edit_range: f32,
 }
 
 impl ElevationFunction {
     pub fn new(radius: f32) -> Self {
-        Self { radius, terrain: Vec::new() }
+        Self {
+            radius,
+            sea_level: 0.0,
+            terrain: Vec::new(),
+            edits: Vec::new(),
+            edit_index: SpatialIndex::new(EDIT_INDEX_CELL_SIZE / radius),
+            max_edit_range: 0.0,
+        }
     }
 
     pub fn get_radius(&self) -> f32 {
         self.radius
     }
 
+    /// Returns height of the water surface above the radius.
+    pub fn get_sea_level(&self) -> f32 {
+        self.sea_level
+    }
+
+    pub fn set_sea_level(&mut self, sea_level: f32) {
+        self.sea_level = sea_level;
+    }
+
+    /// Returns distance of the water surface from the center of the planet.
+    pub fn get_sea_radius(&self) -> f32 {
+        self.radius + self.sea_level
+    }
+
+    /// Adds a terrain feature covering the whole planet.
     pub fn add_terrain(&mut self, name: &str, theta: f32, phi: f32) {
-        let origin = Point::new(theta, phi);
-        let variant = match name {
-            "hills" => TerrainVariant::Hills,
-            "ranges" => TerrainVariant::Ranges,
-            "continents" => TerrainVariant::Continents,
-            _ => return,
-        };
+        if let Some(variant) = TerrainVariant::from_name(name) {
+            self.terrain.push(TerrainData::new_global(variant, Point::new(theta, phi)));
+        } else {
+            log::warn!("Unknown terrain variant '{}'", name);
+        }
+    }
+
+    /// Adds a terrain feature present only within `range` (measured on the surface of the planet)
+    /// around the origin. The range has to be positive.
+    pub fn add_local_terrain(
+        &mut self,
+        name: &str,
+        theta: f32,
+        phi: f32,
+        range: f32,
+        amplitude: f32,
+        falloff: &str,
+    ) {
+        if range.is_nan() || range <= 0.0 {
+            log::warn!("Terrain range has to be positive: {}", range);
+            return;
+        }
+        match (TerrainVariant::from_name(name), Falloff::from_name(falloff)) {
+            (Some(variant), Some(falloff)) => {
+                let origin = Point::new(theta, phi);
+                let terrain = TerrainData::new_local(variant, origin, range, amplitude, falloff);
+                self.terrain.push(terrain);
+            }
+            (None, _) => log::warn!("Unknown terrain variant '{}'", name),
+            (_, None) => log::warn!("Unknown terrain falloff '{}'", falloff),
+        }
+    }
+
+    /// Adds seeded gradient noise covering the whole planet. `frequency` is the number of noise
+    /// cells per planet radius in the first octave and `amplitude` is the maximal height.
+    pub fn add_noise_terrain(&mut self, seed: u64, octaves: u32, frequency: f32, amplitude: f32) {
+        let variant = TerrainVariant::Noise(NoiseTerrain::new(seed, octaves, frequency, amplitude));
+        self.terrain.push(TerrainData::new_global(variant, Point::new(0.0, 0.0)));
+    }
 
-        self.terrain.push(TerrainData { variant, origin });
+    /// Adds heights read from a heightmap covering the whole planet.
+    pub fn add_heightmap(&mut self, heightmap: Heightmap, min_height: f32, max_height: f32) {
+        let variant =
+            TerrainVariant::Heightmap(HeightmapTerrain::new(heightmap, min_height, max_height));
+        self.terrain.push(TerrainData::new_global(variant, Point::new(0.0, 0.0)));
+    }
+
+    /// Adds heights read from an equirectangular PNG heightmap covering the whole planet.
+    pub fn add_heightmap_terrain(
+        &mut self,
+        path: &Path,
+        min_height: f32,
+        max_height: f32,
+    ) -> Result<(), String> {
+        let variant =
+            TerrainVariant::Heightmap(HeightmapTerrain::load(path, min_height, max_height)?);
+        self.terrain.push(TerrainData::new_global(variant, Point::new(0.0, 0.0)));
+        Ok(())
+    }
+
+    /// Reads heightmaps of terrain deserialized from a world description. Relative paths are
+    /// resolved against `dir`.
+    pub fn load_heightmaps(&mut self, dir: &Path) -> Result<(), String> {
+        for terrain in self.terrain.iter_mut() {
+            if let TerrainVariant::Heightmap(ref mut heightmap) = terrain.variant {
+                heightmap.load_heightmap(dir)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Tells if the terrain can be stored in a world description. Heightmaps not loaded from a file
+    /// cannot.
+    pub fn is_storable(&self) -> bool {
+        self.terrain.iter().all(|terrain| match terrain.variant {
+            TerrainVariant::Heightmap(ref heightmap) => heightmap.has_path(),
+            _ => true,
+        })
+    }
+
+    /// Appends a local change of the terrain. Edits are applied in order of addition.
+    pub fn add_edit(&mut self, edit: TerrainEdit) {
+        self.edit_index.insert(self.edits.len(), &edit.center);
+        self.max_edit_range = self.max_edit_range.max(edit.range);
+        self.edits.push(edit);
+    }
+
+    pub fn get_edits(&self) -> &Vec<TerrainEdit> {
+        &self.edits
     }
 
     pub fn evaluate(&self, point: &Point) -> f32 {
@@ -66,26 +546,66 @@ impl ElevationFunction {
         for terrain in &self.terrain {
             result += terrain.evaluate(point, self.radius);
         }
+        if !self.edits.is_empty() {
+            let angle = self.max_edit_range / self.radius;
+            let mut candidates = self.edit_index.find_candidates(point, angle);
+            candidates.sort_unstable();
+            for index in candidates {
+                result = self.edits[index].apply(point, result, self.radius);
+            }
+        }
         result
     }
 }
 
 impl Default for ElevationFunction {
     fn default() -> Self {
-        Self { radius: 1000.0, terrain: Vec::new() }
+        Self::new(1000.0)
     }
 }
 
-#[derive(Clone, Debug)]
+impl From<ElevationFunctionSpec> for ElevationFunction {
+    fn from(spec: ElevationFunctionSpec) -> Self {
+        let mut elevation = Self::new(spec.radius);
+        elevation.sea_level = spec.sea_level;
+        elevation.terrain = spec.terrain;
+        for edit in spec.edits {
+            elevation.add_edit(edit);
+        }
+        elevation
+    }
+}
+
+impl From<ElevationFunction> for ElevationFunctionSpec {
+    fn from(elevation: ElevationFunction) -> Self {
+        Self {
+            radius: elevation.radius,
+            sea_level: elevation.sea_level,
+            terrain: elevation.terrain,
+            edits: elevation.edits,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Actor {
     id: ActorId,
     entity_name: String,
+
+    #[serde(default)]
     position: Option<Point>,
+
+    /// Direction in which the actor heads, measured clockwise from the north.
+    #[serde(default)]
+    bearing: Option<f32>,
+
+    #[serde(default, skip_serializing_if = "ActorAppearance::is_default")]
+    appearance: ActorAppearance,
 }
 
 impl Actor {
     pub fn new(id: ActorId, entity_name: String, position: Option<Point>) -> Self {
-        Self { id, entity_name, position }
+        Self { id, entity_name, position, bearing: None, appearance: ActorAppearance::default() }
     }
 
     pub fn get_id(&self) -> ActorId {
@@ -100,17 +620,47 @@ impl Actor {
         self.position.as_ref()
     }
 
+    pub fn get_bearing(&self) -> Option<f32> {
+        self.bearing
+    }
+
+    pub fn get_appearance(&self) -> &ActorAppearance {
+        &self.appearance
+    }
+
     pub fn is_visible(&self) -> bool {
         self.position.is_some()
     }
 
+    pub fn set_entity_name(&mut self, entity_name: String) {
+        self.entity_name = entity_name;
+    }
+
     pub fn set_position(&mut self, position: Point) {
         self.position = Some(position);
     }
 
+    pub fn set_bearing(&mut self, bearing: Option<f32>) {
+        self.bearing = bearing;
+    }
+
+    pub fn set_appearance(&mut self, appearance: ActorAppearance) {
+        self.appearance = appearance;
+    }
+
+    /// Turns the actor in the direction of travel from `start` to `end`. The bearing does not
+    /// change if both points are the same.
+    pub fn face_along(&mut self, start: &Point, end: &Point) {
+        if Point::great_circle_distance(start, end, 1.0) > 0.0 {
+            self.bearing = Some(Point::final_bearing(start, end));
+        }
+    }
+
     pub fn move_by(&mut self, distance: f32, bearing: f32, radius: f32) {
-        if let Some(position) = &mut self.position {
-            *position = position.moved_by(distance, bearing, radius);
+        if let Some(position) = self.position.clone() {
+            let destination = position.moved_by(distance, bearing, radius);
+            self.face_along(&position, &destination);
+            self.position = Some(destination);
         }
     }
 
@@ -119,21 +669,209 @@ impl Actor {
     }
 }
 
+/// Properties changing how the sprite of an actor is drawn, independently of its animation.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ActorAppearance {
+    /// Multiplier of the size of the sprite.
+    pub scale: f32,
+
+    /// Color multiplying the colors of the sprite, given as RGBA components within `[0, 1]`.
+    pub tint: [f32; 4],
+
+    /// Opacity of the whole sprite within `[0, 1]`.
+    pub opacity: f32,
+
+    /// Height in world units above the ground at which the sprite is placed.
+    pub altitude: f32,
+
+    /// Radius in world units of the shadow cast on the ground, replacing the one defined by the
+    /// sprite. Zero means no shadow.
+    pub shadow_radius: Option<f32>,
+}
+
+impl ActorAppearance {
+    pub fn new(scale: f32, tint: [f32; 4], opacity: f32, altitude: f32) -> Self {
+        Self { scale, tint, opacity, altitude, shadow_radius: None }
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl Default for ActorAppearance {
+    fn default() -> Self {
+        Self::new(1.0, [1.0, 1.0, 1.0, 1.0], 1.0, 0.0)
+    }
+}
+
+/// Change of an actor recorded by the scene, so that expositors can keep their renderers in sync.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ActorChange {
+    Added(ActorId),
+    Removed(ActorId),
+
+    /// The actor was moved, hidden or replaced with one of the same entity. Moves without a
+    /// transition are recorded once per actor until the changes are read again.
+    Moved(ActorId),
+
+    Renamed(ActorId),
+}
+
+impl ActorChange {
+    pub fn get_actor_id(&self) -> ActorId {
+        match self {
+            ActorChange::Added(id)
+            | ActorChange::Removed(id)
+            | ActorChange::Moved(id)
+            | ActorChange::Renamed(id) => *id,
+        }
+    }
+}
+
+/// Description of a world from which a scene can be created.
+///
+/// It can be stored in a YAML file or, if the file has the `json` extension, a JSON file. Example:
+///
+/// ```yaml
+/// radius: 1000.0
+/// sea_level: 0.5
+/// hero_id: 1
+/// terrain:
+///   - variant: continents
+///   - variant: bump
+///     origin: { theta: 1.57, phi: 0.5 }
+///     range: 100.0
+///     amplitude: -2.0
+///     falloff: linear
+///   - variant:
+///       noise: { seed: 42, octaves: 5, frequency: 4.0, amplitude: 20.0 }
+///   - variant:
+///       heightmap: { path: maps/world.png, min_height: -10.0, max_height: 30.0 }
+/// actors:
+///   - id: 1
+///     entity_name: pirate
+///     position: { theta: 1.57, phi: 0.0 }
+///   - id: 2
+///     entity_name: axe
+/// ```
+///
+/// The `sea_level` is optional and defaults to zero. Terrain `origin`, `range`, `amplitude` and
+/// `falloff` are optional and have the same meaning as parameters of
+/// `ElevationFunction::add_local_terrain`. Terrain `edits` made at runtime are stored in the same
+/// list form, e.g. `{ kind: flatten, center: { theta: 1.5, phi: 0.5 }, range: 5.0, amount: 1.0 }`.
+/// Relative paths of heightmaps are resolved against the directory of the file. Actors without
+/// `position` are hidden.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SceneDescription {
+    #[serde(flatten)]
+    pub elevation: ElevationFunction,
+
+    pub hero_id: ActorId,
+
+    #[serde(default)]
+    pub actors: Vec<Actor>,
+}
+
 #[derive(Clone, Debug)]
 pub struct Scene {
+    id: usize,
     elevation: ElevationFunction,
     hero_actor_id: ActorId,
     actors: HashMap<ActorId, Actor>,
     is_ready: bool,
+    generation: u64,
+    reset_renderers: bool,
+    actor_changes: Vec<ActorChange>,
+    actor_change_transitions: Vec<Option<Transition>>,
+    change_transition: Option<Transition>,
+    num_dropped_changes: usize,
+    read_revision: Cell<usize>,
+    move_revisions: HashMap<ActorId, usize>,
+    actor_index: SpatialIndex<ActorId>,
+    unindexed_actors: HashSet<ActorId>,
+    motions: HashMap<ActorId, ActorMotion>,
+    motion_smoothing: MotionSmoothing,
+    paths: HashMap<ActorId, ActorPath>,
+    arrived_actors: Vec<ActorId>,
+    time: f64,
 }
 
 impl Scene {
     pub fn new() -> Self {
+        let elevation = ElevationFunction::default();
+        let actor_index = SpatialIndex::new(ACTOR_INDEX_CELL_SIZE / elevation.get_radius());
         Self {
-            elevation: ElevationFunction::default(),
+            id: NEXT_SCENE_ID.fetch_add(1, AtomicOrdering::Relaxed),
+            elevation,
             hero_actor_id: 0,
             actors: HashMap::new(),
             is_ready: false,
+            generation: 0,
+            reset_renderers: false,
+            actor_changes: Vec::new(),
+            actor_change_transitions: Vec::new(),
+            change_transition: None,
+            num_dropped_changes: 0,
+            read_revision: Cell::new(0),
+            move_revisions: HashMap::new(),
+            actor_index,
+            unindexed_actors: HashSet::new(),
+            motions: HashMap::new(),
+            motion_smoothing: MotionSmoothing::default(),
+            paths: HashMap::new(),
+            arrived_actors: Vec::new(),
+            time: 0.0,
+        }
+    }
+
+    /// Creates a scene from a world description. Relative paths of heightmaps are resolved against
+    /// the current directory.
+    pub fn from_description(mut description: SceneDescription) -> Result<Self, String> {
+        description.elevation.load_heightmaps(Path::new(""))?;
+        let mut scene = Self::new();
+        scene.configure(description.hero_id, description.elevation);
+        scene.create_actors(&description.actors);
+        Ok(scene)
+    }
+
+    pub fn to_description(&self) -> SceneDescription {
+        let mut actors: Vec<Actor> = self.actors.values().cloned().collect();
+        actors.sort_by_key(|actor| actor.id);
+        SceneDescription { elevation: self.elevation.clone(), hero_id: self.hero_actor_id, actors }
+    }
+
+    /// Creates a scene from a world description stored in a YAML or JSON file. Relative paths of
+    /// heightmaps are resolved against the directory of the file.
+    pub fn load_from(path: &Path) -> Result<Self, String> {
+        let file =
+            File::open(path).map_err(|e| format!("{}: {:?}: {}", err::FILE_FAILED, path, e))?;
+        let mut description: SceneDescription = if Self::is_json(path) {
+            serde_json::from_reader(file).map_err(|e| format!("{}: {}", err::JSON_FAILED, e))?
+        } else {
+            serde_yaml::from_reader(file).map_err(|e| format!("{}: {}", err::YAML_FAILED, e))?
+        };
+        let dir = path.parent().unwrap_or_else(|| Path::new(""));
+        description.elevation.load_heightmaps(dir)?;
+        Self::from_description(description)
+    }
+
+    /// Stores the world description of the scene in a YAML or JSON file. Fails without touching the
+    /// file if the terrain contains a heightmap not loaded from a file.
+    pub fn save_to(&self, path: &Path) -> Result<(), String> {
+        if !self.elevation.is_storable() {
+            return Err(err::HEIGHTMAP_WITHOUT_PATH.to_owned());
+        }
+        let file =
+            File::create(path).map_err(|e| format!("{}: {:?}: {}", err::FILE_FAILED, path, e))?;
+        let description = self.to_description();
+        if Self::is_json(path) {
+            serde_json::to_writer_pretty(file, &description)
+                .map_err(|e| format!("{}: {}", err::JSON_FAILED, e))
+        } else {
+            serde_yaml::to_writer(file, &description)
+                .map_err(|e| format!("{}: {}", err::YAML_FAILED, e))
         }
     }
 
@@ -149,6 +887,23 @@ impl Scene {
         self.elevation.evaluate(point)
     }
 
+    /// Returns the unit vector perpendicular to the ground at the point, in the coordinates of
+    /// `Point::to_point3d`. The slope is estimated from the elevation at nearby points.
+    pub fn get_ground_normal(&self, point: &Point) -> Point3D {
+        let radius = self.get_radius();
+        let sample = |bearing: f32| {
+            let neighbour = point.moved_by(GROUND_NORMAL_SAMPLE_DISTANCE, bearing, radius);
+            neighbour.to_point3d(self.get_elevation(&neighbour))
+        };
+        let north = &sample(0.0) - &sample(PI);
+        let east = &sample(0.5 * PI) - &sample(1.5 * PI);
+        east.cross(&north).enlongated(1.0)
+    }
+
+    pub fn get_sea_radius(&self) -> f32 {
+        self.elevation.get_sea_radius()
+    }
+
     pub fn get_hero_id(&self) -> ActorId {
         self.hero_actor_id
     }
@@ -157,8 +912,15 @@ impl Scene {
         self.actors.get(&actor_id)
     }
 
+    /// Gives mutable access to the actor. Its position may change, so it is checked on every
+    /// proximity query until it is positioned with `set_actor_position` or `move_actor_by`.
     pub fn get_actor_mut(&mut self, actor_id: ActorId) -> Option<&mut Actor> {
-        self.actors.get_mut(&actor_id)
+        let actor = self.actors.get_mut(&actor_id);
+        if actor.is_some() {
+            self.actor_index.remove(actor_id);
+            self.unindexed_actors.insert(actor_id);
+        }
+        actor
     }
 
     pub fn get_actors(&self) -> std::collections::hash_map::Values<ActorId, Actor> {
@@ -166,57 +928,481 @@ impl Scene {
     }
 
     pub fn get_focus_point(&self) -> Point {
-        self.actors
-            .get(&self.hero_actor_id)
-            .cloned()
-            .expect(err::NOT_EXISTING_HERO)
-            .position
-            .expect(err::HERO_WITHOUT_POSITION)
+        self.actors.get(&self.hero_actor_id).expect(err::NOT_EXISTING_HERO);
+        self.get_rendered_position(self.hero_actor_id).expect(err::HERO_WITHOUT_POSITION)
+    }
+
+    /// Returns a number which changes every time the scene is configured and differs between
+    /// scenes. It is zero until the first configuration. Expositors reload their data when it
+    /// changes.
+    pub fn get_generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Tells if renderers of entities should be dropped after the last configuration.
+    pub fn should_reset_renderers(&self) -> bool {
+        self.reset_renderers
     }
 
+    /// Sets up the hero and the terrain. Renderers of entities are kept if the scene was already
+    /// configured before.
     pub fn configure(&mut self, hero_actor_id: ActorId, elevation: ElevationFunction) {
+        self.reconfigure(hero_actor_id, elevation, false)
+    }
+
+    /// Sets up the hero and the terrain, e.g. when moving to another planet. If `reset_renderers`
+    /// is true, expositors drop renderers of all entities and create them anew.
+    pub fn reconfigure(
+        &mut self,
+        hero_actor_id: ActorId,
+        elevation: ElevationFunction,
+        reset_renderers: bool,
+    ) {
         self.hero_actor_id = hero_actor_id;
         self.elevation = elevation;
+        self.rebuild_actor_index();
         self.is_ready = true;
+        self.generation = NEXT_GENERATION.fetch_add(1, AtomicOrdering::Relaxed);
+        self.reset_renderers = reset_renderers;
     }
 
+    /// Adds actors to the scene. Actors with already existing IDs are replaced.
     pub fn create_actors(&mut self, actors: &Vec<Actor>) {
         for actor in actors.iter() {
-            self.actors.insert(actor.id, actor.clone());
+            let change = match self.actors.insert(actor.id, actor.clone()) {
+                None => ActorChange::Added(actor.id),
+                Some(old) if old.entity_name != actor.entity_name => ActorChange::Renamed(actor.id),
+                Some(_) => ActorChange::Moved(actor.id),
+            };
+            self.stop_movement(actor.id);
+            self.index_actor(actor.id);
+            match change {
+                ActorChange::Moved(actor_id) => self.record_actor_move(actor_id),
+                _ => self.record_actor_change(change),
+            }
         }
     }
 
     pub fn delete_actors(&mut self, actor_ids: &Vec<ActorId>) {
         for id in actor_ids {
-            self.actors.remove(&id);
+            if self.actors.remove(id).is_some() {
+                self.actor_index.remove(*id);
+                self.unindexed_actors.remove(id);
+                self.stop_movement(*id);
+                self.move_revisions.remove(id);
+                self.record_actor_change(ActorChange::Removed(*id));
+            }
         }
     }
 
     pub fn hide_actors(&mut self, actor_ids: &Vec<ActorId>) {
         for id in actor_ids {
-            if let Some(actor) = self.get_actor_mut(*id) {
+            if let Some(actor) = self.actors.get_mut(id) {
                 actor.hide();
+                self.stop_movement(*id);
+                self.index_actor(*id);
+                self.record_actor_move(*id);
+            }
+        }
+    }
+
+    /// Like `create_actors`, but expositors reveal the actors with the given transition.
+    pub fn create_actors_with_transition(&mut self, actors: &Vec<Actor>, transition: Transition) {
+        self.change_transition = Some(transition);
+        self.create_actors(actors);
+        self.change_transition = None;
+    }
+
+    /// Like `delete_actors`, but expositors keep showing the actors until the given transition
+    /// conceals them.
+    pub fn delete_actors_with_transition(
+        &mut self,
+        actor_ids: &Vec<ActorId>,
+        transition: Transition,
+    ) {
+        self.change_transition = Some(transition);
+        self.delete_actors(actor_ids);
+        self.change_transition = None;
+    }
+
+    /// Like `hide_actors`, but expositors keep showing the actors until the given transition
+    /// conceals them.
+    pub fn hide_actors_with_transition(
+        &mut self,
+        actor_ids: &Vec<ActorId>,
+        transition: Transition,
+    ) {
+        self.change_transition = Some(transition);
+        self.hide_actors(actor_ids);
+        self.change_transition = None;
+    }
+
+    /// Places the actor at the given position immediately, dropping its smoothed motion and path.
+    pub fn set_actor_position(&mut self, actor_id: ActorId, position: Point) {
+        if let Some(actor) = self.actors.get_mut(&actor_id) {
+            actor.set_position(position);
+            self.stop_movement(actor_id);
+            self.index_actor(actor_id);
+            self.record_actor_move(actor_id);
+        }
+    }
+
+    pub fn move_actor_by(&mut self, actor_id: ActorId, distance: f32, bearing: f32) {
+        let radius = self.get_radius();
+        if let Some(actor) = self.actors.get_mut(&actor_id) {
+            actor.move_by(distance, bearing, radius);
+            self.stop_movement(actor_id);
+            self.index_actor(actor_id);
+            self.record_actor_move(actor_id);
+        }
+    }
+
+    /// Records the position of the actor at the given time, e.g. received from a server. The
+    /// actor is rendered moving smoothly between the recorded positions (see `set_time`).
+    /// Timestamps are in seconds and must use the same clock as `set_time`.
+    pub fn update_actor_position(&mut self, actor_id: ActorId, position: Point, timestamp: f64) {
+        let radius = self.get_radius();
+        if let Some(actor) = self.actors.get_mut(&actor_id) {
+            let previous = actor.get_position().cloned();
+            let smoothing = &self.motion_smoothing;
+            let pushed = self
+                .motions
+                .get_mut(&actor_id)
+                .map(|motion| motion.push(position.clone(), timestamp, smoothing, radius))
+                .unwrap_or(false);
+            if !pushed {
+                self.motions.insert(actor_id, ActorMotion::new(position, timestamp));
+            }
+            self.paths.remove(&actor_id);
+
+            let position = self.motions[&actor_id].get_last_position().clone();
+            if let (true, Some(previous)) = (pushed, previous) {
+                actor.face_along(&previous, &position);
             }
+            actor.set_position(position);
+            self.index_actor(actor_id);
+            self.record_actor_move(actor_id);
+        }
+    }
+
+    /// Sets the current time in seconds, used to compute rendered positions of actors and to
+    /// advance actors along their paths.
+    pub fn set_time(&mut self, time: f64) {
+        self.time = time;
+        self.advance_paths();
+    }
+
+    /// Makes the actor walk through the waypoints with `speed` given in world units per second,
+    /// starting from the next call to `set_time`. An empty list stops the actor. Hidden actors
+    /// cannot walk, so they are reported as arrived at once. Fails if the speed is not finite and
+    /// positive.
+    pub fn set_actor_path(
+        &mut self,
+        actor_id: ActorId,
+        waypoints: Vec<Point>,
+        speed: f32,
+    ) -> Result<(), String> {
+        if !(speed.is_finite() && speed > 0.0) {
+            return Err(format!("{}: {}", err::PATH_INVALID_SPEED, speed));
         }
+        if let Some(actor) = self.actors.get(&actor_id) {
+            let visible = actor.is_visible();
+            self.stop_movement(actor_id);
+            if !waypoints.is_empty() {
+                if visible {
+                    self.paths.insert(actor_id, ActorPath::new(waypoints, speed));
+                } else {
+                    self.arrived_actors.push(actor_id);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn get_actor_path(&self, actor_id: ActorId) -> Option<&ActorPath> {
+        self.paths.get(&actor_id)
     }
 
+    /// Tells if the actor is walking along a path.
+    pub fn is_actor_moving(&self, actor_id: ActorId) -> bool {
+        self.paths.contains_key(&actor_id)
+    }
+
+    /// Returns IDs of actors which reached the ends of their paths since the last call.
+    pub fn take_arrived_actors(&mut self) -> Vec<ActorId> {
+        std::mem::take(&mut self.arrived_actors)
+    }
+
+    pub fn get_time(&self) -> f64 {
+        self.time
+    }
+
+    pub fn get_motion_smoothing(&self) -> &MotionSmoothing {
+        &self.motion_smoothing
+    }
+
+    pub fn set_motion_smoothing(&mut self, motion_smoothing: MotionSmoothing) {
+        self.motion_smoothing = motion_smoothing;
+    }
+
+    /// Returns the position at which the actor should be drawn at the current time. Actors moved
+    /// with `update_actor_position` are interpolated, others are drawn at their positions.
+    pub fn get_rendered_position(&self, actor_id: ActorId) -> Option<Point> {
+        let position = self.actors.get(&actor_id)?.get_position()?;
+        match self.motions.get(&actor_id) {
+            Some(motion) => {
+                let time = self.time - self.motion_smoothing.delay;
+                Some(motion.evaluate(time, &self.motion_smoothing, self.get_radius()))
+            }
+            None => Some(position.clone()),
+        }
+    }
+
+    /// Turns the actor to the given bearing, measured clockwise from the north. `None` makes it
+    /// face the camera. Moving actors turn in the direction of movement by themselves.
+    pub fn set_actor_bearing(&mut self, actor_id: ActorId, bearing: Option<f32>) {
+        if let Some(actor) = self.actors.get_mut(&actor_id) {
+            actor.set_bearing(bearing);
+        }
+    }
+
+    /// Changes the scale, tint, opacity and altitude with which the actor is drawn.
+    pub fn set_actor_appearance(&mut self, actor_id: ActorId, appearance: ActorAppearance) {
+        if let Some(actor) = self.actors.get_mut(&actor_id) {
+            actor.set_appearance(appearance);
+        }
+    }
+
+    /// Sets the radius of the shadow of the actor. `None` restores the radius defined by its
+    /// sprite.
+    pub fn set_actor_shadow_radius(&mut self, actor_id: ActorId, shadow_radius: Option<f32>) {
+        if let Some(actor) = self.actors.get_mut(&actor_id) {
+            actor.appearance.shadow_radius = shadow_radius;
+        }
+    }
+
+    /// Changes the entity of the actor. Expositors reload its sprite.
+    pub fn set_actor_entity_name(&mut self, actor_id: ActorId, entity_name: String) {
+        if let Some(actor) = self.actors.get_mut(&actor_id) {
+            if actor.entity_name != entity_name {
+                actor.set_entity_name(entity_name);
+                self.record_actor_change(ActorChange::Renamed(actor_id));
+            }
+        }
+    }
+
+    /// Returns an identifier unique among the scenes created in the process. Actor revisions are
+    /// meaningful only together with the identifier of the scene. Clones share the identifier.
+    pub fn get_id(&self) -> usize {
+        self.id
+    }
+
+    /// Returns number of actor changes recorded so far.
+    pub fn get_actor_revision(&self) -> usize {
+        let revision = self.num_dropped_changes + self.actor_changes.len();
+        self.read_revision.set(revision);
+        revision
+    }
+
+    /// Returns actor changes recorded after the given revision or `None` if some of them were
+    /// already dropped. Changes made through `get_actor_mut` are not recorded.
+    pub fn get_actor_changes_since(&self, revision: usize) -> Option<&[ActorChange]> {
+        if revision < self.num_dropped_changes {
+            None
+        } else {
+            self.read_revision.set(self.num_dropped_changes + self.actor_changes.len());
+            self.actor_changes.get((revision - self.num_dropped_changes)..)
+        }
+    }
+
+    /// Returns the transition requested for the change which followed the given revision, i.e.
+    /// the first one returned by `get_actor_changes_since(revision)`.
+    pub fn get_actor_transition(&self, revision: usize) -> Option<&Transition> {
+        let index = revision.checked_sub(self.num_dropped_changes)?;
+        self.actor_change_transitions.get(index)?.as_ref()
+    }
+
+    fn is_json(path: &Path) -> bool {
+        path.extension().map(|extension| extension == JSON_EXTENSION).unwrap_or(false)
+    }
+
+    fn stop_movement(&mut self, actor_id: ActorId) {
+        self.motions.remove(&actor_id);
+        self.paths.remove(&actor_id);
+    }
+
+    fn advance_paths(&mut self) {
+        let radius = self.get_radius();
+        let mut actor_ids: Vec<ActorId> = self.paths.keys().cloned().collect();
+        actor_ids.sort();
+        for actor_id in actor_ids {
+            let (moved, finished) =
+                match (self.paths.get_mut(&actor_id), self.actors.get_mut(&actor_id)) {
+                    (Some(path), Some(actor)) => {
+                        let moved = path.advance(actor, self.time, radius).is_some();
+                        (moved, path.is_finished())
+                    }
+                    _ => continue,
+                };
+
+            if moved {
+                self.index_actor(actor_id);
+                self.record_actor_move(actor_id);
+            }
+            if finished {
+                self.paths.remove(&actor_id);
+                self.arrived_actors.push(actor_id);
+            }
+        }
+    }
+
+    fn index_actor(&mut self, actor_id: ActorId) {
+        self.unindexed_actors.remove(&actor_id);
+        match self.actors.get(&actor_id).and_then(|actor| actor.get_position()) {
+            Some(position) => self.actor_index.insert(actor_id, position),
+            None => self.actor_index.remove(actor_id),
+        }
+    }
+
+    fn rebuild_actor_index(&mut self) {
+        self.actor_index = SpatialIndex::new(ACTOR_INDEX_CELL_SIZE / self.get_radius());
+        self.unindexed_actors.clear();
+        for actor in self.actors.values() {
+            if let Some(position) = actor.get_position() {
+                self.actor_index.insert(actor.id, position);
+            }
+        }
+    }
+
+    /// Returns actors with positions within `angle` radians from `center` and their distances
+    /// computed with `distance`.
+    fn find_actors_within_angle<D>(
+        &self,
+        center: &Point,
+        angle: f32,
+        distance: D,
+    ) -> Vec<(ActorId, f32)>
+    where
+        D: Fn(&Point) -> f32,
+    {
+        let candidates = self.actor_index.find_candidates(center, angle);
+        candidates
+            .iter()
+            .chain(self.unindexed_actors.iter())
+            .filter_map(|id| self.actors.get(id))
+            .filter_map(|actor| actor.get_position().map(|position| (actor.id, distance(position))))
+            .collect()
+    }
+
+    fn record_actor_change(&mut self, change: ActorChange) {
+        if self.actor_changes.len() >= MAX_ACTOR_CHANGES {
+            let num_dropped = MAX_ACTOR_CHANGES / 2;
+            self.actor_changes.drain(..num_dropped);
+            self.actor_change_transitions.drain(..num_dropped);
+            self.num_dropped_changes += num_dropped;
+        }
+        self.actor_changes.push(change);
+        self.actor_change_transitions.push(self.change_transition.clone());
+    }
+
+    /// Records a move unless the same actor already has a move without a transition which was not
+    /// read yet, so that frequent moves do not flood the change log.
+    fn record_actor_move(&mut self, actor_id: ActorId) {
+        if self.change_transition.is_none() {
+            let unread = self.read_revision.get().max(self.num_dropped_changes);
+            if self.move_revisions.get(&actor_id).is_some_and(|revision| *revision >= unread) {
+                return;
+            }
+            let revision = self.num_dropped_changes + self.actor_changes.len();
+            self.move_revisions.insert(actor_id, revision);
+        }
+        self.record_actor_change(ActorChange::Moved(actor_id));
+    }
+
+    /// Raises the terrain by `amount` within `range` around `center`.
+    pub fn raise_terrain(&mut self, center: &Point, range: f32, amount: f32) {
+        self.elevation.add_edit(TerrainEdit::new(EditKind::Raise, center.clone(), range, amount));
+    }
+
+    /// Lowers the terrain by `amount` within `range` around `center`.
+    pub fn lower_terrain(&mut self, center: &Point, range: f32, amount: f32) {
+        self.elevation.add_edit(TerrainEdit::new(EditKind::Lower, center.clone(), range, amount));
+    }
+
+    /// Levels the terrain within `range` around `center` to `height` above the radius.
+    pub fn flatten_terrain(&mut self, center: &Point, range: f32, height: f32) {
+        self.elevation.add_edit(TerrainEdit::new(EditKind::Flatten, center.clone(), range, height));
+    }
+
+    /// Returns all the terrain edits applied so far. New edits are always appended, so renderers
+    /// can find the ones they did not handle yet by remembering the length.
+    pub fn get_terrain_edits(&self) -> &Vec<TerrainEdit> {
+        self.elevation.get_edits()
+    }
+
+    /// Returns actors closer than `max_distance` to `reference_position`, the closest first.
+    /// Distances are measured in straight lines between points on the sphere.
     pub fn find_closest_actors(
         &self,
         reference_position: &Point,
         max_distance: f32,
     ) -> Vec<ActorId> {
-        let mut actors = Vec::<(ActorId, f32)>::new();
-        for actor in self.actors.values() {
-            if let Some(actor_position) = actor.get_position() {
-                let current_distance =
-                    Point::distance(reference_position, actor_position, self.get_radius());
-                if current_distance < max_distance {
-                    actors.push((actor.get_id(), current_distance));
-                }
+        let radius = self.get_radius();
+        let angle = chord_to_angle(max_distance, radius);
+        let mut actors = self.find_actors_within_angle(reference_position, angle, |position| {
+            Point::distance(reference_position, position, radius)
+        });
+        actors.retain(|a| a.1 < max_distance);
+        sort_by_distance(actors)
+    }
+
+    /// Returns actors whose distance along the surface from `center` is at most `distance`, the
+    /// closest first.
+    pub fn find_actors_in_cap(&self, center: &Point, distance: f32) -> Vec<ActorId> {
+        let radius = self.get_radius();
+        let mut actors = self.find_actors_within_angle(center, distance / radius, |position| {
+            Point::great_circle_distance(center, position, radius)
+        });
+        actors.retain(|a| a.1 <= distance);
+        sort_by_distance(actors)
+    }
+
+    /// Returns up to `count` actors closest to `reference_position`, the closest first.
+    pub fn find_nearest_actors(&self, reference_position: &Point, count: usize) -> Vec<ActorId> {
+        let radius = self.get_radius();
+        let mut angle = self.actor_index.get_cell_angle();
+        loop {
+            // All actors within the angle are found, so if there are enough of them, no actor
+            // outside could be closer
+            let max_distance = angle_to_chord(angle, radius);
+            let mut actors = self.find_actors_within_angle(reference_position, angle, |position| {
+                Point::distance(reference_position, position, radius)
+            });
+            if angle < PI {
+                actors.retain(|a| a.1 <= max_distance);
+            }
+            if actors.len() >= count || angle >= PI {
+                let mut ids = sort_by_distance(actors);
+                ids.truncate(count);
+                return ids;
             }
+            angle = (2.0 * angle).min(PI);
         }
-
-        actors.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal));
-        actors.iter().map(|a| a.0).collect()
     }
 }
+
+/// Converts a straight-line distance between two points on a sphere to the angle between them.
+fn chord_to_angle(distance: f32, radius: f32) -> f32 {
+    2.0 * (0.5 * distance / radius).clamp(0.0, 1.0).asin()
+}
+
+/// Converts an angle between two points on a sphere to the straight-line distance between them.
+fn angle_to_chord(angle: f32, radius: f32) -> f32 {
+    2.0 * radius * (0.5 * angle).sin()
+}
+
+/// Sorts actors by distance and, if equally distant, by ID.
+fn sort_by_distance(mut actors: Vec<(ActorId, f32)>) -> Vec<ActorId> {
+    actors.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal).then(a.0.cmp(&b.0)));
+    actors.iter().map(|a| a.0).collect()
+}
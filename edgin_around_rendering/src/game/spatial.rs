@@ -0,0 +1,162 @@
+use std::{collections::HashMap, f32::consts::PI, hash::Hash};
+
+use crate::utils::coordinates::Point;
+
+/// Smallest supported cell size in radians. Limits the number of rows of the grid.
+const MIN_CELL_ANGLE: f32 = 1.0e-3;
+
+/// Angle added to query ranges to make up for rounding errors when choosing cells.
+const MARGIN_ANGLE: f32 = 1.0e-4;
+
+type Cell = (usize, usize);
+
+/// Grid over the unit sphere dividing it into bands of equal latitude, each split into cells of
+/// roughly equal size. Used to find items, like actors or terrain edits, close to a point without
+/// scanning all of them. Items are identified by keys of type `K`.
+///
+/// Queries return candidates: every item within the given angle is included, but some farther
+/// ones may be too, so callers are expected to check the exact distance.
+#[derive(Clone, Debug)]
+pub struct SpatialIndex<K> {
+    cell_angle: f32,
+    row_height: f32,
+    row_columns: Vec<usize>,
+    cells: HashMap<Cell, Vec<K>>,
+    entries: HashMap<K, Cell>,
+}
+
+impl<K: Copy + Eq + Hash> SpatialIndex<K> {
+    /// Creates an empty index with cells about `cell_angle` radians wide.
+    pub fn new(cell_angle: f32) -> Self {
+        let cell_angle = cell_angle.clamp(MIN_CELL_ANGLE, PI);
+        let num_rows = (PI / cell_angle).ceil() as usize;
+        let row_height = PI / num_rows as f32;
+        let row_columns = (0..num_rows)
+            .map(|row| {
+                // Use the widest circle of the row so no cell is wider than `cell_angle`
+                let top = row as f32 * row_height;
+                let bottom = top + row_height;
+                let width = if top < 0.5 * PI && bottom > 0.5 * PI {
+                    1.0
+                } else {
+                    top.sin().max(bottom.sin())
+                };
+                ((2.0 * PI * width / cell_angle).ceil() as usize).max(1)
+            })
+            .collect();
+        Self { cell_angle, row_height, row_columns, cells: HashMap::new(), entries: HashMap::new() }
+    }
+
+    pub fn get_cell_angle(&self) -> f32 {
+        self.cell_angle
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn contains(&self, key: K) -> bool {
+        self.entries.contains_key(&key)
+    }
+
+    /// Adds the item at the given position or moves it there if it was already indexed.
+    pub fn insert(&mut self, key: K, point: &Point) {
+        let cell = self.find_cell(point);
+        if let Some(old_cell) = self.entries.insert(key, cell) {
+            if old_cell == cell {
+                return;
+            }
+            self.remove_from_cell(key, old_cell);
+        }
+        self.cells.entry(cell).or_default().push(key);
+    }
+
+    pub fn remove(&mut self, key: K) {
+        if let Some(cell) = self.entries.remove(&key) {
+            self.remove_from_cell(key, cell);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.entries.clear();
+    }
+
+    /// Returns keys of items which may lie within `angle` radians from `center`.
+    pub fn find_candidates(&self, center: &Point, angle: f32) -> Vec<K> {
+        let angle = angle + MARGIN_ANGLE;
+        let (theta, phi) = normalize(center);
+
+        let first_row = self.find_row((theta - angle).max(0.0));
+        let last_row = self.find_row((theta + angle).min(PI));
+        let includes_pole = (theta - angle <= 0.0) || (theta + angle >= PI);
+
+        // Half of the longitude span of the spherical cap
+        let span = if includes_pole { PI } else { (angle.sin() / theta.sin()).min(1.0).asin() };
+
+        let mut ranges = Vec::with_capacity(last_row + 1 - first_row);
+        let mut num_cells = 0;
+        for row in first_row..=last_row {
+            let columns = self.row_columns[row];
+            let column_width = 2.0 * PI / columns as f32;
+            let first = ((phi - span) / column_width).floor() as isize;
+            let last = ((phi + span) / column_width).floor() as isize;
+            let range = if (last - first + 1) as usize >= columns {
+                (0, columns as isize - 1)
+            } else {
+                (first, last)
+            };
+            num_cells += (range.1 - range.0 + 1) as usize;
+            ranges.push((row, columns, range));
+        }
+
+        if num_cells > self.cells.len() {
+            // Cheaper to check all the occupied cells
+            return self.entries.keys().cloned().collect();
+        }
+
+        let mut result = Vec::new();
+        for (row, columns, (first, last)) in ranges {
+            for column in first..=last {
+                let cell = (row, column.rem_euclid(columns as isize) as usize);
+                if let Some(keys) = self.cells.get(&cell) {
+                    result.extend_from_slice(keys);
+                }
+            }
+        }
+        result
+    }
+}
+
+impl<K: Copy + Eq + Hash> SpatialIndex<K> {
+    fn find_row(&self, theta: f32) -> usize {
+        ((theta / self.row_height) as usize).min(self.row_columns.len() - 1)
+    }
+
+    fn find_cell(&self, point: &Point) -> Cell {
+        let (theta, phi) = normalize(point);
+        let row = self.find_row(theta);
+        let columns = self.row_columns[row];
+        let column = ((phi.rem_euclid(2.0 * PI) / (2.0 * PI)) * columns as f32) as usize;
+        (row, column.min(columns - 1))
+    }
+
+    fn remove_from_cell(&mut self, key: K, cell: Cell) {
+        if let Some(keys) = self.cells.get_mut(&cell) {
+            keys.retain(|k| *k != key);
+            if keys.is_empty() {
+                self.cells.remove(&cell);
+            }
+        }
+    }
+}
+
+/// Returns the polar and azimuthal angle of the point with the polar angle within `[0, PI]`.
+fn normalize(point: &Point) -> (f32, f32) {
+    let unit = point.to_point3d(1.0);
+    (unit.y.clamp(-1.0, 1.0).acos(), unit.x.atan2(unit.z))
+}
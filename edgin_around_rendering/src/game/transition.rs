@@ -0,0 +1,79 @@
+/// Visual effect with which an actor appears or disappears.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TransitionKind {
+    /// Changes the opacity of the sprite.
+    Fade,
+
+    /// Grows the sprite from nothing or shrinks it to nothing.
+    Scale,
+
+    /// Reveals or removes the sprite piece by piece in a noisy pattern.
+    Dissolve,
+}
+
+impl TransitionKind {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "fade" => Some(TransitionKind::Fade),
+            "scale" => Some(TransitionKind::Scale),
+            "dissolve" => Some(TransitionKind::Dissolve),
+            _ => None,
+        }
+    }
+}
+
+/// Modifiers of the appearance of an actor during a transition.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TransitionEffect {
+    /// Multiplier of the opacity of the sprite.
+    pub opacity: f32,
+
+    /// Multiplier of the scale of the sprite.
+    pub scale: f32,
+
+    /// Fraction of the sprite which is dissolved, within `[0, 1]`.
+    pub dissolve: f32,
+}
+
+impl Default for TransitionEffect {
+    fn default() -> Self {
+        Self { opacity: 1.0, scale: 1.0, dissolve: 0.0 }
+    }
+}
+
+/// Timed effect revealing or concealing an actor.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Transition {
+    pub kind: TransitionKind,
+
+    /// Duration of the transition in seconds.
+    pub duration: f32,
+}
+
+impl Transition {
+    pub fn new(kind: TransitionKind, duration: f32) -> Self {
+        Self { kind, duration }
+    }
+
+    /// Returns the completed fraction of the transition after `elapsed` seconds, within `[0, 1]`.
+    pub fn progress(&self, elapsed: f32) -> f32 {
+        if self.duration > 0.0 {
+            (elapsed / self.duration).clamp(0.0, 1.0)
+        } else {
+            1.0
+        }
+    }
+
+    /// Returns the effect showing the given fraction of the actor, zero meaning fully concealed
+    /// and one fully revealed.
+    pub fn effect(&self, visibility: f32) -> TransitionEffect {
+        let visibility = visibility.clamp(0.0, 1.0);
+        match self.kind {
+            TransitionKind::Fade => TransitionEffect { opacity: visibility, ..Default::default() },
+            TransitionKind::Scale => TransitionEffect { scale: visibility, ..Default::default() },
+            TransitionKind::Dissolve => {
+                TransitionEffect { dissolve: 1.0 - visibility, ..Default::default() }
+            }
+        }
+    }
+}
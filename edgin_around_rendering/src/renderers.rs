@@ -1,7 +1,21 @@
+mod decal;
 mod fixed;
+mod ground;
+mod highlight;
+mod particles;
+mod path;
 mod polyhedron;
 mod positioned;
+mod shadow;
 
+pub use decal::{
+    triangulate, Decal, DecalAnimation, DecalAnimationKind, DecalMesh, DecalRenderer, DecalShape,
+};
 pub use fixed::FixedRenderer;
+pub use ground::{GroundRenderer, LevelOfDetail};
+pub use highlight::{Highlight, HighlightStyle};
+pub use particles::ParticleRenderer;
+pub use path::{Dash, Path, PathMesh, PathRenderer};
 pub use polyhedron::PolyhedronRenderer;
-pub use positioned::PositionedRenderer;
+pub use positioned::{EntityUniforms, PositionedRenderer};
+pub use shadow::ShadowRenderer;
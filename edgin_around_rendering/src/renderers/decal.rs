@@ -0,0 +1,491 @@
+use std::{collections::HashMap, f32::consts::PI};
+
+use gl;
+
+use crate::utils::{
+    coordinates::{Point, Point3D},
+    defs::prelude::*,
+    ids::TextureId,
+};
+
+/// Distance in world units by which decals are lifted above the ground.
+const LIFT: f32 = 0.03;
+
+const MIN_SEGMENTS: usize = 12;
+const MAX_SEGMENTS: usize = 128;
+const MAX_RINGS: usize = 128;
+
+/// Maximal number of times triangles of polygons are split to follow the ground.
+const MAX_SUBDIVISIONS: u32 = 6;
+
+const FLOATS_PER_VERTEX: usize = 5;
+
+/// Area covered by a decal. Distances are given in world units and bearings in radians clockwise
+/// from the north.
+#[derive(Clone, Debug)]
+pub enum DecalShape {
+    Circle {
+        center: Point,
+        radius: f32,
+    },
+
+    /// Band between two circles around the same center.
+    Ring {
+        center: Point,
+        inner_radius: f32,
+        outer_radius: f32,
+    },
+
+    /// Slice of a disc spanning `angle` radians, split in half by the direction of `bearing`.
+    Sector {
+        center: Point,
+        radius: f32,
+        bearing: f32,
+        angle: f32,
+    },
+
+    /// Area enclosed by a simple polygon, e.g. a territory. It should be small enough for the
+    /// ground under it to be seen as a plane from its center, i.e. much smaller than the planet.
+    Polygon {
+        vertices: Vec<Point>,
+    },
+}
+
+impl DecalShape {
+    /// Tells if distances of the shape are finite and not negative, the inner radius of a ring is
+    /// not larger than the outer one and the angles are finite. Invalid shapes are not drawn.
+    pub fn is_valid(&self) -> bool {
+        let is_distance = |distance: f32| distance.is_finite() && distance >= 0.0;
+        match self {
+            DecalShape::Circle { radius, .. } => is_distance(*radius),
+            DecalShape::Ring { inner_radius, outer_radius, .. } => {
+                is_distance(*inner_radius)
+                    && is_distance(*outer_radius)
+                    && inner_radius <= outer_radius
+            }
+            DecalShape::Sector { radius, bearing, angle, .. } => {
+                is_distance(*radius) && bearing.is_finite() && angle.is_finite()
+            }
+            DecalShape::Polygon { .. } => true,
+        }
+    }
+}
+
+/// Way in which a decal changes over time.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DecalAnimationKind {
+    Static,
+
+    /// Changes the opacity of the decal back and forth.
+    Pulse,
+
+    /// Rotates the texture of the decal around its center.
+    Rotate,
+}
+
+impl DecalAnimationKind {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "static" => Some(DecalAnimationKind::Static),
+            "pulse" => Some(DecalAnimationKind::Pulse),
+            "rotate" => Some(DecalAnimationKind::Rotate),
+            _ => None,
+        }
+    }
+
+    /// Returns the identifier of the animation understood by the decal shader.
+    pub fn get_shader_id(&self) -> i32 {
+        match self {
+            DecalAnimationKind::Static => 0,
+            DecalAnimationKind::Pulse => 1,
+            DecalAnimationKind::Rotate => 2,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecalAnimation {
+    pub kind: DecalAnimationKind,
+
+    /// Angular speed of the animation in radians per second.
+    pub speed: f32,
+}
+
+impl DecalAnimation {
+    pub fn new(kind: DecalAnimationKind, speed: f32) -> Self {
+        Self { kind, speed }
+    }
+}
+
+impl Default for DecalAnimation {
+    fn default() -> Self {
+        Self::new(DecalAnimationKind::Static, 0.0)
+    }
+}
+
+/// Shape drawn on the ground, like a spell range, a selection circle or a territory border.
+#[derive(Clone, Debug)]
+pub struct Decal {
+    pub shape: DecalShape,
+
+    /// Color of the decal multiplying its texture, given as RGBA components within `[0, 1]`.
+    pub color: [f32; 4],
+
+    /// Name of the texture in the decals directory of the resources, without extension. The
+    /// texture is stretched over the square enclosing the shape.
+    pub texture: Option<String>,
+
+    pub animation: DecalAnimation,
+}
+
+impl Decal {
+    pub fn new(shape: DecalShape) -> Self {
+        Self {
+            shape,
+            color: [1.0, 1.0, 1.0, 0.5],
+            texture: None,
+            animation: DecalAnimation::default(),
+        }
+    }
+}
+
+/// Triangles covering the shape of a decal, with texture coordinates spanning the square
+/// enclosing it.
+#[derive(Clone, Debug, Default)]
+pub struct DecalMesh {
+    pub points: Vec<Point>,
+    pub tex_coords: Vec<(f32, f32)>,
+    pub triangles: Vec<(u32, u32, u32)>,
+}
+
+impl DecalMesh {
+    /// Builds the mesh of the shape on a planet with the given radius. Edges of the triangles are
+    /// not much longer than `step`, so that the mesh can follow the ground closely.
+    pub fn new(shape: &DecalShape, radius: f32, step: f32) -> Self {
+        let mut mesh = Self::default();
+        match shape {
+            DecalShape::Circle { center, radius: outer } => {
+                mesh.build_disc(center, (0.0, *outer), (0.0, 2.0 * PI), radius, step)
+            }
+            DecalShape::Ring { center, inner_radius, outer_radius } => mesh.build_disc(
+                center,
+                (*inner_radius, *outer_radius),
+                (0.0, 2.0 * PI),
+                radius,
+                step,
+            ),
+            DecalShape::Sector { center, radius: outer, bearing, angle } => mesh.build_disc(
+                center,
+                (0.0, *outer),
+                (bearing - 0.5 * angle, angle.min(2.0 * PI)),
+                radius,
+                step,
+            ),
+            DecalShape::Polygon { vertices } => mesh.build_polygon(vertices, radius, step),
+        }
+        mesh
+    }
+
+    fn push(&mut self, point: Point, tex_coords: (f32, f32)) -> u32 {
+        self.points.push(point);
+        self.tex_coords.push(tex_coords);
+        (self.points.len() - 1) as u32
+    }
+
+    /// Builds a disc, ring or sector between the inner and outer distance from the center,
+    /// spanning `sweep` radians clockwise from the `start` bearing.
+    fn build_disc(
+        &mut self,
+        center: &Point,
+        (inner, outer): (f32, f32),
+        (start, sweep): (f32, f32),
+        radius: f32,
+        step: f32,
+    ) {
+        if !(outer > inner && outer.is_finite() && sweep > 0.0) {
+            return;
+        }
+
+        let closed = sweep >= 2.0 * PI;
+        let segments = ((sweep * outer / step).ceil() as usize).clamp(MIN_SEGMENTS, MAX_SEGMENTS);
+        let columns = if closed { segments } else { segments + 1 };
+        let rings = (((outer - inner) / step).ceil() as usize).clamp(1, MAX_RINGS);
+
+        // With no hole the innermost ring collapses into the center
+        let first_ring = if inner > 0.0 { 0 } else { 1 };
+        let center_index =
+            if inner > 0.0 { None } else { Some(self.push(center.clone(), (0.5, 0.5))) };
+
+        let base = self.points.len() as u32;
+        for i in first_ring..=rings {
+            let distance = inner + (outer - inner) * i as f32 / rings as f32;
+            for j in 0..columns {
+                let bearing = start + sweep * j as f32 / segments as f32;
+                let (x, y) = (distance * bearing.sin(), distance * bearing.cos());
+                let point = center.moved_by(distance, bearing, radius);
+                self.push(point, (0.5 + 0.5 * x / outer, 0.5 - 0.5 * y / outer));
+            }
+        }
+
+        let index = |i: usize, j: usize| base + ((i - first_ring) * columns + j % columns) as u32;
+        for j in 0..segments {
+            if let Some(center_index) = center_index {
+                self.triangles.push((center_index, index(1, j), index(1, j + 1)));
+            }
+            for i in first_ring..rings {
+                self.triangles.push((index(i, j), index(i + 1, j), index(i, j + 1)));
+                self.triangles.push((index(i, j + 1), index(i + 1, j), index(i + 1, j + 1)));
+            }
+        }
+    }
+
+    fn build_polygon(&mut self, vertices: &[Point], radius: f32, step: f32) {
+        if vertices.len() < 3 {
+            return;
+        }
+
+        // Lay the polygon out on the plane touching the planet at its center
+        let sum = vertices
+            .iter()
+            .fold(Point3D::new(0.0, 0.0, 0.0), |sum, vertex| &sum + &vertex.to_point3d(1.0));
+        let center = Point::from_point3d(&sum);
+        let planar: Vec<(f32, f32)> = vertices
+            .iter()
+            .map(|vertex| {
+                let distance = Point::great_circle_distance(&center, vertex, radius);
+                let bearing = Point::initial_bearing(&center, vertex);
+                (distance * bearing.sin(), distance * bearing.cos())
+            })
+            .collect();
+        let extent = planar.iter().map(|(x, y)| x.abs().max(y.abs())).fold(f32::EPSILON, f32::max);
+
+        let base = self.points.len() as u32;
+        for (vertex, (x, y)) in vertices.iter().zip(planar.iter()) {
+            self.push(vertex.clone(), (0.5 + 0.5 * x / extent, 0.5 - 0.5 * y / extent));
+        }
+
+        let mut midpoints = HashMap::new();
+        for (a, b, c) in triangulate(&planar) {
+            let triangle = (base + a as u32, base + b as u32, base + c as u32);
+            self.subdivide(&mut midpoints, triangle, radius, step, 0);
+        }
+    }
+
+    /// Splits the triangle into four until its edges are not longer than `step`.
+    fn subdivide(
+        &mut self,
+        midpoints: &mut HashMap<(u32, u32), u32>,
+        (a, b, c): (u32, u32, u32),
+        radius: f32,
+        step: f32,
+        depth: u32,
+    ) {
+        let length = |i: u32, j: u32| {
+            Point::great_circle_distance(&self.points[i as usize], &self.points[j as usize], radius)
+        };
+        if depth >= MAX_SUBDIVISIONS || length(a, b).max(length(b, c)).max(length(c, a)) <= step {
+            self.triangles.push((a, b, c));
+            return;
+        }
+
+        let ab = self.midpoint(midpoints, a, b);
+        let bc = self.midpoint(midpoints, b, c);
+        let ca = self.midpoint(midpoints, c, a);
+        for triangle in [(a, ab, ca), (ab, b, bc), (ca, bc, c), (ab, bc, ca)] {
+            self.subdivide(midpoints, triangle, radius, step, depth + 1);
+        }
+    }
+
+    /// Returns the index of the vertex in the middle of the edge, shared by both triangles
+    /// adjacent to the edge.
+    fn midpoint(&mut self, midpoints: &mut HashMap<(u32, u32), u32>, a: u32, b: u32) -> u32 {
+        let key = (a.min(b), a.max(b));
+        if let Some(index) = midpoints.get(&key) {
+            return *index;
+        }
+
+        let (a, b) = (a as usize, b as usize);
+        let point = Point::midpoint(&self.points[a], &self.points[b]);
+        let (ua, va) = self.tex_coords[a];
+        let (ub, vb) = self.tex_coords[b];
+        let index = self.push(point, (0.5 * (ua + ub), 0.5 * (va + vb)));
+        midpoints.insert(key, index);
+        index
+    }
+}
+
+/// Tells how far `point` lies to the left of the line from `start` to `end`, as the doubled area
+/// of the triangle they make.
+fn turn(start: (f32, f32), end: (f32, f32), point: (f32, f32)) -> f32 {
+    (end.0 - start.0) * (point.1 - start.1) - (end.1 - start.1) * (point.0 - start.0)
+}
+
+/// Splits a simple polygon on a plane into triangles by clipping its ears. Returns indices of
+/// vertices of the triangles. Degenerate parts of the polygon are left out.
+pub fn triangulate(vertices: &[(f32, f32)]) -> Vec<(usize, usize, usize)> {
+    let mut remaining: Vec<usize> = (0..vertices.len()).collect();
+    let area: f32 = (0..vertices.len())
+        .map(|i| turn((0.0, 0.0), vertices[i], vertices[(i + 1) % vertices.len()]))
+        .sum();
+    if area < 0.0 {
+        remaining.reverse();
+    }
+
+    let is_ear = |remaining: &[usize], i: usize| {
+        let count = remaining.len();
+        let corners =
+            [remaining[(i + count - 1) % count], remaining[i], remaining[(i + 1) % count]];
+        let [a, b, c] = corners.map(|k| vertices[k]);
+        turn(a, b, c) > 0.0
+            && remaining.iter().filter(|k| !corners.contains(*k)).all(|k| {
+                let point = vertices[*k];
+                turn(a, b, point) < 0.0 || turn(b, c, point) < 0.0 || turn(c, a, point) < 0.0
+            })
+    };
+
+    let mut triangles = Vec::with_capacity(vertices.len().saturating_sub(2));
+    while remaining.len() > 3 {
+        match (0..remaining.len()).find(|i| is_ear(&remaining, *i)) {
+            Some(i) => {
+                let count = remaining.len();
+                let previous = remaining[(i + count - 1) % count];
+                let next = remaining[(i + 1) % count];
+                triangles.push((previous, remaining[i], next));
+                remaining.remove(i);
+            }
+            None => break,
+        }
+    }
+    if let [a, b, c] = remaining[..] {
+        if turn(vertices[a], vertices[b], vertices[c]) > 0.0 {
+            triangles.push((a, b, c));
+        }
+    }
+    triangles
+}
+
+/// Draws a decal draped over the ground.
+pub struct DecalRenderer {
+    texture_id: TextureId,
+    index_count: usize,
+    vao: gl::types::GLuint,
+    vbo: gl::types::GLuint,
+    ibo: gl::types::GLuint,
+}
+
+impl DecalRenderer {
+    /// Places the vertices of the mesh slightly above the ground, which at each point is
+    /// `elevation` away from the center of the planet.
+    pub fn new<E>(mesh: &DecalMesh, elevation: E, texture_id: TextureId) -> Self
+    where
+        E: Fn(&Point) -> f32,
+    {
+        let mut vertices =
+            Vec::<gl::types::GLfloat>::with_capacity(FLOATS_PER_VERTEX * mesh.points.len());
+        for (point, (u, v)) in mesh.points.iter().zip(mesh.tex_coords.iter()) {
+            let position = point.to_point3d(elevation(point) + LIFT);
+            vertices.extend_from_slice(&[position.x, position.y, position.z, *u, *v]);
+        }
+
+        let mut indices = Vec::<gl::types::GLuint>::with_capacity(3 * mesh.triangles.len());
+        for triangle in mesh.triangles.iter() {
+            indices.extend_from_slice(&[triangle.0, triangle.1, triangle.2]);
+        }
+
+        let mut mine = Self { texture_id, index_count: indices.len(), vao: 0, vbo: 0, ibo: 0 };
+
+        unsafe {
+            gl::GenVertexArrays(1, &mut mine.vao);
+            gl::GenBuffers(1, &mut mine.vbo);
+            gl::GenBuffers(1, &mut mine.ibo);
+
+            gl::BindVertexArray(mine.vao);
+            gl::BindBuffer(gl::ARRAY_BUFFER, mine.vbo);
+            gl::BindBuffer(gl::ELEMENT_ARRAY_BUFFER, mine.ibo);
+
+            gl::BufferData(
+                gl::ARRAY_BUFFER,
+                SIZEPTR_FLOAT * vertices.len() as gl::types::GLsizeiptr,
+                vertices.as_ptr() as *const _,
+                gl::STATIC_DRAW,
+            );
+            gl::BufferData(
+                gl::ELEMENT_ARRAY_BUFFER,
+                SIZEPTR_FLOAT * indices.len() as gl::types::GLsizeiptr,
+                indices.as_ptr() as *const _,
+                gl::STATIC_DRAW,
+            );
+
+            gl::BindBuffer(gl::ELEMENT_ARRAY_BUFFER, 0);
+            gl::BindBuffer(gl::ARRAY_BUFFER, 0);
+            gl::BindVertexArray(0);
+        }
+
+        mine
+    }
+
+    pub fn get_texture_id(&self) -> TextureId {
+        self.texture_id
+    }
+
+    pub fn render(&self) {
+        const LOC_POSITION: gl::types::GLuint = 0;
+        const LOC_TEX_COORDS: gl::types::GLuint = 1;
+        const SIZE_POSITION: gl::types::GLint = 3;
+        const SIZE_TEX_COORDS: gl::types::GLint = 2;
+        const PTR_POSITION: *const gl::types::GLvoid = 0 as _;
+        const PTR_TEX_COORDS: *const gl::types::GLvoid = (SIZE_POSITION * SIZE_FLOAT) as _;
+        const STRIDE: gl::types::GLint = (SIZE_POSITION + SIZE_TEX_COORDS) * SIZE_FLOAT;
+
+        unsafe {
+            gl::BindTexture(gl::TEXTURE_2D, self.texture_id);
+
+            gl::BindVertexArray(self.vao);
+            gl::BindBuffer(gl::ARRAY_BUFFER, self.vbo);
+            gl::BindBuffer(gl::ELEMENT_ARRAY_BUFFER, self.ibo);
+
+            gl::EnableVertexAttribArray(LOC_POSITION);
+            gl::VertexAttribPointer(
+                LOC_POSITION,
+                SIZE_POSITION,
+                gl::FLOAT,
+                gl::FALSE,
+                STRIDE,
+                PTR_POSITION,
+            );
+            gl::EnableVertexAttribArray(LOC_TEX_COORDS);
+            gl::VertexAttribPointer(
+                LOC_TEX_COORDS,
+                SIZE_TEX_COORDS,
+                gl::FLOAT,
+                gl::FALSE,
+                STRIDE,
+                PTR_TEX_COORDS,
+            );
+
+            gl::DrawElements(
+                gl::TRIANGLES,
+                self.index_count as gl::types::GLint,
+                gl::UNSIGNED_INT,
+                NULL,
+            );
+
+            gl::DisableVertexAttribArray(LOC_TEX_COORDS);
+            gl::DisableVertexAttribArray(LOC_POSITION);
+
+            gl::BindBuffer(gl::ELEMENT_ARRAY_BUFFER, 0);
+            gl::BindBuffer(gl::ARRAY_BUFFER, 0);
+            gl::BindVertexArray(0);
+        }
+    }
+}
+
+impl Drop for DecalRenderer {
+    fn drop(&mut self) {
+        unsafe {
+            gl::DeleteBuffers(1, &self.vbo);
+            gl::DeleteBuffers(1, &self.ibo);
+            gl::DeleteVertexArrays(1, &self.vao);
+        }
+    }
+}
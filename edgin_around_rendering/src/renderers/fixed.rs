@@ -58,6 +58,16 @@ impl FixedRenderer {
         }
     }
 
+    pub fn set_heading(&mut self, heading: Option<f32>) {
+        self.sprite.set_heading(heading);
+    }
+
+    /// Returns the position of the hook of the sprite at the current moment of its animation.
+    pub fn get_hook_position(&self, hook_name: &str) -> Option<(f32, f32)> {
+        let moment = (Instant::now() - self.start_instant).as_secs_f32();
+        self.sprite.get_hook_position(hook_name, moment)
+    }
+
     pub fn render(&mut self, sprites: &Sprites) {
         const LOC_POSITION: gl::types::GLuint = 0;
         const LOC_TEX_COORD: gl::types::GLuint = 1;
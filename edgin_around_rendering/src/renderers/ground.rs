@@ -0,0 +1,213 @@
+use std::collections::HashMap;
+
+use crate::{
+    renderers::PolyhedronRenderer,
+    utils::{coordinates, figures, ids::TextureId},
+};
+
+/// Parameters deciding how finely the ground chunks are subdivided.
+#[derive(Clone, Debug)]
+pub struct LevelOfDetail {
+    /// Number of subdivisions of the icosahedron giving the chunks.
+    pub base_level: u32,
+
+    /// Subdivision level of chunks far from the focus point.
+    pub min_level: u32,
+
+    /// Subdivision level of chunks close to the focus point.
+    pub max_level: u32,
+
+    /// Distance from the focus point within which chunks use the highest level. Each doubling of
+    /// this distance lowers the level by one.
+    pub detail_distance: f32,
+}
+
+/// Identifies the geometry of a chunk. A chunk is rebuilt only when its key changes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct ChunkKey {
+    level: u32,
+    edge_levels: [u32; 3],
+}
+
+struct Chunk {
+    corners: [coordinates::Point3D; 3],
+    center: coordinates::Point3D,
+    extent: f32,
+    side: f32,
+    neighbours: [Option<usize>; 3],
+    key: Option<ChunkKey>,
+    renderer: Option<PolyhedronRenderer>,
+}
+
+/// Renders a sphere-like surface split into chunks with level of detail depending on the distance
+/// from the focus point.
+///
+/// Chunks behind the visible distance are not rendered. Edges shared by chunks with different
+/// levels use the lower one on both sides so the surface stays crack-free.
+pub struct GroundRenderer {
+    texture_id: TextureId,
+    lod: LevelOfDetail,
+    radius: f32,
+    chunks: Vec<Chunk>,
+}
+
+impl GroundRenderer {
+    pub fn new(texture_id: TextureId, lod: LevelOfDetail, radius: f32) -> Self {
+        let base = figures::sphere(lod.base_level, 1.0);
+        let vertices = base.get_vertices();
+
+        let mut edges = HashMap::<(u32, u32), Vec<usize>>::new();
+        for (i, t) in base.get_triangles().iter().enumerate() {
+            for (a, b) in [(t.0, t.1), (t.1, t.2), (t.2, t.0)].iter() {
+                edges.entry((*a.min(b), *a.max(b))).or_default().push(i);
+            }
+        }
+
+        let mut chunks = Vec::with_capacity(base.get_triangles().len());
+        for (i, t) in base.get_triangles().iter().enumerate() {
+            let corners = [
+                vertices[t.0 as usize].clone(),
+                vertices[t.1 as usize].clone(),
+                vertices[t.2 as usize].clone(),
+            ];
+            let center = (&(&corners[0] + &corners[1]) + &corners[2]).enlongated(1.0);
+            let extent = corners.iter().map(|c| angle(&center, c)).fold(0.0, f32::max);
+            let side = angle(&corners[0], &corners[1]);
+
+            let mut neighbours = [None; 3];
+            for (n, (a, b)) in [(t.0, t.1), (t.1, t.2), (t.2, t.0)].iter().enumerate() {
+                neighbours[n] = edges[&(*a.min(b), *a.max(b))].iter().cloned().find(|&j| j != i);
+            }
+
+            chunks.push(Chunk {
+                corners,
+                center,
+                extent,
+                side,
+                neighbours,
+                key: None,
+                renderer: None,
+            });
+        }
+
+        Self { texture_id, lod, radius, chunks }
+    }
+
+    /// Chooses levels of all chunks for the given focus point and rebuilds those which changed.
+    /// Chunks farther than `visible_distance` from the focus are dropped. The `stretch` function
+    /// gives the distance of the surface from the center and `value` the scalar attached to each
+    /// vertex.
+    pub fn update<S, V>(
+        &mut self,
+        focus: &coordinates::Point,
+        visible_distance: f32,
+        stretch: S,
+        value: V,
+    ) where
+        S: Fn(f32, f32) -> f32,
+        V: Fn(f32, f32) -> f32,
+    {
+        let focus = focus.to_point3d(1.0);
+        let levels: Vec<Option<u32>> = self
+            .chunks
+            .iter()
+            .map(|chunk| self.choose_level(chunk, &focus, visible_distance))
+            .collect();
+
+        let texture_id = self.texture_id;
+        for (i, chunk) in self.chunks.iter_mut().enumerate() {
+            let key = levels[i].map(|level| {
+                let mut edge_levels = [level; 3];
+                for (n, neighbour) in chunk.neighbours.iter().enumerate() {
+                    if let Some(Some(neighbour_level)) = neighbour.map(|j| levels[j]) {
+                        edge_levels[n] = level.min(neighbour_level);
+                    }
+                }
+                ChunkKey { level, edge_levels }
+            });
+
+            if key != chunk.key {
+                chunk.renderer = key.map(|key| {
+                    PolyhedronRenderer::new(texture_id, chunk.build(&key, &stretch, &value))
+                });
+                chunk.key = key;
+            }
+        }
+    }
+
+    /// Re-uploads vertices of the built chunks lying within `range` around `center`, e.g. after
+    /// the terrain there was edited. Vertices slightly outside the range are updated too, because
+    /// their normals depend on the neighbouring vertices.
+    pub fn refresh<S, V>(&mut self, center: &coordinates::Point, range: f32, stretch: S, value: V)
+    where
+        S: Fn(f32, f32) -> f32,
+        V: Fn(f32, f32) -> f32,
+    {
+        let center = center.to_point3d(1.0);
+        for chunk in self.chunks.iter_mut() {
+            if let Some(key) = chunk.key {
+                let step = chunk.side / (1u32 << key.level) as f32;
+                let limit = range / self.radius + 2.0 * step;
+                if angle(&chunk.center, &center) - chunk.extent > limit {
+                    continue;
+                }
+
+                let figure = chunk.build(&key, &stretch, &value);
+                let indices: Vec<usize> = figure
+                    .get_vertices()
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, vertex)| angle(&vertex.enlongated(1.0), &center) <= limit)
+                    .map(|(i, _)| i)
+                    .collect();
+                if let Some(renderer) = &mut chunk.renderer {
+                    renderer.update_vertices(&figure, &indices);
+                }
+            }
+        }
+    }
+
+    pub fn render(&self) {
+        for chunk in self.chunks.iter() {
+            if let Some(renderer) = &chunk.renderer {
+                renderer.render();
+            }
+        }
+    }
+}
+
+impl Chunk {
+    fn build<S, V>(&self, key: &ChunkKey, stretch: &S, value: &V) -> figures::Polyhedron
+    where
+        S: Fn(f32, f32) -> f32,
+        V: Fn(f32, f32) -> f32,
+    {
+        let corners = [&self.corners[0], &self.corners[1], &self.corners[2]];
+        let mut figure = figures::sphere_chunk(corners, key.level, key.edge_levels, stretch);
+        figure.assign_values(value);
+        figure
+    }
+}
+
+impl GroundRenderer {
+    fn choose_level(
+        &self,
+        chunk: &Chunk,
+        focus: &coordinates::Point3D,
+        visible_distance: f32,
+    ) -> Option<u32> {
+        let distance = self.radius * (angle(&chunk.center, focus) - chunk.extent).max(0.0);
+        if distance > visible_distance {
+            None
+        } else {
+            let ratio = (distance / self.lod.detail_distance).max(1.0);
+            let decrease = ratio.log2().floor() as u32;
+            Some(self.lod.max_level.saturating_sub(decrease).max(self.lod.min_level))
+        }
+    }
+}
+
+/// Returns angle between two unit vectors.
+fn angle(a: &coordinates::Point3D, b: &coordinates::Point3D) -> f32 {
+    (a.x * b.x + a.y * b.y + a.z * b.z).clamp(-1.0, 1.0).acos()
+}
@@ -0,0 +1,54 @@
+/// Way in which a highlighted sprite is marked.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HighlightStyle {
+    /// Mixes the sprite with the highlight color.
+    Tint,
+
+    /// Mixes the sprite with the highlight color with strength changing over time.
+    Pulse,
+
+    /// Draws a line of the highlight color around the opaque parts of the sprite.
+    Outline,
+}
+
+impl HighlightStyle {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "tint" => Some(HighlightStyle::Tint),
+            "pulse" => Some(HighlightStyle::Pulse),
+            "outline" => Some(HighlightStyle::Outline),
+            _ => None,
+        }
+    }
+
+    /// Returns the identifier of the style understood by the entities shader. Zero means no
+    /// highlight.
+    pub fn get_shader_id(&self) -> i32 {
+        match self {
+            HighlightStyle::Tint => 1,
+            HighlightStyle::Pulse => 2,
+            HighlightStyle::Outline => 3,
+        }
+    }
+}
+
+/// Style and color of a highlighted sprite.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Highlight {
+    pub style: HighlightStyle,
+
+    /// RGB color of the highlight, with the alpha channel being its strength.
+    pub color: [f32; 4],
+}
+
+impl Highlight {
+    pub fn new(style: HighlightStyle, color: [f32; 4]) -> Self {
+        Self { style, color }
+    }
+}
+
+impl Default for Highlight {
+    fn default() -> Self {
+        Self { style: HighlightStyle::Tint, color: [1.0, 1.0, 1.0, 0.5] }
+    }
+}
@@ -0,0 +1,221 @@
+use std::cmp::Ordering;
+
+use gl;
+
+use crate::{
+    game::ParticleEmitter,
+    utils::{
+        defs::prelude::*,
+        geometry::{Matrix3D, Vector3D},
+        ids::TextureId,
+    },
+};
+
+/// Corners of the square of each particle facing the camera.
+const CORNERS: [(f32, f32); 4] = [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)];
+
+const FLOATS_PER_VERTEX: usize = 10;
+
+/// Draws particles of an emitter as squares facing the camera. Vertices are uploaded anew each
+/// frame.
+pub struct ParticleRenderer {
+    texture_id: TextureId,
+    capacity: usize,
+    count: usize,
+    camera_distance: f32,
+    vao: gl::types::GLuint,
+    vbo: gl::types::GLuint,
+    ibo: gl::types::GLuint,
+}
+
+impl ParticleRenderer {
+    pub fn new(texture_id: TextureId) -> Self {
+        let mut mine = Self {
+            texture_id,
+            capacity: 0,
+            count: 0,
+            camera_distance: 0.0,
+            vao: 0,
+            vbo: 0,
+            ibo: 0,
+        };
+
+        unsafe {
+            gl::GenVertexArrays(1, &mut mine.vao);
+            gl::GenBuffers(1, &mut mine.vbo);
+            gl::GenBuffers(1, &mut mine.ibo);
+        }
+
+        mine
+    }
+
+    pub fn get_texture_id(&self) -> TextureId {
+        self.texture_id
+    }
+
+    /// Returns the distance from the camera to the center of the particles, as used for sorting
+    /// them with entities.
+    pub fn get_camera_distance(&self) -> f32 {
+        self.camera_distance
+    }
+
+    /// Uploads the particles of the emitter sorted from the farthest to the nearest one.
+    pub fn load(&mut self, emitter: &ParticleEmitter, view: &Matrix3D) {
+        let depth = |x: f32, y: f32, z: f32| {
+            let point = view * Vector3D::new(x, y, z);
+            point.get_z() / point.get_w()
+        };
+
+        let particles = emitter.get_particles();
+        let mut order: Vec<(f32, usize)> = particles
+            .iter()
+            .enumerate()
+            .map(|(i, particle)| {
+                let position = &particle.position;
+                (depth(position.x, position.y, position.z), i)
+            })
+            .collect();
+        order.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(Ordering::Equal));
+
+        let (mut x, mut y, mut z) = (0.0, 0.0, 0.0);
+        let mut vertices = Vec::<gl::types::GLfloat>::with_capacity(
+            FLOATS_PER_VERTEX * CORNERS.len() * particles.len(),
+        );
+        for (_, i) in order.iter() {
+            let particle = &particles[*i];
+            let (size, [red, green, blue, alpha]) = emitter.get_look(particle);
+            let position = &particle.position;
+            for (u, v) in CORNERS.iter() {
+                vertices.extend_from_slice(&[position.x, position.y, position.z, *u, *v, size]);
+                vertices.extend_from_slice(&[red, green, blue, alpha]);
+            }
+            x += position.x;
+            y += position.y;
+            z += position.z;
+        }
+
+        self.count = particles.len();
+        if self.count > 0 {
+            let n = self.count as f32;
+            self.camera_distance = depth(x / n, y / n, z / n);
+        }
+
+        unsafe {
+            gl::BindVertexArray(self.vao);
+            gl::BindBuffer(gl::ARRAY_BUFFER, self.vbo);
+            gl::BufferData(
+                gl::ARRAY_BUFFER,
+                SIZEPTR_FLOAT * vertices.len() as gl::types::GLsizeiptr,
+                vertices.as_ptr() as *const _,
+                gl::STREAM_DRAW,
+            );
+            if self.count > self.capacity {
+                self.capacity = self.count.next_power_of_two();
+                self.load_indices();
+            }
+            gl::BindBuffer(gl::ARRAY_BUFFER, 0);
+            gl::BindVertexArray(0);
+        }
+    }
+
+    pub fn render(&self) {
+        const LOC_POSITION: gl::types::GLuint = 0;
+        const LOC_CORNER: gl::types::GLuint = 1;
+        const LOC_SIZE: gl::types::GLuint = 2;
+        const LOC_COLOR: gl::types::GLuint = 3;
+        const SIZE_POSITION: gl::types::GLint = 3;
+        const SIZE_CORNER: gl::types::GLint = 2;
+        const SIZE_SIZE: gl::types::GLint = 1;
+        const SIZE_COLOR: gl::types::GLint = 4;
+        const PTR_POSITION: *const gl::types::GLvoid = 0 as _;
+        const PTR_CORNER: *const gl::types::GLvoid = (SIZE_POSITION * SIZE_FLOAT) as _;
+        const PTR_SIZE: *const gl::types::GLvoid =
+            ((SIZE_POSITION + SIZE_CORNER) * SIZE_FLOAT) as _;
+        const PTR_COLOR: *const gl::types::GLvoid =
+            ((SIZE_POSITION + SIZE_CORNER + SIZE_SIZE) * SIZE_FLOAT) as _;
+        const STRIDE: gl::types::GLint =
+            (SIZE_POSITION + SIZE_CORNER + SIZE_SIZE + SIZE_COLOR) * SIZE_FLOAT;
+
+        if self.count == 0 {
+            return;
+        }
+
+        unsafe {
+            gl::BindTexture(gl::TEXTURE_2D, self.texture_id);
+
+            gl::BindVertexArray(self.vao);
+            gl::BindBuffer(gl::ARRAY_BUFFER, self.vbo);
+            gl::BindBuffer(gl::ELEMENT_ARRAY_BUFFER, self.ibo);
+
+            gl::EnableVertexAttribArray(LOC_POSITION);
+            gl::VertexAttribPointer(
+                LOC_POSITION,
+                SIZE_POSITION,
+                gl::FLOAT,
+                gl::FALSE,
+                STRIDE,
+                PTR_POSITION,
+            );
+            gl::EnableVertexAttribArray(LOC_CORNER);
+            gl::VertexAttribPointer(
+                LOC_CORNER,
+                SIZE_CORNER,
+                gl::FLOAT,
+                gl::FALSE,
+                STRIDE,
+                PTR_CORNER,
+            );
+            gl::EnableVertexAttribArray(LOC_SIZE);
+            gl::VertexAttribPointer(LOC_SIZE, SIZE_SIZE, gl::FLOAT, gl::FALSE, STRIDE, PTR_SIZE);
+            gl::EnableVertexAttribArray(LOC_COLOR);
+            gl::VertexAttribPointer(LOC_COLOR, SIZE_COLOR, gl::FLOAT, gl::FALSE, STRIDE, PTR_COLOR);
+
+            gl::DrawElements(
+                gl::TRIANGLES,
+                (VERTICES_PER_RECT_SIZE * self.count) as gl::types::GLint,
+                gl::UNSIGNED_INT,
+                NULL,
+            );
+
+            gl::DisableVertexAttribArray(LOC_COLOR);
+            gl::DisableVertexAttribArray(LOC_SIZE);
+            gl::DisableVertexAttribArray(LOC_CORNER);
+            gl::DisableVertexAttribArray(LOC_POSITION);
+
+            gl::BindBuffer(gl::ELEMENT_ARRAY_BUFFER, 0);
+            gl::BindBuffer(gl::ARRAY_BUFFER, 0);
+            gl::BindVertexArray(0);
+        }
+    }
+}
+
+impl ParticleRenderer {
+    /// Uploads indices of squares for as many particles as the capacity allows. The vertex array
+    /// has to be bound.
+    unsafe fn load_indices(&self) {
+        let mut indices =
+            Vec::<gl::types::GLuint>::with_capacity(VERTICES_PER_RECT_SIZE * self.capacity);
+        for num in 0..self.capacity as gl::types::GLuint {
+            for offset in [0, 1, 2, 2, 3, 0].iter() {
+                indices.push(4 * num + offset);
+            }
+        }
+        gl::BindBuffer(gl::ELEMENT_ARRAY_BUFFER, self.ibo);
+        gl::BufferData(
+            gl::ELEMENT_ARRAY_BUFFER,
+            SIZEPTR_FLOAT * indices.len() as gl::types::GLsizeiptr,
+            indices.as_ptr() as *const _,
+            gl::STATIC_DRAW,
+        );
+    }
+}
+
+impl Drop for ParticleRenderer {
+    fn drop(&mut self) {
+        unsafe {
+            gl::DeleteBuffers(1, &self.vbo);
+            gl::DeleteBuffers(1, &self.ibo);
+            gl::DeleteVertexArrays(1, &self.vao);
+        }
+    }
+}
@@ -0,0 +1,304 @@
+use std::f32::consts::PI;
+
+use gl;
+
+use crate::utils::{coordinates::Point, defs::prelude::*, ids::TextureId};
+
+/// Distance in world units by which paths are lifted above the ground. Paths are drawn over
+/// decals, so they are lifted a bit higher.
+const LIFT: f32 = 0.04;
+
+/// Points of a path closer to each other than this distance are merged.
+const MIN_SEGMENT_LENGTH: f32 = 0.001;
+
+/// Limits widening of the ribbon in sharp corners.
+const MAX_MITER: f32 = 2.0;
+
+const DEFAULT_COLOR: [f32; 4] = [1.0, 1.0, 1.0, 1.0];
+
+const FLOATS_PER_VERTEX: usize = 9;
+
+/// Pattern of dashes along a path. Lengths are given in world units.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Dash {
+    pub length: f32,
+    pub gap: f32,
+}
+
+impl Dash {
+    pub fn new(length: f32, gap: f32) -> Self {
+        Self { length, gap }
+    }
+}
+
+/// Line drawn on the ground through a sequence of points, like a planned route, a road or a river.
+/// The points are connected along great circles.
+#[derive(Clone, Debug)]
+pub struct Path {
+    pub points: Vec<Point>,
+
+    /// Colors at the points of the path blended along the segments between them, given as RGBA
+    /// components within `[0, 1]`. Points without a color of their own take the last color.
+    pub colors: Vec<[f32; 4]>,
+
+    /// Width of the ribbon in world units.
+    pub width: f32,
+
+    /// Pattern of dashes. The path is solid if not given.
+    pub dash: Option<Dash>,
+
+    /// Name of the texture in the paths directory of the resources, without extension. The
+    /// texture is repeated along the path in squares as wide as the ribbon.
+    pub texture: Option<String>,
+
+    /// Speed in world units per second at which dashes and the texture move along the path.
+    /// Negative speed moves them towards the first point.
+    pub scroll_speed: f32,
+}
+
+impl Path {
+    pub fn new(points: Vec<Point>, width: f32) -> Self {
+        Self {
+            points,
+            colors: vec![DEFAULT_COLOR],
+            width,
+            dash: None,
+            texture: None,
+            scroll_speed: 0.0,
+        }
+    }
+
+    /// Returns the color at the point with the given index.
+    pub fn get_color(&self, index: usize) -> [f32; 4] {
+        self.colors.get(index).or_else(|| self.colors.last()).cloned().unwrap_or(DEFAULT_COLOR)
+    }
+}
+
+/// Triangles of the ribbon covering a path. The first texture coordinate of each vertex is the
+/// distance along the path from its first point, the second one is `0` on the left edge of the
+/// ribbon and `1` on the right one.
+#[derive(Clone, Debug, Default)]
+pub struct PathMesh {
+    pub points: Vec<Point>,
+    pub tex_coords: Vec<(f32, f32)>,
+    pub colors: Vec<[f32; 4]>,
+    pub triangles: Vec<(u32, u32, u32)>,
+}
+
+impl PathMesh {
+    /// Builds the mesh of the path on a planet with the given radius. Segments of the path are
+    /// split into pieces not longer than `step`, so that the ribbon can follow the ground closely.
+    pub fn new(path: &Path, radius: f32, step: f32) -> Self {
+        let mut mesh = Self::default();
+
+        // Repeated points have no direction, so they are left out
+        let mut corners: Vec<(Point, [f32; 4])> = Vec::with_capacity(path.points.len());
+        for (i, point) in path.points.iter().enumerate() {
+            let repeated = corners.last().is_some_and(|(last, _)| {
+                Point::great_circle_distance(last, point, radius) < MIN_SEGMENT_LENGTH
+            });
+            if !repeated {
+                corners.push((point.clone(), path.get_color(i)));
+            }
+        }
+        if corners.len() < 2 {
+            return mesh;
+        }
+
+        let mut samples: Vec<(Point, [f32; 4], f32)> = Vec::new();
+        let mut distance = 0.0;
+        for pair in corners.windows(2) {
+            let ((start, start_color), (end, end_color)) = (&pair[0], &pair[1]);
+            let length = Point::great_circle_distance(start, end, radius);
+            let pieces = (length / step).ceil().max(1.0) as usize;
+            for k in 0..pieces {
+                let fraction = k as f32 / pieces as f32;
+                let point = Point::interpolate(start, end, fraction);
+                let color = mix(start_color, end_color, fraction);
+                samples.push((point, color, distance + length * fraction));
+            }
+            distance += length;
+        }
+        let (last, last_color) = &corners[corners.len() - 1];
+        samples.push((last.clone(), *last_color, distance));
+
+        let count = samples.len();
+        for i in 0..count {
+            let (point, color, distance) = &samples[i];
+            let (bearing, miter) = if i == 0 {
+                (Point::initial_bearing(point, &samples[1].0), 1.0)
+            } else if i == count - 1 {
+                (Point::final_bearing(&samples[i - 1].0, point), 1.0)
+            } else {
+                // Turn half way in corners, widening the ribbon to keep its edges parallel
+                let incoming = Point::final_bearing(&samples[i - 1].0, point);
+                let outgoing = Point::initial_bearing(point, &samples[i + 1].0);
+                let turn = (outgoing - incoming + PI).rem_euclid(2.0 * PI) - PI;
+                (incoming + 0.5 * turn, (1.0 / (0.5 * turn).cos()).min(MAX_MITER))
+            };
+
+            let offset = 0.5 * path.width * miter;
+            let left = point.moved_by(offset, bearing - 0.5 * PI, radius);
+            let right = point.moved_by(offset, bearing + 0.5 * PI, radius);
+            mesh.push(left, (*distance, 0.0), *color);
+            mesh.push(right, (*distance, 1.0), *color);
+        }
+
+        for i in 0..(count - 1) as u32 {
+            let (left, right, next_left, next_right) = (2 * i, 2 * i + 1, 2 * i + 2, 2 * i + 3);
+            mesh.triangles.push((left, next_left, right));
+            mesh.triangles.push((right, next_left, next_right));
+        }
+        mesh
+    }
+
+    fn push(&mut self, point: Point, tex_coords: (f32, f32), color: [f32; 4]) {
+        self.points.push(point);
+        self.tex_coords.push(tex_coords);
+        self.colors.push(color);
+    }
+}
+
+fn mix(start: &[f32; 4], end: &[f32; 4], fraction: f32) -> [f32; 4] {
+    let mut result = [0.0; 4];
+    for (i, component) in result.iter_mut().enumerate() {
+        *component = start[i] + (end[i] - start[i]) * fraction;
+    }
+    result
+}
+
+/// Draws a path as a ribbon lying on the ground.
+pub struct PathRenderer {
+    texture_id: TextureId,
+    index_count: usize,
+    vao: gl::types::GLuint,
+    vbo: gl::types::GLuint,
+    ibo: gl::types::GLuint,
+}
+
+impl PathRenderer {
+    /// Places the vertices of the mesh slightly above the ground, which at each point is
+    /// `elevation` away from the center of the planet.
+    pub fn new<E>(mesh: &PathMesh, elevation: E, texture_id: TextureId) -> Self
+    where
+        E: Fn(&Point) -> f32,
+    {
+        let mut vertices =
+            Vec::<gl::types::GLfloat>::with_capacity(FLOATS_PER_VERTEX * mesh.points.len());
+        let attributes = mesh.tex_coords.iter().zip(mesh.colors.iter());
+        for (point, ((u, v), color)) in mesh.points.iter().zip(attributes) {
+            let position = point.to_point3d(elevation(point) + LIFT);
+            vertices.extend_from_slice(&[position.x, position.y, position.z, *u, *v]);
+            vertices.extend_from_slice(color);
+        }
+
+        let mut indices = Vec::<gl::types::GLuint>::with_capacity(3 * mesh.triangles.len());
+        for triangle in mesh.triangles.iter() {
+            indices.extend_from_slice(&[triangle.0, triangle.1, triangle.2]);
+        }
+
+        let mut mine = Self { texture_id, index_count: indices.len(), vao: 0, vbo: 0, ibo: 0 };
+
+        unsafe {
+            gl::GenVertexArrays(1, &mut mine.vao);
+            gl::GenBuffers(1, &mut mine.vbo);
+            gl::GenBuffers(1, &mut mine.ibo);
+
+            gl::BindVertexArray(mine.vao);
+            gl::BindBuffer(gl::ARRAY_BUFFER, mine.vbo);
+            gl::BindBuffer(gl::ELEMENT_ARRAY_BUFFER, mine.ibo);
+
+            gl::BufferData(
+                gl::ARRAY_BUFFER,
+                SIZEPTR_FLOAT * vertices.len() as gl::types::GLsizeiptr,
+                vertices.as_ptr() as *const _,
+                gl::STATIC_DRAW,
+            );
+            gl::BufferData(
+                gl::ELEMENT_ARRAY_BUFFER,
+                SIZEPTR_FLOAT * indices.len() as gl::types::GLsizeiptr,
+                indices.as_ptr() as *const _,
+                gl::STATIC_DRAW,
+            );
+
+            gl::BindBuffer(gl::ELEMENT_ARRAY_BUFFER, 0);
+            gl::BindBuffer(gl::ARRAY_BUFFER, 0);
+            gl::BindVertexArray(0);
+        }
+
+        mine
+    }
+
+    pub fn get_texture_id(&self) -> TextureId {
+        self.texture_id
+    }
+
+    pub fn render(&self) {
+        const LOC_POSITION: gl::types::GLuint = 0;
+        const LOC_TEX_COORDS: gl::types::GLuint = 1;
+        const LOC_COLOR: gl::types::GLuint = 2;
+        const SIZE_POSITION: gl::types::GLint = 3;
+        const SIZE_TEX_COORDS: gl::types::GLint = 2;
+        const SIZE_COLOR: gl::types::GLint = 4;
+        const PTR_POSITION: *const gl::types::GLvoid = 0 as _;
+        const PTR_TEX_COORDS: *const gl::types::GLvoid = (SIZE_POSITION * SIZE_FLOAT) as _;
+        const PTR_COLOR: *const gl::types::GLvoid =
+            ((SIZE_POSITION + SIZE_TEX_COORDS) * SIZE_FLOAT) as _;
+        const STRIDE: gl::types::GLint =
+            (SIZE_POSITION + SIZE_TEX_COORDS + SIZE_COLOR) * SIZE_FLOAT;
+
+        unsafe {
+            gl::BindTexture(gl::TEXTURE_2D, self.texture_id);
+
+            gl::BindVertexArray(self.vao);
+            gl::BindBuffer(gl::ARRAY_BUFFER, self.vbo);
+            gl::BindBuffer(gl::ELEMENT_ARRAY_BUFFER, self.ibo);
+
+            gl::EnableVertexAttribArray(LOC_POSITION);
+            gl::VertexAttribPointer(
+                LOC_POSITION,
+                SIZE_POSITION,
+                gl::FLOAT,
+                gl::FALSE,
+                STRIDE,
+                PTR_POSITION,
+            );
+            gl::EnableVertexAttribArray(LOC_TEX_COORDS);
+            gl::VertexAttribPointer(
+                LOC_TEX_COORDS,
+                SIZE_TEX_COORDS,
+                gl::FLOAT,
+                gl::FALSE,
+                STRIDE,
+                PTR_TEX_COORDS,
+            );
+            gl::EnableVertexAttribArray(LOC_COLOR);
+            gl::VertexAttribPointer(LOC_COLOR, SIZE_COLOR, gl::FLOAT, gl::FALSE, STRIDE, PTR_COLOR);
+
+            gl::DrawElements(
+                gl::TRIANGLES,
+                self.index_count as gl::types::GLint,
+                gl::UNSIGNED_INT,
+                NULL,
+            );
+
+            gl::DisableVertexAttribArray(LOC_COLOR);
+            gl::DisableVertexAttribArray(LOC_TEX_COORDS);
+            gl::DisableVertexAttribArray(LOC_POSITION);
+
+            gl::BindBuffer(gl::ELEMENT_ARRAY_BUFFER, 0);
+            gl::BindBuffer(gl::ARRAY_BUFFER, 0);
+            gl::BindVertexArray(0);
+        }
+    }
+}
+
+impl Drop for PathRenderer {
+    fn drop(&mut self) {
+        unsafe {
+            gl::DeleteBuffers(1, &self.vbo);
+            gl::DeleteBuffers(1, &self.ibo);
+            gl::DeleteVertexArrays(1, &self.vao);
+        }
+    }
+}
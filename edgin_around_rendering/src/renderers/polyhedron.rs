@@ -2,6 +2,16 @@ use gl;
 
 use crate::utils::{defs::prelude::*, figures, ids::TextureId};
 
+const FLOATS_PER_VERTEX: usize = 7;
+
+/// Appends position, normal and value of the vertex with the given index to `data`.
+fn push_vertex(data: &mut Vec<gl::types::GLfloat>, figure: &figures::Polyhedron, index: usize) {
+    let vertex = &figure.get_vertices()[index];
+    let normal = &figure.get_normals()[index];
+    let value = figure.get_values()[index];
+    data.extend_from_slice(&[vertex.x, vertex.y, vertex.z, normal.x, normal.y, normal.z, value]);
+}
+
 pub struct PolyhedronRenderer {
     texture_id: TextureId,
     index_count: usize,
@@ -12,12 +22,11 @@ pub struct PolyhedronRenderer {
 
 impl PolyhedronRenderer {
     pub fn new(texture_id: TextureId, figure: figures::Polyhedron) -> Self {
-        let mut vertices =
-            Vec::<gl::types::GLfloat>::with_capacity(3 * figure.get_vertices().len());
-        for vertex in figure.get_vertices() {
-            vertices.push(vertex.x);
-            vertices.push(vertex.y);
-            vertices.push(vertex.z);
+        let mut vertices = Vec::<gl::types::GLfloat>::with_capacity(
+            FLOATS_PER_VERTEX * figure.get_vertices().len(),
+        );
+        for i in 0..figure.get_vertices().len() {
+            push_vertex(&mut vertices, &figure, i);
         }
 
         let mut indices = Vec::<gl::types::GLuint>::with_capacity(3 * figure.get_triangles().len());
@@ -59,7 +68,47 @@ impl PolyhedronRenderer {
         mine
     }
 
+    /// Re-uploads the vertices with given indices, sorted in ascending order. The figure must
+    /// have the same number of vertices as the one the renderer was created with.
+    pub fn update_vertices(&mut self, figure: &figures::Polyhedron, indices: &[usize]) {
+        const STRIDE: gl::types::GLsizeiptr = FLOATS_PER_VERTEX as gl::types::GLsizeiptr;
+
+        let mut data = Vec::<gl::types::GLfloat>::new();
+        let mut i = 0;
+        unsafe {
+            gl::BindBuffer(gl::ARRAY_BUFFER, self.vbo);
+            while i < indices.len() {
+                // Upload consecutive vertices at once
+                let first = indices[i];
+                data.clear();
+                while i < indices.len() && indices[i] == first + data.len() / FLOATS_PER_VERTEX {
+                    push_vertex(&mut data, figure, indices[i]);
+                    i += 1;
+                }
+                gl::BufferSubData(
+                    gl::ARRAY_BUFFER,
+                    SIZEPTR_FLOAT * STRIDE * first as gl::types::GLsizeiptr,
+                    SIZEPTR_FLOAT * data.len() as gl::types::GLsizeiptr,
+                    data.as_ptr() as *const _,
+                );
+            }
+            gl::BindBuffer(gl::ARRAY_BUFFER, 0);
+        }
+    }
+
     pub fn render(&self) {
+        const LOC_POSITION: gl::types::GLuint = 0;
+        const LOC_NORMAL: gl::types::GLuint = 1;
+        const LOC_VALUE: gl::types::GLuint = 2;
+        const SIZE_POSITION: gl::types::GLint = 3;
+        const SIZE_NORMAL: gl::types::GLint = 3;
+        const SIZE_VALUE: gl::types::GLint = 1;
+        const PTR_POSITION: *const gl::types::GLvoid = 0 as _;
+        const PTR_NORMAL: *const gl::types::GLvoid = (SIZE_POSITION * SIZE_FLOAT) as _;
+        const PTR_VALUE: *const gl::types::GLvoid =
+            ((SIZE_POSITION + SIZE_NORMAL) * SIZE_FLOAT) as _;
+        const STRIDE: gl::types::GLint = (SIZE_POSITION + SIZE_NORMAL + SIZE_VALUE) * SIZE_FLOAT;
+
         unsafe {
             gl::BindTexture(gl::TEXTURE_2D, self.texture_id);
 
@@ -67,8 +116,26 @@ impl PolyhedronRenderer {
             gl::BindBuffer(gl::ARRAY_BUFFER, self.vbo);
             gl::BindBuffer(gl::ELEMENT_ARRAY_BUFFER, self.ibo);
 
-            gl::EnableVertexAttribArray(0);
-            gl::VertexAttribPointer(0, 3, gl::FLOAT, gl::FALSE, 0, NULL);
+            gl::EnableVertexAttribArray(LOC_POSITION);
+            gl::VertexAttribPointer(
+                LOC_POSITION,
+                SIZE_POSITION,
+                gl::FLOAT,
+                gl::FALSE,
+                STRIDE,
+                PTR_POSITION,
+            );
+            gl::EnableVertexAttribArray(LOC_NORMAL);
+            gl::VertexAttribPointer(
+                LOC_NORMAL,
+                SIZE_NORMAL,
+                gl::FLOAT,
+                gl::FALSE,
+                STRIDE,
+                PTR_NORMAL,
+            );
+            gl::EnableVertexAttribArray(LOC_VALUE);
+            gl::VertexAttribPointer(LOC_VALUE, SIZE_VALUE, gl::FLOAT, gl::FALSE, STRIDE, PTR_VALUE);
 
             gl::DrawElements(
                 gl::TRIANGLES,
@@ -77,7 +144,9 @@ impl PolyhedronRenderer {
                 NULL,
             );
 
-            gl::DisableVertexAttribArray(0);
+            gl::DisableVertexAttribArray(LOC_VALUE);
+            gl::DisableVertexAttribArray(LOC_NORMAL);
+            gl::DisableVertexAttribArray(LOC_POSITION);
 
             gl::BindBuffer(gl::ELEMENT_ARRAY_BUFFER, 0);
             gl::BindBuffer(gl::ARRAY_BUFFER, 0);
@@ -129,6 +198,7 @@ impl Drop for PolyhedronRenderer {
         unsafe {
             gl::DeleteBuffers(1, &self.vbo as *const _);
             gl::DeleteBuffers(1, &self.ibo as *const _);
+            gl::DeleteVertexArrays(1, &self.vao as *const _);
         }
     }
 }
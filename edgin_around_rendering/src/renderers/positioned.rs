@@ -2,15 +2,62 @@ use std::f32::consts::PI;
 
 use crate::{
     animations::Sprite,
-    game::Sprites,
-    renderers::fixed::FixedRenderer,
+    game::{ActorAppearance, Sprites, Transition, TransitionEffect},
+    renderers::{fixed::FixedRenderer, highlight::Highlight, shadow::ShadowRenderer},
     utils::{
-        coordinates::Position,
+        coordinates::{Point3D, Position},
+        defs,
         geometry::{Matrix3D, Vector3D},
         ids::ActorId,
     },
 };
 
+/// Altitude offset in world units at which the shadow of an actor fully fades out.
+const SHADOW_FADE_ALTITUDE: f32 = 3.0;
+
+/// Transition running on a renderer since `start` seconds.
+#[derive(Clone, Debug)]
+struct ActiveTransition {
+    transition: Transition,
+    start: f32,
+    revealing: bool,
+}
+
+impl ActiveTransition {
+    fn get_visibility(&self, time: f32) -> f32 {
+        let progress = self.transition.progress(time - self.start);
+        if self.revealing {
+            progress
+        } else {
+            1.0 - progress
+        }
+    }
+}
+
+/// Locations of the uniforms of the entities shader program which are set for each sprite.
+#[derive(Clone, Debug)]
+pub struct EntityUniforms {
+    pub highlight: gl::types::GLint,
+    pub highlight_color: gl::types::GLint,
+    pub model: gl::types::GLint,
+    pub tint: gl::types::GLint,
+    pub opacity: gl::types::GLint,
+    pub dissolve: gl::types::GLint,
+}
+
+impl Default for EntityUniforms {
+    fn default() -> Self {
+        Self {
+            highlight: defs::INONE,
+            highlight_color: defs::INONE,
+            model: defs::INONE,
+            tint: defs::INONE,
+            opacity: defs::INONE,
+            dissolve: defs::INONE,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct PositionedRenderer {
     actor_id: ActorId,
@@ -19,7 +66,13 @@ pub struct PositionedRenderer {
     view: Matrix3D,
     model: Matrix3D,
     camera_distance: f32,
-    highlight: bool,
+    highlight: Option<Highlight>,
+    appearance: ActorAppearance,
+    transition: Option<ActiveTransition>,
+    effect: TransitionEffect,
+    concealed: bool,
+    shadow: Option<Matrix3D>,
+    shadow_fade: f32,
 }
 
 impl PositionedRenderer {
@@ -36,7 +89,13 @@ impl PositionedRenderer {
             view: Matrix3D::identity(),
             model: Matrix3D::identity(),
             camera_distance: 0.0,
-            highlight: false,
+            highlight: None,
+            appearance: ActorAppearance::default(),
+            transition: None,
+            effect: TransitionEffect::default(),
+            concealed: false,
+            shadow: None,
+            shadow_fade: 1.0,
         };
 
         if let Some(position) = position {
@@ -46,8 +105,58 @@ impl PositionedRenderer {
         mine
     }
 
-    pub fn set_highlight(&mut self, highlight: bool) {
-        self.highlight = highlight;
+    pub fn set_highlight(&mut self, highlight: Option<&Highlight>) {
+        self.highlight = highlight.cloned();
+    }
+
+    /// Sets the scale, tint and opacity of the sprite. The altitude has to be included in the
+    /// position. Scale takes effect with the next position update, as do transitions.
+    pub fn set_appearance(&mut self, appearance: &ActorAppearance) {
+        self.appearance = appearance.clone();
+    }
+
+    /// Starts revealing or concealing the sprite at the given time in seconds. A transition
+    /// interrupting another one continues from the visibility the previous one reached.
+    pub fn start_transition(&mut self, transition: Transition, revealing: bool, time: f32) {
+        let visibility = match &self.transition {
+            Some(active) => active.get_visibility(time),
+            None if revealing => 0.0,
+            None => 1.0,
+        };
+        let done = if revealing { visibility } else { 1.0 - visibility };
+        let start = time - done * transition.duration;
+        self.transition = Some(ActiveTransition { transition, start, revealing });
+        self.update_transition(time);
+    }
+
+    pub fn stop_transition(&mut self) {
+        self.transition = None;
+        self.effect = TransitionEffect::default();
+        self.concealed = false;
+    }
+
+    /// Updates the effect of the running transition to the given time. Finished revealing
+    /// transitions are dropped, while finished concealing ones stay until stopped.
+    pub fn update_transition(&mut self, time: f32) {
+        if let Some(active) = &self.transition {
+            let visibility = active.get_visibility(time);
+            if active.revealing && visibility >= 1.0 {
+                self.stop_transition();
+            } else {
+                self.effect = active.transition.effect(visibility);
+                self.concealed = !active.revealing && visibility <= 0.0;
+            }
+        }
+    }
+
+    /// Tells if a concealing transition is running or finished.
+    pub fn is_concealing(&self) -> bool {
+        self.transition.as_ref().map(|active| !active.revealing).unwrap_or(false)
+    }
+
+    /// Tells if a concealing transition finished.
+    pub fn is_concealed(&self) -> bool {
+        self.concealed
     }
 
     pub fn change_position(&mut self, position: Position) {
@@ -67,7 +176,25 @@ impl PositionedRenderer {
     }
 
     pub fn unset_position(&mut self) {
-        self.position = None
+        self.position = None;
+        self.shadow = None;
+    }
+
+    /// Places the shadow at the `center` on the ground with the given `normal`. Its radius comes
+    /// from the appearance or from the sprite, and it fades out as the sprite rises above the
+    /// ground by `altitude`. Takes into account the appearance and transition set so far.
+    pub fn place_shadow(&mut self, center: &Point3D, normal: &Point3D, altitude: f32) {
+        let radius = self
+            .appearance
+            .shadow_radius
+            .unwrap_or_else(|| self.renderer.get_sprite().get_shadow_radius());
+        let radius = radius * self.appearance.scale * self.effect.scale;
+        self.shadow_fade = (1.0 - altitude / SHADOW_FADE_ALTITUDE).clamp(0.0, 1.0);
+        self.shadow = if radius > 0.0 && self.shadow_fade > 0.0 {
+            Some(ShadowRenderer::prepare_model(center, normal, radius))
+        } else {
+            None
+        };
     }
 
     pub fn get_sprite(&self) -> &Sprite {
@@ -86,6 +213,20 @@ impl PositionedRenderer {
         self.renderer.select_action(name);
     }
 
+    /// Turns the sprite according to the heading of the actor relative to the camera. Takes effect
+    /// with the next position update.
+    pub fn set_heading(&mut self, heading: Option<f32>) {
+        self.renderer.set_heading(heading);
+    }
+
+    /// Returns the point in the world space where the hook of the sprite currently is.
+    pub fn get_hook_point(&self, hook_name: &str) -> Option<Point3D> {
+        self.position.as_ref()?;
+        let (x, y) = self.renderer.get_hook_position(hook_name)?;
+        let point = &self.model * Vector3D::new(x, y, 0.0);
+        Some(Point3D::new(point.get_x(), point.get_y(), point.get_z()))
+    }
+
     pub fn has_position(&self) -> bool {
         self.position.is_some()
     }
@@ -98,25 +239,39 @@ impl PositionedRenderer {
         self.actor_id
     }
 
-    pub fn render(
-        &mut self,
-        loc_highlight: gl::types::GLint,
+    pub fn render_shadow(
+        &self,
         loc_model: gl::types::GLint,
-        sprites: &Sprites,
+        loc_opacity: gl::types::GLint,
+        renderer: &ShadowRenderer,
     ) {
-        unsafe { self.setup_rendering(loc_highlight, loc_model) };
+        if let Some(model) = &self.shadow {
+            let opacity = self.shadow_fade * self.appearance.opacity * self.effect.opacity;
+            unsafe {
+                gl::UniformMatrix4fv(loc_model, 1, gl::TRUE, model.as_ptr());
+                gl::Uniform1f(loc_opacity, opacity);
+            }
+            renderer.render();
+        }
+    }
+
+    pub fn render(&mut self, uniforms: &EntityUniforms, sprites: &Sprites) {
+        unsafe { self.setup_rendering(uniforms) };
         self.renderer.render(sprites);
     }
 }
 
 impl PositionedRenderer {
     fn update_position(&mut self, position: Position) {
+        let scale = self.appearance.scale * self.effect.scale;
+        let mirror = if self.renderer.get_sprite().is_mirrored() { -scale } else { scale };
         self.model = Matrix3D::rotation_x(-0.5 * PI)
             * Matrix3D::rotation_z(position.phi)
             * Matrix3D::rotation_x(position.theta)
             * Matrix3D::rotation_z(-position.bearing)
             * Matrix3D::translation((0.0, 0.0, position.altitude))
-            * Matrix3D::rotation_x(0.5 * PI);
+            * Matrix3D::rotation_x(0.5 * PI)
+            * Matrix3D::scale((mirror, scale, scale));
         self.position = Some(position);
     }
 
@@ -129,8 +284,19 @@ impl PositionedRenderer {
         self.camera_distance = center.get_z() / center.get_w();
     }
 
-    unsafe fn setup_rendering(&self, loc_highlight: gl::types::GLint, loc_model: gl::types::GLint) {
-        gl::Uniform1i(loc_highlight, self.highlight as gl::types::GLint);
-        gl::UniformMatrix4fv(loc_model, 1, gl::TRUE, self.model.as_ptr());
+    unsafe fn setup_rendering(&self, uniforms: &EntityUniforms) {
+        let [red, green, blue, alpha] = self.appearance.tint;
+        let opacity = self.appearance.opacity * self.effect.opacity;
+        if let Some(highlight) = &self.highlight {
+            let [red, green, blue, strength] = highlight.color;
+            gl::Uniform1i(uniforms.highlight, highlight.style.get_shader_id());
+            gl::Uniform4f(uniforms.highlight_color, red, green, blue, strength);
+        } else {
+            gl::Uniform1i(uniforms.highlight, 0);
+        }
+        gl::UniformMatrix4fv(uniforms.model, 1, gl::TRUE, self.model.as_ptr());
+        gl::Uniform4f(uniforms.tint, red, green, blue, alpha);
+        gl::Uniform1f(uniforms.opacity, opacity);
+        gl::Uniform1f(uniforms.dissolve, self.effect.dissolve);
     }
 }
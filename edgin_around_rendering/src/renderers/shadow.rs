@@ -0,0 +1,108 @@
+use gl;
+
+use crate::utils::{coordinates::Point3D, defs::prelude::*, geometry::Matrix3D};
+
+/// Distance in world units by which shadows are lifted above the ground along its normal.
+const LIFT: f32 = 0.02;
+
+/// Corners of the square enclosing the shadow disc of unit radius.
+#[rustfmt::skip]
+const CORNERS: [gl::types::GLfloat; 8] = [
+    -1.0, -1.0,
+     1.0, -1.0,
+    -1.0,  1.0,
+     1.0,  1.0,
+];
+
+/// Draws soft round shadows lying on the ground. A single renderer is shared by all actors, each
+/// of them placing the shadow with its own model matrix.
+pub struct ShadowRenderer {
+    vao: gl::types::GLuint,
+    vbo: gl::types::GLuint,
+}
+
+impl ShadowRenderer {
+    /// Returns the model matrix placing the shadow with the given radius at the `center` on the
+    /// ground, in the plane perpendicular to the ground `normal`. On slopes the shadow is seen
+    /// flattened the same way as the ground under it.
+    pub fn prepare_model(center: &Point3D, normal: &Point3D, radius: f32) -> Matrix3D {
+        // Any two perpendicular directions in the plane will do, as the shadow is round
+        let axis = if normal.x.abs() < 0.5 {
+            Point3D::new(1.0, 0.0, 0.0)
+        } else {
+            Point3D::new(0.0, 1.0, 0.0)
+        };
+        let u = normal.cross(&axis).enlongated(radius);
+        let v = normal.cross(&u);
+        let c = center + &normal.scaled(LIFT);
+
+        #[rustfmt::skip]
+        Matrix3D::new(ndarray::arr2(&[
+            [u.x, v.x, normal.x, c.x],
+            [u.y, v.y, normal.y, c.y],
+            [u.z, v.z, normal.z, c.z],
+            [0.0, 0.0,      0.0, 1.0],
+        ]))
+    }
+
+    pub fn render(&self) {
+        const LOC_POSITION: gl::types::GLuint = 0;
+        const SIZE_POSITION: gl::types::GLint = 2;
+        const STRIDE: gl::types::GLint = SIZE_POSITION * SIZE_FLOAT;
+
+        unsafe {
+            gl::BindVertexArray(self.vao);
+            gl::BindBuffer(gl::ARRAY_BUFFER, self.vbo);
+
+            gl::EnableVertexAttribArray(LOC_POSITION);
+            gl::VertexAttribPointer(
+                LOC_POSITION,
+                SIZE_POSITION,
+                gl::FLOAT,
+                gl::FALSE,
+                STRIDE,
+                NULL,
+            );
+
+            gl::DrawArrays(gl::TRIANGLE_STRIP, 0, (CORNERS.len() / 2) as gl::types::GLsizei);
+
+            gl::DisableVertexAttribArray(LOC_POSITION);
+
+            gl::BindBuffer(gl::ARRAY_BUFFER, 0);
+            gl::BindVertexArray(0);
+        }
+    }
+}
+
+impl Default for ShadowRenderer {
+    fn default() -> Self {
+        let mut mine = Self { vao: 0, vbo: 0 };
+
+        unsafe {
+            gl::GenVertexArrays(1, &mut mine.vao);
+            gl::GenBuffers(1, &mut mine.vbo);
+
+            gl::BindVertexArray(mine.vao);
+            gl::BindBuffer(gl::ARRAY_BUFFER, mine.vbo);
+            gl::BufferData(
+                gl::ARRAY_BUFFER,
+                SIZEPTR_FLOAT * CORNERS.len() as gl::types::GLsizeiptr,
+                CORNERS.as_ptr() as *const _,
+                gl::STATIC_DRAW,
+            );
+            gl::BindBuffer(gl::ARRAY_BUFFER, 0);
+            gl::BindVertexArray(0);
+        }
+
+        mine
+    }
+}
+
+impl Drop for ShadowRenderer {
+    fn drop(&mut self) {
+        unsafe {
+            gl::DeleteBuffers(1, &self.vbo);
+            gl::DeleteVertexArrays(1, &self.vao);
+        }
+    }
+}
@@ -5,4 +5,5 @@ pub mod figures;
 pub mod geometry;
 pub mod graphics;
 pub mod ids;
+pub mod noise;
 pub mod tile;
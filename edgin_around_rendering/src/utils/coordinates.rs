@@ -1,3 +1,5 @@
+use serde::{Deserialize, Serialize};
+
 pub fn degrees(radians: f32) -> f32 {
     180.0 * radians * std::f32::consts::FRAC_1_PI
 }
@@ -89,7 +91,7 @@ impl Coordinate {
 }
 
 /// Position expressed in spherical coordinates.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Point {
     pub theta: f32,
     pub phi: f32,
@@ -103,7 +105,7 @@ impl Point {
 
 impl Point {
     pub fn moved_by(&self, distance: f32, bearing: f32, radius: f32) -> Point {
-        self.to_coordinate().moved_by(distance, bearing, radius).to_point()
+        Self::destination(self, distance, bearing, radius)
     }
 
     pub fn to_coordinate(&self) -> Coordinate {
@@ -116,11 +118,114 @@ impl Point {
         Point3D::new(x, y, z)
     }
 
+    /// Returns the point in the direction of the given vector, with `phi` within `[0, 2 * PI)`.
+    pub fn from_point3d(point: &Point3D) -> Point {
+        let theta = (point.x * point.x + point.z * point.z).sqrt().atan2(point.y);
+        let phi = point.x.atan2(point.z);
+        Point::new(theta, if phi >= 0.0 { phi } else { phi + 2.0 * std::f32::consts::PI })
+    }
+
     pub fn distance(point1: &Point, point2: &Point, altitude: f32) -> f32 {
         let point3d1 = point1.to_point3d(altitude);
         let point3d2 = point2.to_point3d(altitude);
         Point3D::distance(&point3d1, &point3d2)
     }
+
+    /// Distance between two points measured along the surface of a sphere with given radius.
+    pub fn great_circle_distance(point1: &Point, point2: &Point, radius: f32) -> f32 {
+        let point3d1 = point1.to_point3d(1.0);
+        let point3d2 = point2.to_point3d(1.0);
+        radius * point3d1.angle(&point3d2)
+    }
+}
+
+/// Geodesic computations. Bearings are measured clockwise from the north in radians within
+/// `(-PI, PI]`. At the poles, where the north is undefined, it is taken to be the direction in
+/// which the meridian of `phi` heads north, continuing over the pole, so the results stay
+/// consistent with each other there too.
+impl Point {
+    /// Direction in which a great-circle path from `point1` to `point2` starts.
+    pub fn initial_bearing(point1: &Point, point2: &Point) -> f32 {
+        let (north, east) = point1.local_frame();
+        let direction = point2.to_point3d(1.0);
+        direction.dot(&east).atan2(direction.dot(&north))
+    }
+
+    /// Direction in which a great-circle path from `point1` arrives at `point2`.
+    pub fn final_bearing(point1: &Point, point2: &Point) -> f32 {
+        let reverse = Self::initial_bearing(point2, point1);
+        if reverse > 0.0 {
+            reverse - std::f32::consts::PI
+        } else {
+            reverse + std::f32::consts::PI
+        }
+    }
+
+    /// Point lying on the great-circle path from `point1` to `point2` at the given `fraction` of
+    /// its length. Paths between antipodal points are ambiguous, any of them may be chosen.
+    pub fn interpolate(point1: &Point, point2: &Point, fraction: f32) -> Point {
+        let start = point1.to_point3d(1.0);
+        let end = point2.to_point3d(1.0);
+        let angle = start.angle(&end);
+        let tangent = if angle.sin() > f32::EPSILON {
+            (&end - &start.scaled(angle.cos())).enlongated(1.0)
+        } else {
+            let (north, _east) = point1.local_frame();
+            north
+        };
+        let (sin, cos) = (fraction * angle).sin_cos();
+        Self::from_point3d(&(&start.scaled(cos) + &tangent.scaled(sin)))
+    }
+
+    /// Point lying halfway along the great-circle path between `point1` and `point2`.
+    pub fn midpoint(point1: &Point, point2: &Point) -> Point {
+        Self::interpolate(point1, point2, 0.5)
+    }
+
+    /// Point reached by travelling `distance` from `start` along a great circle in the direction
+    /// of `bearing` on a sphere with given radius.
+    pub fn destination(start: &Point, distance: f32, bearing: f32, radius: f32) -> Point {
+        let (north, east) = start.local_frame();
+        let tangent = &north.scaled(bearing.cos()) + &east.scaled(bearing.sin());
+        let (sin, cos) = (distance / radius).sin_cos();
+        Self::from_point3d(&(&start.to_point3d(cos) + &tangent.scaled(sin)))
+    }
+
+    /// Distance of `point` from the great circle passing through `start` and `end` on a sphere
+    /// with given radius. It is positive if the point lies on the right of the path.
+    pub fn cross_track_distance(point: &Point, start: &Point, end: &Point, radius: f32) -> f32 {
+        let normal = start.to_point3d(1.0).cross(&end.to_point3d(1.0)).enlongated(1.0);
+        -radius * point.to_point3d(1.0).dot(&normal).clamp(-1.0, 1.0).asin()
+    }
+
+    /// Area of a polygon on a sphere with given radius. Edges are the shorter great-circle arcs
+    /// between consecutive vertices and the vertices may be listed in either order. The polygon
+    /// must be simple and cover at most a hemisphere.
+    pub fn polygon_area(vertices: &[Point], radius: f32) -> f32 {
+        if vertices.len() < 3 {
+            return 0.0;
+        }
+
+        // Sum up signed solid angles of triangles fanning out from the first vertex
+        let points: Vec<Point3D> = vertices.iter().map(|v| v.to_point3d(1.0)).collect();
+        let mut excess = 0.0;
+        for pair in points[1..].windows(2) {
+            let (a, b, c) = (&points[0], &pair[0], &pair[1]);
+            let numerator = a.dot(&b.cross(c));
+            let denominator = 1.0 + a.dot(b) + b.dot(c) + c.dot(a);
+            excess += 2.0 * numerator.atan2(denominator);
+        }
+        radius * radius * excess.abs()
+    }
+}
+
+impl Point {
+    /// Returns unit vectors pointing to the north and to the east in the tangent plane.
+    fn local_frame(&self) -> (Point3D, Point3D) {
+        let (st, ct) = self.theta.sin_cos();
+        let (sp, cp) = self.phi.sin_cos();
+        (Point3D::new(-ct * sp, st, -ct * cp), Point3D::new(cp, 0.0, -sp))
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -187,3 +292,34 @@ impl std::ops::Add for &Point3D {
         Point3D::new(self.x + other.x, self.y + other.y, self.z + other.z)
     }
 }
+
+impl std::ops::Sub for &Point3D {
+    type Output = Point3D;
+
+    fn sub(self, other: &Point3D) -> Point3D {
+        Point3D::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl Point3D {
+    pub fn dot(&self, other: &Point3D) -> f32 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    /// Returns the angle between two vectors of any non-zero length.
+    pub fn angle(&self, other: &Point3D) -> f32 {
+        self.cross(other).length().atan2(self.dot(other))
+    }
+
+    pub fn scaled(&self, multiplier: f32) -> Point3D {
+        Point3D::new(self.x * multiplier, self.y * multiplier, self.z * multiplier)
+    }
+
+    pub fn cross(&self, other: &Point3D) -> Point3D {
+        Point3D::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+}
@@ -5,6 +5,12 @@ pub const GL_SHADER_FAILED: &str = "failed to create an OpenGL shader";
 pub const GL_LOCATION_FAILED: &str = "failed to get an OpenGL location";
 pub const FILE_FAILED: &str = "failed to open a file";
 pub const PNG_FAILED: &str = "parsing PNG file failed";
+pub const HEIGHTMAP_INVALID_SIZE: &str = "heightmap size does not match its samples";
+pub const DECAL_INVALID_SHAPE: &str = "decal distances must be finite and not negative";
+pub const HEIGHTMAP_WITHOUT_PATH: &str = "only heightmaps loaded from a file can be stored";
+pub const NOISE_INVALID_OCTAVES: &str = "number of noise octaves must not be negative or too big";
+pub const PATH_INVALID_SPEED: &str = "speed of an actor must be finite and positive";
+pub const JSON_FAILED: &str = "parsing JSON file failed";
 pub const YAML_FAILED: &str = "parsing YAML file failed";
 pub const SAML_NOT_EXISTING_ANIMATION: &str = "requested animation does not exist";
 pub const SAML_NOT_EXISTING_BONE: &str = "requested bone does not exist";
@@ -12,6 +18,7 @@ pub const SAML_NOT_EXISTING_IMAGE: &str = "requested image does not exist";
 pub const SAML_NOT_EXISTING_POSE: &str = "requested pose does not exist";
 pub const SAML_NOT_EXISTING_SKELETON: &str = "requested skeleton does not exist";
 pub const SAML_NOT_EXISTING_SOURCE: &str = "requested source does not exist";
+pub const SAML_UNSUPPORTED_DIRECTIONS: &str = "unsupported number of directions";
 pub const DEFAULT_VARIANT_AND_ACTION_FAILED: &str = "failed to activate default variant and action";
 pub const DEFAULT_ACTION_FAILED: &str = "failed to activate default action";
 pub const DEFAULT_ANIMATION_FAILED: &str = "failed to activate default animation";
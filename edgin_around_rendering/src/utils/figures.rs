@@ -8,12 +8,17 @@ type Indices3D = (u32, u32, u32);
 #[derive(Clone, Debug)]
 pub struct Polyhedron {
     vertices: Vec<coordinates::Point3D>,
+    normals: Vec<coordinates::Point3D>,
+    values: Vec<f32>,
     triangles: Vec<Indices3D>,
 }
 
 impl Polyhedron {
     pub fn new(vertices: Vec<coordinates::Point3D>, triangles: Vec<Indices3D>) -> Self {
-        Self { vertices, triangles }
+        let values = vec![0.0; vertices.len()];
+        let mut polyhedron = Self { vertices, normals: Vec::new(), values, triangles };
+        polyhedron.compute_normals();
+        polyhedron
     }
 
     pub fn new_from_tuples(vertices: Vec<(f32, f32, f32)>, triangles: Vec<Indices3D>) -> Self {
@@ -27,10 +32,56 @@ impl Polyhedron {
         &self.vertices
     }
 
+    pub fn get_normals(&self) -> &Vec<coordinates::Point3D> {
+        &self.normals
+    }
+
+    /// Returns scalar values attached to the vertices, e.g. depth of water. They are zero unless
+    /// set with `assign_values`.
+    pub fn get_values(&self) -> &Vec<f32> {
+        &self.values
+    }
+
     pub fn get_triangles(&self) -> &Vec<Indices3D> {
         &self.triangles
     }
 
+    /// Computes the scalar value of every vertex from its spherical coordinates.
+    pub fn assign_values<V>(&mut self, value: V)
+    where
+        V: Fn(f32, f32) -> f32,
+    {
+        for (v, vertex) in self.values.iter_mut().zip(self.vertices.iter()) {
+            let (_r, theta, phi) =
+                coordinates::cartesian_to_spherical(vertex.x, vertex.y, vertex.z);
+            *v = value(theta, phi);
+        }
+    }
+
+    /// Computes per-vertex normals by averaging normals of adjacent triangles weighted by their
+    /// areas. Vertices not belonging to any triangle point away from the origin.
+    pub fn compute_normals(&mut self) {
+        let mut normals = vec![coordinates::Point3D::new(0.0, 0.0, 0.0); self.vertices.len()];
+        for t in self.triangles.iter() {
+            let (i0, i1, i2) = (t.0 as usize, t.1 as usize, t.2 as usize);
+            let edge1 = &self.vertices[i1] - &self.vertices[i0];
+            let edge2 = &self.vertices[i2] - &self.vertices[i0];
+            let mut normal = edge1.cross(&edge2);
+            if normal.dot(&self.vertices[i0]) < 0.0 {
+                normal.stretch(-1.0);
+            }
+            for i in [i0, i1, i2].iter() {
+                normals[*i] = &normals[*i] + &normal;
+            }
+        }
+
+        for (normal, vertex) in normals.iter_mut().zip(self.vertices.iter()) {
+            *normal =
+                if normal.length() > 0.0 { normal.enlongated(1.0) } else { vertex.enlongated(1.0) };
+        }
+        self.normals = normals;
+    }
+
     pub fn rescale<S>(&mut self, stretch: S)
     where
         S: Fn(f32, f32) -> f32,
@@ -39,6 +90,7 @@ impl Polyhedron {
             let (r, theta, phi) = coordinates::cartesian_to_spherical(v.x, v.y, v.z);
             v.stretch(stretch(theta, phi) / r);
         }
+        self.compute_normals();
     }
 }
 
@@ -118,3 +170,172 @@ pub fn sphere(quality: u32, radius: f32) -> Polyhedron {
 
     Polyhedron::new(vertices, old_triangles)
 }
+
+/// Computes position of a point on an edge of a sphere chunk divided into `2^level` segments.
+///
+/// The point is placed on the straight segment between the two closest edge vertices, so that
+/// edges of neighbouring chunks divided into different number of segments meet without cracks.
+/// Edge ends are sorted to produce exactly the same vertices for both chunks sharing the edge.
+fn chunk_edge_point<S>(
+    start: &coordinates::Point3D,
+    end: &coordinates::Point3D,
+    t: f32,
+    level: u32,
+    stretch: &S,
+) -> coordinates::Point3D
+where
+    S: Fn(f32, f32) -> f32,
+{
+    let (start, end, t) = if (start.x, start.y, start.z) <= (end.x, end.y, end.z) {
+        (start, end, t)
+    } else {
+        (end, start, 1.0 - t)
+    };
+
+    let num_segments = (1u32 << level) as f32;
+    let s = t * num_segments;
+    let k = s.floor().min(num_segments - 1.0);
+    let f = s - k;
+
+    let p0 = chunk_surface_point(start, end, k / num_segments, stretch);
+    if f == 0.0 {
+        p0
+    } else {
+        let p1 = chunk_surface_point(start, end, (k + 1.0) / num_segments, stretch);
+        coordinates::Point3D::new(
+            p0.x * (1.0 - f) + p1.x * f,
+            p0.y * (1.0 - f) + p1.y * f,
+            p0.z * (1.0 - f) + p1.z * f,
+        )
+    }
+}
+
+/// Projects a point between `start` and `end` on the surface described by `stretch`.
+fn chunk_surface_point<S>(
+    start: &coordinates::Point3D,
+    end: &coordinates::Point3D,
+    t: f32,
+    stretch: &S,
+) -> coordinates::Point3D
+where
+    S: Fn(f32, f32) -> f32,
+{
+    let mut point = coordinates::Point3D::new(
+        start.x * (1.0 - t) + end.x * t,
+        start.y * (1.0 - t) + end.y * t,
+        start.z * (1.0 - t) + end.z * t,
+    );
+    let (r, theta, phi) = coordinates::cartesian_to_spherical(point.x, point.y, point.z);
+    point.stretch(stretch(theta, phi) / r);
+    point
+}
+
+/// Sphere chunk generation function.
+///
+/// Divides the spherical triangle spanned by `corners` into `4^level` triangles and projects them
+/// on the surface described by `stretch`. Edges `(0, 1)`, `(1, 2)` and `(2, 0)` are divided
+/// according to `edge_levels`, which must not be greater than `level`. To stay crack-free two
+/// chunks sharing an edge must use the same level for it.
+pub fn sphere_chunk<S>(
+    corners: [&coordinates::Point3D; 3],
+    level: u32,
+    edge_levels: [u32; 3],
+    stretch: S,
+) -> Polyhedron
+where
+    S: Fn(f32, f32) -> f32,
+{
+    let n = 1u32 << level;
+    let nf = n as f32;
+    let [a, b, c] = corners;
+
+    let mut vertices = Vec::with_capacity(((n + 1) * (n + 2) / 2) as usize);
+    for i in 0..=n {
+        for j in 0..=(n - i) {
+            let vertex = if j == 0 {
+                chunk_edge_point(a, b, i as f32 / nf, edge_levels[0], &stretch)
+            } else if i + j == n {
+                chunk_edge_point(b, c, j as f32 / nf, edge_levels[1], &stretch)
+            } else if i == 0 {
+                chunk_edge_point(c, a, (n - j) as f32 / nf, edge_levels[2], &stretch)
+            } else {
+                let (wa, wb, wc) = ((n - i - j) as f32 / nf, i as f32 / nf, j as f32 / nf);
+                let mut point = coordinates::Point3D::new(
+                    wa * a.x + wb * b.x + wc * c.x,
+                    wa * a.y + wb * b.y + wc * c.y,
+                    wa * a.z + wb * b.z + wc * c.z,
+                );
+                let (r, theta, phi) =
+                    coordinates::cartesian_to_spherical(point.x, point.y, point.z);
+                point.stretch(stretch(theta, phi) / r);
+                point
+            };
+            vertices.push(vertex);
+        }
+    }
+
+    let index = |i: u32, j: u32| -> u32 { i * (2 * n + 3 - i) / 2 + j };
+    let mut triangles = Vec::with_capacity((n * n) as usize);
+    for i in 0..n {
+        for j in 0..(n - i) {
+            triangles.push((index(i, j), index(i + 1, j), index(i, j + 1)));
+            if j + 1 < n - i {
+                triangles.push((index(i + 1, j), index(i + 1, j + 1), index(i, j + 1)));
+            }
+        }
+    }
+
+    // Normals of edge vertices are sampled from the surface, because the triangles of the
+    // neighbouring chunks are not known here.
+    let mut polyhedron = Polyhedron::new(vertices, triangles);
+    let step = (a.dot(b) / (a.length() * b.length())).clamp(-1.0, 1.0).acos() / nf;
+    for i in 0..=n {
+        for j in 0..=(n - i) {
+            if i == 0 || j == 0 || i + j == n {
+                let k = index(i, j) as usize;
+                let direction = polyhedron.vertices[k].enlongated(1.0);
+                polyhedron.normals[k] = surface_normal(&direction, step, &stretch);
+            }
+        }
+    }
+    polyhedron
+}
+
+/// Estimates normal of the surface described by `stretch` in the given unit `direction` using
+/// central differences over the angular `step`.
+fn surface_normal<S>(
+    direction: &coordinates::Point3D,
+    step: f32,
+    stretch: &S,
+) -> coordinates::Point3D
+where
+    S: Fn(f32, f32) -> f32,
+{
+    let up = if direction.y.abs() < 0.9 {
+        coordinates::Point3D::new(0.0, 1.0, 0.0)
+    } else {
+        coordinates::Point3D::new(1.0, 0.0, 0.0)
+    };
+    let tangent1 = direction.cross(&up).enlongated(step);
+    let tangent2 = direction.cross(&tangent1).enlongated(step);
+
+    let sample = |offset: &coordinates::Point3D, sign: f32| {
+        let mut point = coordinates::Point3D::new(
+            direction.x + sign * offset.x,
+            direction.y + sign * offset.y,
+            direction.z + sign * offset.z,
+        );
+        let (r, theta, phi) = coordinates::cartesian_to_spherical(point.x, point.y, point.z);
+        point.stretch(stretch(theta, phi) / r);
+        point
+    };
+
+    let du = &sample(&tangent1, 1.0) - &sample(&tangent1, -1.0);
+    let dv = &sample(&tangent2, 1.0) - &sample(&tangent2, -1.0);
+    let normal = du.cross(&dv);
+    if normal.dot(direction) < 0.0 {
+        normal.enlongated(-1.0)
+    } else {
+        normal.enlongated(1.0)
+    }
+}
@@ -2,8 +2,18 @@ use gl;
 
 const GROUND_VERTEX: &str = include_str!("../../shaders/ground_vertex.glsl");
 const GROUND_FRAGMENT: &str = include_str!("../../shaders/ground_fragment.glsl");
+const WATER_VERTEX: &str = include_str!("../../shaders/water_vertex.glsl");
+const WATER_FRAGMENT: &str = include_str!("../../shaders/water_fragment.glsl");
 const ENTITIES_VERTEX: &str = include_str!("../../shaders/entities_vertex.glsl");
 const ENTITIES_FRAGMENT: &str = include_str!("../../shaders/entities_fragment.glsl");
+const SHADOW_VERTEX: &str = include_str!("../../shaders/shadow_vertex.glsl");
+const SHADOW_FRAGMENT: &str = include_str!("../../shaders/shadow_fragment.glsl");
+const DECAL_VERTEX: &str = include_str!("../../shaders/decal_vertex.glsl");
+const DECAL_FRAGMENT: &str = include_str!("../../shaders/decal_fragment.glsl");
+const PATH_VERTEX: &str = include_str!("../../shaders/path_vertex.glsl");
+const PATH_FRAGMENT: &str = include_str!("../../shaders/path_fragment.glsl");
+const PARTICLE_VERTEX: &str = include_str!("../../shaders/particle_vertex.glsl");
+const PARTICLE_FRAGMENT: &str = include_str!("../../shaders/particle_fragment.glsl");
 
 /// Initializes OpenGL library.
 pub fn init() -> Result<(), ()> {
@@ -11,6 +21,35 @@ pub fn init() -> Result<(), ()> {
     Ok(())
 }
 
+/// Checks if the current GL context supports the extension with the given name.
+pub fn has_extension(name: &str) -> bool {
+    unsafe {
+        let mut num_extensions: gl::types::GLint = 0;
+        gl::GetIntegerv(gl::NUM_EXTENSIONS, &mut num_extensions);
+        for i in 0..num_extensions as gl::types::GLuint {
+            let extension = gl::GetStringi(gl::EXTENSIONS, i);
+            if !extension.is_null() {
+                let cstr = std::ffi::CStr::from_ptr(extension as *const std::os::raw::c_char);
+                if cstr.to_bytes() == name.as_bytes() {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+}
+
+/// Checks if the current GL context can upload textures in the given compressed format.
+pub fn has_compressed_format(format: gl::types::GLenum) -> bool {
+    unsafe {
+        let mut num_formats: gl::types::GLint = 0;
+        gl::GetIntegerv(gl::NUM_COMPRESSED_TEXTURE_FORMATS, &mut num_formats);
+        let mut formats = vec![0 as gl::types::GLint; num_formats as usize];
+        gl::GetIntegerv(gl::COMPRESSED_TEXTURE_FORMATS, formats.as_mut_ptr());
+        formats.iter().any(|&f| f as gl::types::GLenum == format)
+    }
+}
+
 /// Get GL info log.
 pub fn get_info_log(object: gl::types::GLuint) -> String {
     unsafe {
@@ -111,16 +150,41 @@ pub fn prepare_shader_program(
     create_program(vertex_shader, fragment_shader)
 }
 
-/// Prepares shader for rendering ground and water.
+/// Prepares shader for rendering ground.
 pub fn prepare_ground_shader_program() -> Result<gl::types::GLuint, String> {
     prepare_shader_program(GROUND_VERTEX, GROUND_FRAGMENT)
 }
 
+/// Prepares shader for rendering water.
+pub fn prepare_water_shader_program() -> Result<gl::types::GLuint, String> {
+    prepare_shader_program(WATER_VERTEX, WATER_FRAGMENT)
+}
+
 /// Prepares shader for rendering entities.
 pub fn prepare_entities_shader_program() -> Result<gl::types::GLuint, String> {
     prepare_shader_program(ENTITIES_VERTEX, ENTITIES_FRAGMENT)
 }
 
+/// Prepares shader for rendering shadows of entities.
+pub fn prepare_shadow_shader_program() -> Result<gl::types::GLuint, String> {
+    prepare_shader_program(SHADOW_VERTEX, SHADOW_FRAGMENT)
+}
+
+/// Prepares shader for rendering decals on the ground.
+pub fn prepare_decal_shader_program() -> Result<gl::types::GLuint, String> {
+    prepare_shader_program(DECAL_VERTEX, DECAL_FRAGMENT)
+}
+
+/// Prepares shader for rendering paths on the ground.
+pub fn prepare_path_shader_program() -> Result<gl::types::GLuint, String> {
+    prepare_shader_program(PATH_VERTEX, PATH_FRAGMENT)
+}
+
+/// Prepares shader for rendering particles.
+pub fn prepare_particle_shader_program() -> Result<gl::types::GLuint, String> {
+    prepare_shader_program(PARTICLE_VERTEX, PARTICLE_FRAGMENT)
+}
+
 /// Get location attribute variable in linked program.
 pub fn get_attrib_location(
     program: gl::types::GLuint,
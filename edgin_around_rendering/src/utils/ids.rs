@@ -1,5 +1,8 @@
 pub type ActorId = usize;
+pub type DecalId = usize;
+pub type EmitterId = usize;
 pub type MediumId = usize;
+pub type PathId = usize;
 pub type TextureId = u32;
 pub type ResourcePath = (String, String);
 
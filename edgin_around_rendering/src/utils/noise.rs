@@ -0,0 +1,135 @@
+const PERMUTATION_SIZE: usize = 256;
+const LACUNARITY: f32 = 2.0;
+const GAIN: f32 = 0.5;
+
+/// Highest number of octaves taken into account. Further layers are finer than any terrain detail.
+pub const MAX_OCTAVES: u32 = 16;
+
+/// SplitMix64 pseudo-random number generator.
+struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    fn next(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+fn fade(t: f32) -> f32 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(t: f32, a: f32, b: f32) -> f32 {
+    a + t * (b - a)
+}
+
+fn gradient(hash: u8, x: f32, y: f32, z: f32) -> f32 {
+    let h = hash & 15;
+    let u = if h < 8 { x } else { y };
+    let v = if h < 4 {
+        y
+    } else if h == 12 || h == 14 {
+        x
+    } else {
+        z
+    };
+    (if h & 1 == 0 { u } else { -u }) + (if h & 2 == 0 { v } else { -v })
+}
+
+/// Three-dimensional deterministic gradient noise generator.
+///
+/// The algorithm is Ken Perlin's improved noise with the permutation table shuffled by a seeded
+/// SplitMix64 generator (Fisher-Yates, from the last element down to the second one, index drawn as
+/// `next() % (i + 1)`). All arithmetic is done on `f32`, so any implementation following the same
+/// steps yields the same values for the same seed.
+#[derive(Clone, Debug)]
+pub struct GradientNoise {
+    permutation: Vec<u8>,
+}
+
+impl GradientNoise {
+    pub fn new(seed: u64) -> Self {
+        let mut table: Vec<u8> = (0..PERMUTATION_SIZE).map(|i| i as u8).collect();
+        let mut generator = SplitMix64::new(seed);
+        for i in (1..PERMUTATION_SIZE).rev() {
+            let j = (generator.next() % (i as u64 + 1)) as usize;
+            table.swap(i, j);
+        }
+
+        let mut permutation = Vec::with_capacity(2 * PERMUTATION_SIZE);
+        permutation.extend_from_slice(&table);
+        permutation.extend_from_slice(&table);
+        Self { permutation }
+    }
+
+    /// Evaluates the noise at the given point. The result is roughly in range `[-1, 1]`.
+    pub fn evaluate(&self, x: f32, y: f32, z: f32) -> f32 {
+        let (fx, fy, fz) = (x.floor(), y.floor(), z.floor());
+        let xi = (fx as i64 & 255) as usize;
+        let yi = (fy as i64 & 255) as usize;
+        let zi = (fz as i64 & 255) as usize;
+        let (x, y, z) = (x - fx, y - fy, z - fz);
+        let (u, v, w) = (fade(x), fade(y), fade(z));
+
+        let p = &self.permutation;
+        let a = p[xi] as usize + yi;
+        let aa = p[a] as usize + zi;
+        let ab = p[a + 1] as usize + zi;
+        let b = p[xi + 1] as usize + yi;
+        let ba = p[b] as usize + zi;
+        let bb = p[b + 1] as usize + zi;
+
+        lerp(
+            w,
+            lerp(
+                v,
+                lerp(u, gradient(p[aa], x, y, z), gradient(p[ba], x - 1.0, y, z)),
+                lerp(u, gradient(p[ab], x, y - 1.0, z), gradient(p[bb], x - 1.0, y - 1.0, z)),
+            ),
+            lerp(
+                v,
+                lerp(
+                    u,
+                    gradient(p[aa + 1], x, y, z - 1.0),
+                    gradient(p[ba + 1], x - 1.0, y, z - 1.0),
+                ),
+                lerp(
+                    u,
+                    gradient(p[ab + 1], x, y - 1.0, z - 1.0),
+                    gradient(p[bb + 1], x - 1.0, y - 1.0, z - 1.0),
+                ),
+            ),
+        )
+    }
+
+    /// Evaluates a sum of `octaves` layers of noise. Each next layer has doubled frequency and
+    /// halved amplitude. At most `MAX_OCTAVES` layers are evaluated. The result is normalized to
+    /// roughly `[-1, 1]`.
+    pub fn fractal(&self, x: f32, y: f32, z: f32, octaves: u32, frequency: f32) -> f32 {
+        let mut result = 0.0;
+        let mut total_amplitude = 0.0;
+        let mut amplitude = 1.0;
+        let mut frequency = frequency;
+        for _ in 0..octaves.min(MAX_OCTAVES) {
+            result += amplitude * self.evaluate(frequency * x, frequency * y, frequency * z);
+            total_amplitude += amplitude;
+            amplitude *= GAIN;
+            frequency *= LACUNARITY;
+        }
+
+        if total_amplitude > 0.0 {
+            result / total_amplitude
+        } else {
+            0.0
+        }
+    }
+}
@@ -0,0 +1,142 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+use std::f32::consts::PI;
+
+use edgin_around_rendering::utils::coordinates::{radians, Point};
+
+const RADIUS: f32 = 1000.0;
+const EPSILON: f32 = 1.0e-3;
+
+/// Creates a point from latitude and longitude in degrees.
+fn geo(lat: f32, lon: f32) -> Point {
+    Point::new(radians(90.0 - lat), radians(lon).rem_euclid(2.0 * PI))
+}
+
+fn assert_close(actual: f32, expected: f32) {
+    assert!((actual - expected).abs() < EPSILON, "{} != {}", actual, expected);
+}
+
+fn assert_same_point(actual: &Point, expected: &Point) {
+    let distance = Point::great_circle_distance(actual, expected, RADIUS);
+    assert!(distance < EPSILON * RADIUS, "{:?} != {:?}", actual, expected);
+}
+
+#[test]
+fn distances_and_bearings() {
+    let quarter = 0.5 * PI * RADIUS;
+    assert_close(Point::great_circle_distance(&geo(0.0, 0.0), &geo(0.0, 90.0), RADIUS), quarter);
+    assert_close(Point::great_circle_distance(&geo(0.0, 0.0), &geo(90.0, 0.0), RADIUS), quarter);
+    assert_close(Point::great_circle_distance(&geo(10.0, 0.0), &geo(10.0, 0.0), RADIUS), 0.0);
+    assert_close(
+        Point::great_circle_distance(&geo(0.0, 0.0), &geo(0.0, 180.0), RADIUS),
+        2.0 * quarter,
+    );
+
+    assert_close(Point::initial_bearing(&geo(0.0, 0.0), &geo(10.0, 0.0)), 0.0);
+    assert_close(Point::initial_bearing(&geo(0.0, 0.0), &geo(0.0, 10.0)), 0.5 * PI);
+    assert_close(Point::initial_bearing(&geo(0.0, 0.0), &geo(0.0, -10.0)), -0.5 * PI);
+    assert_close(Point::initial_bearing(&geo(10.0, 0.0), &geo(0.0, 0.0)).abs(), PI);
+
+    // Along a parallel the path bends towards the pole
+    let (start, end) = (geo(45.0, -10.0), geo(45.0, 10.0));
+    let initial = Point::initial_bearing(&start, &end);
+    let final_ = Point::final_bearing(&start, &end);
+    assert!(initial < 0.5 * PI && final_ > 0.5 * PI);
+    assert_close(initial + final_, PI);
+}
+
+#[test]
+fn geodesics_across_antimeridian() {
+    let (west, east) = (geo(0.0, 179.9), geo(0.0, -179.9));
+    let expected = radians(0.2) * RADIUS;
+    assert_close(Point::great_circle_distance(&west, &east, RADIUS), expected);
+    assert_close(Point::initial_bearing(&west, &east), 0.5 * PI);
+    assert_close(Point::final_bearing(&west, &east), 0.5 * PI);
+    assert_same_point(&Point::midpoint(&west, &east), &geo(0.0, 180.0));
+    assert_same_point(&Point::destination(&west, expected, 0.5 * PI, RADIUS), &east);
+
+    let point = Point::destination(&geo(10.0, 179.0), radians(2.0) * RADIUS, 0.5 * PI, RADIUS);
+    let (lat, lon) = (90.0 - point.theta.to_degrees(), point.phi.to_degrees());
+    assert!(lat > 9.0 && lat < 10.0 && lon > 180.0 && lon < 181.5, "{} {}", lat, lon);
+}
+
+#[test]
+fn geodesics_near_poles() {
+    // Crossing the pole
+    let (start, end) = (geo(89.9, 0.0), geo(89.9, 180.0));
+    assert_close(Point::great_circle_distance(&start, &end, RADIUS), radians(0.2) * RADIUS);
+    assert_close(Point::initial_bearing(&start, &end), 0.0);
+    assert_close(Point::final_bearing(&start, &end).abs(), PI);
+    assert_same_point(&Point::midpoint(&start, &end), &geo(90.0, 0.0));
+
+    // Leaving the pole heads south along the meridian given by the bearing
+    let pole = Point::new(0.0, 0.0);
+    for lon in [0.0, 45.0, 90.0, -135.0].iter() {
+        let target = geo(80.0, *lon);
+        let bearing = Point::initial_bearing(&pole, &target);
+        let distance = Point::great_circle_distance(&pole, &target, RADIUS);
+        assert_same_point(&Point::destination(&pole, distance, bearing, RADIUS), &target);
+    }
+
+    // At the south pole the north lies along the meridian of the pole point
+    let pole = Point::new(PI, 0.0);
+    assert_close(Point::initial_bearing(&pole, &geo(-80.0, 0.0)), 0.0);
+    assert_close(Point::final_bearing(&geo(-80.0, 30.0), &pole), radians(-150.0));
+}
+
+#[test]
+fn interpolate_along_great_circles() {
+    let (start, end) = (geo(0.0, 0.0), geo(0.0, 90.0));
+    assert_same_point(&Point::interpolate(&start, &end, 0.0), &start);
+    assert_same_point(&Point::interpolate(&start, &end, 1.0), &end);
+    assert_same_point(&Point::interpolate(&start, &end, 1.0 / 3.0), &geo(0.0, 30.0));
+    assert_same_point(&Point::midpoint(&geo(0.0, 0.0), &geo(90.0, 0.0)), &geo(45.0, 0.0));
+
+    // Antipodal points give some path of the right length
+    let (start, end) = (geo(0.0, 0.0), geo(0.0, 180.0));
+    let middle = Point::midpoint(&start, &end);
+    assert_close(Point::great_circle_distance(&start, &middle, RADIUS), 0.5 * PI * RADIUS);
+
+    // Destination is consistent with the distance and the bearing
+    let (start, end) = (geo(-30.0, 100.0), geo(20.0, 140.0));
+    let distance = Point::great_circle_distance(&start, &end, RADIUS);
+    let bearing = Point::initial_bearing(&start, &end);
+    assert_same_point(&Point::destination(&start, distance, bearing, RADIUS), &end);
+    assert_same_point(&start.moved_by(distance, bearing, RADIUS), &end);
+}
+
+#[test]
+fn cross_track_distances() {
+    let (start, end) = (geo(0.0, 0.0), geo(0.0, 10.0));
+    let degree = radians(1.0) * RADIUS;
+    assert_close(Point::cross_track_distance(&geo(-1.0, 5.0), &start, &end, RADIUS), degree);
+    assert_close(Point::cross_track_distance(&geo(1.0, 50.0), &start, &end, RADIUS), -degree);
+    assert_close(Point::cross_track_distance(&geo(0.0, -20.0), &start, &end, RADIUS), 0.0);
+    assert_close(
+        Point::cross_track_distance(&geo(90.0, 0.0), &start, &end, RADIUS),
+        -0.5 * PI * RADIUS,
+    );
+}
+
+#[test]
+fn polygon_areas() {
+    let octant = vec![geo(0.0, 0.0), geo(0.0, 90.0), geo(90.0, 0.0)];
+    let expected = 0.5 * PI * RADIUS * RADIUS;
+    assert_close(Point::polygon_area(&octant, RADIUS) / expected, 1.0);
+    let reversed: Vec<Point> = octant.iter().rev().cloned().collect();
+    assert_close(Point::polygon_area(&reversed, RADIUS) / expected, 1.0);
+
+    // Square across the antimeridian around the pole
+    let cap = vec![geo(80.0, 170.0), geo(80.0, -100.0), geo(80.0, -10.0), geo(80.0, 80.0)];
+    let area = Point::polygon_area(&cap, RADIUS);
+    let cap_area = 2.0 * PI * RADIUS * RADIUS * (1.0 - radians(10.0).cos());
+    assert!(area > 0.6 * cap_area && area < cap_area);
+
+    // Small square close to a flat one
+    let side = radians(0.1) * RADIUS;
+    let square = vec![geo(0.0, 0.0), geo(0.0, 0.1), geo(0.1, 0.1), geo(0.1, 0.0)];
+    assert!((Point::polygon_area(&square, RADIUS) / (side * side) - 1.0).abs() < 1.0e-2);
+
+    assert_eq!(Point::polygon_area(&octant[..2], RADIUS), 0.0);
+}
@@ -0,0 +1,166 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+use std::f32::consts::PI;
+
+use edgin_around_rendering::{
+    renderers::{triangulate, DecalMesh, DecalShape},
+    utils::coordinates::Point,
+};
+
+const RADIUS: f32 = 100.0;
+
+fn assert_within(mesh: &DecalMesh, center: &Point, max_distance: f32) {
+    for point in mesh.points.iter() {
+        assert!(Point::great_circle_distance(center, point, RADIUS) <= max_distance + 0.001);
+    }
+    for (u, v) in mesh.tex_coords.iter() {
+        assert!((0.0..=1.0).contains(u) && (0.0..=1.0).contains(v));
+    }
+    for (a, b, c) in mesh.triangles.iter() {
+        let len = mesh.points.len() as u32;
+        assert!(*a < len && *b < len && *c < len);
+    }
+}
+
+#[test]
+fn triangulate_convex_and_concave_polygons() {
+    let square = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)];
+    assert_eq!(triangulate(&square).len(), 2);
+
+    let clockwise = [(0.0, 0.0), (0.0, 1.0), (1.0, 1.0), (1.0, 0.0)];
+    assert_eq!(triangulate(&clockwise).len(), 2);
+
+    let l_shape = [(0.0, 0.0), (2.0, 0.0), (2.0, 1.0), (1.0, 1.0), (1.0, 2.0), (0.0, 2.0)];
+    let triangles = triangulate(&l_shape);
+    assert_eq!(triangles.len(), 4);
+    let area: f32 = triangles
+        .iter()
+        .map(|(a, b, c)| {
+            let (a, b, c) = (l_shape[*a], l_shape[*b], l_shape[*c]);
+            0.5 * ((b.0 - a.0) * (c.1 - a.1) - (c.0 - a.0) * (b.1 - a.1)).abs()
+        })
+        .sum();
+    assert!((area - 3.0).abs() < 0.001);
+}
+
+#[test]
+fn build_meshes_of_round_shapes() {
+    let center = Point::new(0.5 * PI, 0.0);
+
+    let circle =
+        DecalMesh::new(&DecalShape::Circle { center: center.clone(), radius: 3.0 }, RADIUS, 1.0);
+    assert!(!circle.triangles.is_empty());
+    assert_within(&circle, &center, 3.0);
+
+    let shape = DecalShape::Ring { center: center.clone(), inner_radius: 2.0, outer_radius: 3.0 };
+    let ring = DecalMesh::new(&shape, RADIUS, 1.0);
+    assert!(!ring.triangles.is_empty());
+    assert_within(&ring, &center, 3.0);
+    for point in ring.points.iter() {
+        assert!(Point::great_circle_distance(&center, point, RADIUS) >= 2.0 - 0.001);
+    }
+
+    let shape =
+        DecalShape::Sector { center: center.clone(), radius: 3.0, bearing: 0.0, angle: 0.5 * PI };
+    let sector = DecalMesh::new(&shape, RADIUS, 1.0);
+    assert!(!sector.triangles.is_empty());
+    assert!(sector.triangles.len() < circle.triangles.len());
+    assert_within(&sector, &center, 3.0);
+}
+
+#[test]
+fn count_rings_and_segments_of_discs() {
+    let center = Point::new(0.5 * PI, 0.0);
+
+    // A circle with a radius of three steps has three rings of 19 segments around the center
+    let shape = DecalShape::Circle { center: center.clone(), radius: 3.0 };
+    let circle = DecalMesh::new(&shape, RADIUS, 1.0);
+    assert_eq!(circle.points.len(), 1 + 3 * 19);
+    assert_eq!(circle.triangles.len(), 19 * (1 + 2 * 2));
+
+    // Small shapes still have the minimal number of segments
+    let shape = DecalShape::Circle { center: center.clone(), radius: 0.5 };
+    let small = DecalMesh::new(&shape, RADIUS, 1.0);
+    assert_eq!(small.points.len(), 1 + 12);
+    assert_eq!(small.triangles.len(), 12);
+
+    // A ring has no center and one band of quads between its edges
+    let shape = DecalShape::Ring { center: center.clone(), inner_radius: 2.0, outer_radius: 3.0 };
+    let ring = DecalMesh::new(&shape, RADIUS, 1.0);
+    assert_eq!(ring.points.len(), 2 * 19);
+    assert_eq!(ring.triangles.len(), 19 * 2);
+
+    // An open sector repeats its last column instead of wrapping around
+    let shape =
+        DecalShape::Sector { center: center.clone(), radius: 3.0, bearing: 0.0, angle: 0.5 * PI };
+    let sector = DecalMesh::new(&shape, RADIUS, 1.0);
+    assert_eq!(sector.points.len(), 1 + 3 * 13);
+    assert_eq!(sector.triangles.len(), 12 * (1 + 2 * 2));
+
+    // Sectors wider than a full turn are closed circles
+    let shape =
+        DecalShape::Sector { center: center.clone(), radius: 3.0, bearing: 0.0, angle: 7.0 };
+    let full = DecalMesh::new(&shape, RADIUS, 1.0);
+    assert_eq!(full.points.len(), circle.points.len());
+    assert_eq!(full.triangles.len(), circle.triangles.len());
+}
+
+#[test]
+fn map_texture_onto_discs() {
+    let center = Point::new(0.5 * PI, 0.0);
+    let shape = DecalShape::Circle { center: center.clone(), radius: 3.0 };
+    let mesh = DecalMesh::new(&shape, RADIUS, 1.0);
+    assert_eq!(mesh.tex_coords[0], (0.5, 0.5));
+
+    // Points on the edge lie on the circle inscribed in the texture, with the north at the top
+    for (point, (u, v)) in mesh.points.iter().zip(mesh.tex_coords.iter()) {
+        let distance = Point::great_circle_distance(&center, point, RADIUS);
+        let tex_distance = ((u - 0.5).powi(2) + (v - 0.5).powi(2)).sqrt();
+        assert!((tex_distance - 0.5 * distance / 3.0).abs() < 0.001);
+    }
+    let (u, v) = mesh.tex_coords[mesh.points.len() - 19];
+    assert!((u - 0.5).abs() < 0.001 && v.abs() < 0.001);
+}
+
+#[test]
+fn limit_meshes_of_huge_shapes() {
+    let center = Point::new(0.5 * PI, 0.0);
+
+    // Many rings and segments are capped
+    let shape = DecalShape::Circle { center: center.clone(), radius: 1.0e6 };
+    assert!(shape.is_valid());
+    let mesh = DecalMesh::new(&shape, RADIUS, 1.0);
+    assert_eq!(mesh.points.len(), 1 + 128 * 128);
+    assert_eq!(mesh.triangles.len(), 128 * (2 * 128 - 1));
+
+    for radius in [f32::INFINITY, f32::NAN, -1.0].iter() {
+        let shape = DecalShape::Circle { center: center.clone(), radius: *radius };
+        assert!(!shape.is_valid());
+        assert!(DecalMesh::new(&shape, RADIUS, 1.0).triangles.is_empty());
+    }
+    let shape = DecalShape::Ring { center: center.clone(), inner_radius: 3.0, outer_radius: 2.0 };
+    assert!(!shape.is_valid());
+    let shape = DecalShape::Sector { center, radius: 3.0, bearing: f32::NAN, angle: 0.5 * PI };
+    assert!(!shape.is_valid());
+}
+
+#[test]
+fn subdivide_polygon_mesh() {
+    let center = Point::new(0.5 * PI, 0.0);
+    let vertices = vec![
+        center.moved_by(5.0, 0.0, RADIUS),
+        center.moved_by(5.0, 0.5 * PI, RADIUS),
+        center.moved_by(5.0, PI, RADIUS),
+        center.moved_by(5.0, 1.5 * PI, RADIUS),
+    ];
+    let mesh = DecalMesh::new(&DecalShape::Polygon { vertices }, RADIUS, 1.0);
+    assert!(mesh.triangles.len() > 2);
+    assert_within(&mesh, &center, 5.0);
+    for (a, b, c) in mesh.triangles.iter() {
+        for (i, j) in [(a, b), (b, c), (c, a)].iter() {
+            let (p, q) = (&mesh.points[**i as usize], &mesh.points[**j as usize]);
+            assert!(Point::great_circle_distance(p, q, RADIUS) <= 1.0 + 0.001);
+        }
+    }
+}
@@ -0,0 +1,176 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+use std::f32::consts::PI;
+
+use edgin_around_rendering::{
+    game::{EditKind, ElevationFunction, Heightmap, Scene, TerrainEdit},
+    utils::{coordinates::Point, noise::MAX_OCTAVES},
+};
+
+#[test]
+fn local_terrain_stays_within_range() {
+    let mut elevation = ElevationFunction::new(1000.0);
+    elevation.add_local_terrain("bump", 0.5 * PI, 0.0, 100.0, 2.0, "smooth");
+
+    let origin = Point::new(0.5 * PI, 0.0);
+    let near = origin.moved_by(50.0, 0.0, 1000.0);
+    let far = origin.moved_by(150.0, 0.0, 1000.0);
+
+    assert!((elevation.evaluate(&origin) - 1020.0).abs() < 0.01);
+    assert!(elevation.evaluate(&near) > 1000.0);
+    assert!(elevation.evaluate(&near) < 1020.0);
+    assert_eq!(elevation.evaluate(&far), 1000.0);
+}
+
+#[test]
+fn negative_amplitude_makes_depression() {
+    let mut elevation = ElevationFunction::new(1000.0);
+    elevation.add_local_terrain("bump", 0.5 * PI, 0.0, 100.0, -1.0, "linear");
+
+    assert!(elevation.evaluate(&Point::new(0.5 * PI, 0.0)) < 1000.0);
+}
+
+#[test]
+fn global_terrain_is_not_limited() {
+    let mut global = ElevationFunction::new(1000.0);
+    global.add_terrain("continents", 0.0, 0.0);
+
+    let point = Point::new(0.5 * PI, 0.5 * PI);
+    assert!((global.evaluate(&point) - 1018.0).abs() < 0.01);
+}
+
+#[test]
+fn noise_terrain_is_deterministic() {
+    let mut elevation1 = ElevationFunction::new(1000.0);
+    let mut elevation2 = ElevationFunction::new(1000.0);
+    let mut elevation3 = ElevationFunction::new(1000.0);
+    elevation1.add_noise_terrain(42, 5, 4.0, 20.0);
+    elevation2.add_noise_terrain(42, 5, 4.0, 20.0);
+    elevation3.add_noise_terrain(43, 5, 4.0, 20.0);
+
+    let points: Vec<Point> =
+        (0..20).map(|i| Point::new(0.05 * PI * i as f32, 0.1 * PI * i as f32)).collect();
+    let heights1: Vec<f32> = points.iter().map(|p| elevation1.evaluate(p)).collect();
+    let heights2: Vec<f32> = points.iter().map(|p| elevation2.evaluate(p)).collect();
+    let heights3: Vec<f32> = points.iter().map(|p| elevation3.evaluate(p)).collect();
+
+    assert_eq!(heights1, heights2);
+    assert_ne!(heights1, heights3);
+    assert!(heights1.iter().all(|h| (980.0..=1020.0).contains(h)));
+}
+
+#[test]
+fn noise_terrain_limits_octaves() {
+    let mut limited = ElevationFunction::new(1000.0);
+    let mut huge = ElevationFunction::new(1000.0);
+    limited.add_noise_terrain(42, MAX_OCTAVES, 4.0, 20.0);
+    huge.add_noise_terrain(42, u32::MAX, 4.0, 20.0);
+
+    let point = Point::new(0.3, 1.2);
+    assert_eq!(limited.evaluate(&point), huge.evaluate(&point));
+}
+
+#[test]
+fn noise_terrain_has_no_seams() {
+    let mut elevation = ElevationFunction::new(1000.0);
+    elevation.add_noise_terrain(7, 4, 3.0, 20.0);
+
+    let west = elevation.evaluate(&Point::new(0.3 * PI, 2.0 * PI - 0.0001));
+    let east = elevation.evaluate(&Point::new(0.3 * PI, 0.0001));
+    assert!((west - east).abs() < 0.01);
+
+    let pole1 = elevation.evaluate(&Point::new(0.0, 0.0));
+    let pole2 = elevation.evaluate(&Point::new(0.0, PI));
+    assert!((pole1 - pole2).abs() < 0.01);
+}
+
+#[test]
+fn heightmap_is_filtered_and_wrapped() {
+    // Western half is low, eastern half is high.
+    let heightmap = Heightmap::new(4, 2, vec![0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 1.0]);
+    let mut elevation = ElevationFunction::new(1000.0);
+    elevation.add_heightmap(heightmap, -10.0, 30.0);
+    elevation.add_local_terrain("bump", 0.5 * PI, 0.5 * PI, 100.0, 1.0, "linear");
+
+    // Centers of pixels
+    assert_eq!(elevation.evaluate(&Point::new(0.25 * PI, 1.25 * PI)), 990.0);
+    assert_eq!(elevation.evaluate(&Point::new(0.25 * PI, 0.75 * PI)), 1030.0);
+
+    // Halfway between pixels and across the antimeridian
+    assert_eq!(elevation.evaluate(&Point::new(0.25 * PI, 0.0)), 1010.0);
+    assert_eq!(elevation.evaluate(&Point::new(0.75 * PI, PI)), 1010.0);
+
+    // Combined with other terrain
+    assert_eq!(elevation.evaluate(&Point::new(0.5 * PI, 0.5 * PI)), 1030.0 + 10.0);
+}
+
+#[test]
+fn terrain_edits_change_elevation_locally() {
+    let mut scene = Scene::new();
+    scene.configure(0, ElevationFunction::new(1000.0));
+
+    let center = Point::new(0.5 * PI, 0.0);
+    let near = center.moved_by(5.0, 0.0, 1000.0);
+    let far = center.moved_by(50.0, 0.0, 1000.0);
+
+    scene.raise_terrain(&center, 10.0, 3.0);
+    assert!((scene.get_elevation(&center) - 1003.0).abs() < 0.01);
+    assert!(scene.get_elevation(&near) > 1000.0);
+    assert_eq!(scene.get_elevation(&far), 1000.0);
+
+    scene.lower_terrain(&center, 10.0, 1.0);
+    assert!((scene.get_elevation(&center) - 1002.0).abs() < 0.01);
+
+    scene.flatten_terrain(&center, 20.0, -4.0);
+    assert!((scene.get_elevation(&center) - 996.0).abs() < 0.01);
+    assert_eq!(scene.get_elevation(&far), 1000.0);
+    assert_eq!(scene.get_terrain_edits().len(), 3);
+
+    // Edits without a range change nothing
+    scene.raise_terrain(&center, 0.0, 3.0);
+    scene.flatten_terrain(&center, -1.0, 8.0);
+    assert!((scene.get_elevation(&center) - 996.0).abs() < 0.01);
+}
+
+#[test]
+fn terrain_edits_keep_order_among_many() {
+    let mut elevation = ElevationFunction::new(1000.0);
+    let center = Point::new(0.5 * PI, 1.0);
+
+    // Edits scattered around the planet do not affect the center
+    for i in 0..200 {
+        let point = Point::new(0.1 + 0.014 * i as f32, 0.03 * i as f32 + 2.0);
+        elevation.add_edit(TerrainEdit::new(EditKind::Raise, point, 15.0, 1.0));
+    }
+    elevation.add_edit(TerrainEdit::new(EditKind::Flatten, center.clone(), 30.0, 5.0));
+    elevation.add_edit(TerrainEdit::new(EditKind::Raise, center.clone(), 100.0, 2.0));
+    assert!((elevation.evaluate(&center) - 1007.0).abs() < 0.01);
+
+    // The order of edits survives serialization
+    let yaml = serde_yaml::to_string(&elevation).unwrap();
+    let loaded: ElevationFunction = serde_yaml::from_str(&yaml).unwrap();
+    assert_eq!(loaded.get_edits().len(), 202);
+    for i in 0..20 {
+        let point = center.moved_by(4.0 * i as f32, 0.3 * i as f32, 1000.0);
+        assert_eq!(loaded.evaluate(&point), elevation.evaluate(&point));
+    }
+    assert!((loaded.evaluate(&center) - 1007.0).abs() < 0.01);
+}
+
+#[test]
+fn local_terrain_without_range_has_no_effect() {
+    let mut elevation = ElevationFunction::new(1000.0);
+    elevation.add_local_terrain("bump", 0.5 * PI, 0.0, 0.0, 2.0, "smooth");
+    elevation.add_local_terrain("bump", 0.5 * PI, 0.0, -5.0, 2.0, "linear");
+    assert_eq!(elevation.evaluate(&Point::new(0.5 * PI, 0.0)), 1000.0);
+
+    // Ranges read from a description are not validated when added
+    let yaml = "
+radius: 1000.0
+terrain:
+  - { variant: bump, origin: { theta: 1.5, phi: 0.0 }, range: 0.0 }
+";
+    let elevation: ElevationFunction = serde_yaml::from_str(yaml).unwrap();
+    assert_eq!(elevation.evaluate(&Point::new(1.5, 0.0)), 1000.0);
+}
@@ -0,0 +1,110 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+use std::f32::consts::PI;
+
+use edgin_around_rendering::{
+    animations::{Facing, Parser, Sprite, ACTION_NAME_WALK},
+    game::{Actor, Scene},
+    utils::coordinates::Point,
+};
+
+const SAML: &str = "
+directions: 4
+sources: []
+skeletons:
+  - { id: body, scale: 1.0, bones: [] }
+animations:
+  - { id: stand, skeleton_id: body, length: 1.0, keys: {}, muscles: [] }
+  - { id: stand_front, skeleton_id: body, length: 1.0, keys: {}, muscles: [] }
+  - { id: walk, skeleton_id: body, length: 1.0, keys: {}, muscles: [] }
+  - { id: walk_back, skeleton_id: body, length: 1.0, keys: {}, muscles: [] }
+selection:
+  - { variant: default, action: idle, animation: stand }
+  - { variant: default, action: idle, animation: stand_front, direction: front }
+  - { variant: default, action: walk, animation: walk }
+  - { variant: default, action: walk, animation: walk_back, direction: back }
+";
+
+fn load_sprite(name: &str, saml: &str) -> Sprite {
+    let path = std::env::temp_dir().join(name).with_extension("saml");
+    std::fs::write(&path, saml).unwrap();
+    let parser = Parser::new(&path);
+    std::fs::remove_file(&path).unwrap();
+    Sprite::new(0, parser.to_stock())
+}
+
+#[test]
+fn choose_facing_from_heading() {
+    assert_eq!(Facing::from_heading(0.3, 1), None);
+    assert_eq!(Facing::from_heading(0.3, 2), Some(Facing::Right));
+    assert_eq!(Facing::from_heading(-0.3, 2), Some(Facing::Left));
+    assert_eq!(Facing::from_heading(0.3, 4), Some(Facing::Back));
+    assert_eq!(Facing::from_heading(0.3 - PI, 4), Some(Facing::Front));
+    assert_eq!(Facing::from_heading(-0.5 * PI, 4), Some(Facing::Left));
+    assert_eq!(Facing::from_heading(0.3, 8), Some(Facing::Back));
+    assert_eq!(Facing::from_heading(0.6, 8), Some(Facing::BackRight));
+    assert_eq!(Facing::from_heading(2.0 * PI - 0.6, 8), Some(Facing::BackLeft));
+    assert_eq!(Facing::from_heading(0.9 * PI, 8), Some(Facing::Front));
+    assert_eq!(Facing::FrontLeft.mirrored(), Facing::FrontRight);
+    assert_eq!(Facing::Back.mirrored(), Facing::Back);
+}
+
+#[test]
+fn turn_sprite_to_heading() {
+    let mut sprite = load_sprite("edgin_around_facing", SAML);
+    assert_eq!(sprite.get_facing(), None);
+
+    // Undirected animations face right and are mirrored for the left side
+    sprite.set_heading(Some(-0.5 * PI));
+    assert_eq!(sprite.get_facing(), Some(Facing::Left));
+    assert!(sprite.is_mirrored());
+
+    // Directed animations are preferred and kept when the action changes if possible
+    sprite.set_heading(Some(PI));
+    assert_eq!(sprite.get_facing(), Some(Facing::Front));
+    assert!(!sprite.is_mirrored());
+    sprite.set_heading(Some(0.0));
+    sprite.select_action(ACTION_NAME_WALK).unwrap();
+    assert_eq!(sprite.get_facing(), Some(Facing::Back));
+    assert!(!sprite.is_mirrored());
+
+    sprite.set_heading(None);
+    assert_eq!(sprite.get_facing(), None);
+    assert!(!sprite.is_mirrored());
+
+    // Sprites without directions are never turned
+    let mut sprite = load_sprite("edgin_around_no_facing", &SAML.replace("directions: 4", ""));
+    sprite.set_heading(Some(-0.5 * PI));
+    assert_eq!(sprite.get_facing(), None);
+    assert!(!sprite.is_mirrored());
+}
+
+#[test]
+fn actors_face_direction_of_movement() {
+    let mut scene = Scene::new();
+    scene.create_actors(&vec![
+        Actor::new(1, "pirate".to_string(), Some(Point::new(0.5 * PI, 0.0))),
+        Actor::new(2, "pirate".to_string(), Some(Point::new(0.5 * PI, 0.0))),
+        Actor::new(3, "pirate".to_string(), Some(Point::new(0.5 * PI, 0.0))),
+    ]);
+    assert_eq!(scene.get_actor(1).unwrap().get_bearing(), None);
+
+    scene.move_actor_by(1, 5.0, 0.5 * PI);
+    let bearing = scene.get_actor(1).unwrap().get_bearing().unwrap();
+    assert!((bearing - 0.5 * PI).abs() < 1e-3);
+
+    scene.set_time(0.0);
+    scene.set_actor_path(2, vec![Point::new(0.4 * PI, 0.0)], 10.0).unwrap();
+    scene.set_time(0.0);
+    scene.set_time(1.0);
+    assert!(scene.get_actor(2).unwrap().get_bearing().unwrap().abs() < 1e-3);
+
+    scene.update_actor_position(3, Point::new(0.5 * PI, 0.0), 0.0);
+    scene.update_actor_position(3, Point::new(0.5 * PI, -0.001), 1.0);
+    let bearing = scene.get_actor(3).unwrap().get_bearing().unwrap();
+    assert!((bearing + 0.5 * PI).abs() < 1e-3);
+
+    scene.set_actor_bearing(3, None);
+    assert_eq!(scene.get_actor(3).unwrap().get_bearing(), None);
+}
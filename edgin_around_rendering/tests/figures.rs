@@ -0,0 +1,85 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+use edgin_around_rendering::utils::{coordinates::Point3D, figures};
+
+fn stretch(theta: f32, phi: f32) -> f32 {
+    100.0 + 5.0 * (3.0 * theta).sin() * (2.0 * phi).cos()
+}
+
+fn is_on_segment(point: &Point3D, start: &Point3D, end: &Point3D) -> bool {
+    let length = Point3D::distance(start, end);
+    let sum = Point3D::distance(start, point) + Point3D::distance(point, end);
+    (sum - length).abs() < 1e-3
+}
+
+#[test]
+fn sphere_chunk_has_expected_size() {
+    let base = figures::sphere(0, 1.0);
+    let vertices = base.get_vertices();
+    let t = base.get_triangles()[0];
+    let corners = [&vertices[t.0 as usize], &vertices[t.1 as usize], &vertices[t.2 as usize]];
+
+    let chunk = figures::sphere_chunk(corners, 3, [3, 3, 3], stretch);
+    assert_eq!(chunk.get_vertices().len(), 45);
+    assert_eq!(chunk.get_triangles().len(), 64);
+}
+
+#[test]
+fn sphere_chunks_with_different_levels_meet_without_cracks() {
+    let base = figures::sphere(0, 1.0);
+    let vertices = base.get_vertices();
+
+    // Triangles (0, 2, 4) and (0, 2, 6) share the edge (0, 2).
+    let (a, b, c, d) = (&vertices[0], &vertices[2], &vertices[4], &vertices[6]);
+    let fine = figures::sphere_chunk([a, b, c], 4, [1, 4, 4], stretch);
+    let coarse = figures::sphere_chunk([b, a, d], 1, [1, 1, 1], stretch);
+
+    // Vertices of the coarse chunk lying on the shared edge, ordered from `a` to `b`.
+    let coarse_edge: Vec<&Point3D> =
+        vec![&coarse.get_vertices()[5], &coarse.get_vertices()[3], &coarse.get_vertices()[0]];
+
+    // The first row of the fine chunk lies on the shared edge.
+    for i in 0..=16 {
+        let vertex = &fine.get_vertices()[i * (35 - i) / 2];
+        let segment = (i / 8).min(1);
+        assert!(is_on_segment(vertex, coarse_edge[segment], coarse_edge[segment + 1]));
+    }
+}
+
+#[test]
+fn sphere_normals_point_outwards() {
+    let mut sphere = figures::sphere(2, 10.0);
+    sphere.rescale(|_, _| 20.0);
+
+    for (vertex, normal) in sphere.get_vertices().iter().zip(sphere.get_normals().iter()) {
+        assert!((normal.length() - 1.0).abs() < 1e-4);
+        assert!(normal.dot(&vertex.enlongated(1.0)) > 0.99);
+    }
+}
+
+#[test]
+fn sphere_chunk_edge_normals_match_interior_normals() {
+    let base = figures::sphere(0, 1.0);
+    let vertices = base.get_vertices();
+    let t = base.get_triangles()[0];
+    let corners = [&vertices[t.0 as usize], &vertices[t.1 as usize], &vertices[t.2 as usize]];
+
+    let chunk = figures::sphere_chunk(corners, 4, [4, 4, 4], stretch);
+    let normals = chunk.get_normals();
+
+    // Vertices (1, 0) on the edge and (1, 1) inside the chunk are neighbours.
+    let (edge, inner) = (&normals[17], &normals[18]);
+    assert!(edge.dot(inner) > 0.95);
+}
+
+#[test]
+fn polyhedron_values_follow_vertices() {
+    let mut sphere = figures::sphere(1, 10.0);
+    assert!(sphere.get_values().iter().all(|v| *v == 0.0));
+
+    sphere.assign_values(|theta, _| theta);
+    for (vertex, value) in sphere.get_vertices().iter().zip(sphere.get_values().iter()) {
+        assert!((value - (vertex.y / 10.0).acos()).abs() < 1e-4);
+    }
+}
@@ -0,0 +1,62 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+use edgin_around_rendering::{
+    expositors::WorldExpositor,
+    renderers::{Highlight, HighlightStyle},
+};
+
+const ENTITIES_FRAGMENT_SHADER: &str = include_str!("../shaders/entities_fragment.glsl");
+
+#[test]
+fn prefer_hover_highlight() {
+    let mut world = WorldExpositor::new(std::env::temp_dir(), (100, 100));
+    let selected = Highlight::new(HighlightStyle::Tint, [1.0, 0.0, 0.0, 0.5]);
+    let hovered = Highlight::new(HighlightStyle::Pulse, [0.0, 1.0, 0.0, 1.0]);
+    world.set_hover_highlight(hovered.clone());
+    world.highlight_actor(1, selected.clone());
+    assert_eq!(world.get_shown_highlight(1), Some(&selected));
+    assert_eq!(world.get_shown_highlight(2), None);
+
+    // Hovering a highlighted actor shows the hover highlight until the pointer leaves it
+    world.set_hovered_actor_id(Some(1));
+    assert_eq!(world.get_shown_highlight(1), Some(&hovered));
+    world.set_hovered_actor_id(Some(2));
+    assert_eq!(world.get_shown_highlight(1), Some(&selected));
+    assert_eq!(world.get_shown_highlight(2), Some(&hovered));
+
+    // The hovered actor stays marked when its regular highlight is removed
+    world.set_hovered_actor_id(Some(1));
+    world.unhighlight_actor(1);
+    assert_eq!(world.get_shown_highlight(1), Some(&hovered));
+    world.set_hovered_actor_id(None);
+    assert_eq!(world.get_shown_highlight(1), None);
+}
+
+#[test]
+fn outline_hovered_actors_by_default() {
+    let mut world = WorldExpositor::new(std::env::temp_dir(), (100, 100));
+    world.highlight_actor(1, Highlight::default());
+    world.set_hovered_actor_id(Some(1));
+    let highlight = world.get_shown_highlight(1).unwrap();
+    assert_eq!(highlight.style, HighlightStyle::Outline);
+    assert_eq!(world.get_highlight(1).unwrap().style, HighlightStyle::Tint);
+}
+
+#[test]
+fn match_shader_highlight_styles() {
+    let styles = [HighlightStyle::Tint, HighlightStyle::Pulse, HighlightStyle::Outline];
+    let ids: Vec<i32> = styles.iter().map(|style| style.get_shader_id()).collect();
+    assert!(ids.iter().all(|id| *id != 0));
+    assert!(ids.iter().enumerate().all(|(i, id)| !ids[..i].contains(id)));
+
+    let shader_ids = [
+        ("highlightNone", 0),
+        ("highlightPulse", HighlightStyle::Pulse.get_shader_id()),
+        ("highlightOutline", HighlightStyle::Outline.get_shader_id()),
+    ];
+    for (name, id) in shader_ids.iter() {
+        let declaration = format!("const highp int {} = {};", name, id);
+        assert!(ENTITIES_FRAGMENT_SHADER.contains(&declaration), "{}", declaration);
+    }
+}
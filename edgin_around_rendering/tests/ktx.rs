@@ -0,0 +1,83 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+use edgin_around_rendering::game::CompressedImage;
+
+fn make_ktx2(vk_format: u32, width: u32, height: u32, levels: &[Vec<u8>]) -> Vec<u8> {
+    let mut data = vec![0xAB, 0x4B, 0x54, 0x58, 0x20, 0x32, 0x30, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A];
+    for value in &[vk_format, 1, width, height, 0, 0, 1, levels.len() as u32, 0] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.resize(80, 0);
+
+    let mut offset = 80 + 24 * levels.len();
+    for level in levels {
+        data.extend_from_slice(&(offset as u64).to_le_bytes());
+        data.extend_from_slice(&(level.len() as u64).to_le_bytes());
+        data.extend_from_slice(&(level.len() as u64).to_le_bytes());
+        offset += level.len();
+    }
+    for level in levels {
+        data.extend_from_slice(level);
+    }
+    data
+}
+
+#[test]
+fn parse_etc2_with_mipmaps() {
+    let data = make_ktx2(151, 8, 4, &[vec![1; 32], vec![2; 16]]);
+    let image = CompressedImage::parse(&data).expect("Parse KTX2");
+
+    assert_eq!(image.get_format(), gl::COMPRESSED_RGBA8_ETC2_EAC);
+    assert_eq!(image.get_levels().len(), 2);
+    assert_eq!((image.get_levels()[0].width, image.get_levels()[0].height), (8, 4));
+    assert_eq!((image.get_levels()[1].width, image.get_levels()[1].height), (4, 2));
+    assert_eq!(image.get_levels()[1].data, vec![2; 16]);
+}
+
+#[test]
+fn reject_unsupported_format() {
+    let data = make_ktx2(37, 4, 4, &[vec![0; 64]]);
+    assert!(CompressedImage::parse(&data).is_err());
+}
+
+#[test]
+fn reject_truncated_data() {
+    let mut data = make_ktx2(157, 4, 4, &[vec![0; 16]]);
+    data.truncate(data.len() - 1);
+    assert!(CompressedImage::parse(&data).is_err());
+}
+
+#[test]
+fn reject_truncated_header() {
+    let data = make_ktx2(151, 4, 4, &[vec![0; 16]]);
+    for length in [0, 12, 40, 79].iter() {
+        assert!(CompressedImage::parse(&data[..*length]).is_err());
+    }
+}
+
+#[test]
+fn reject_huge_level_count() {
+    let mut data = make_ktx2(151, 4, 4, &[vec![0; 16]]);
+    data[40..44].copy_from_slice(&u32::MAX.to_le_bytes());
+    assert!(CompressedImage::parse(&data).is_err());
+}
+
+#[test]
+fn reject_level_out_of_range() {
+    let mut data = make_ktx2(151, 4, 4, &[vec![0; 16]]);
+    data[80..88].copy_from_slice(&u64::MAX.to_le_bytes());
+    assert!(CompressedImage::parse(&data).is_err());
+
+    let mut data = make_ktx2(151, 4, 4, &[vec![0; 16]]);
+    data[88..96].copy_from_slice(&u64::MAX.to_le_bytes());
+    assert!(CompressedImage::parse(&data).is_err());
+}
+
+#[test]
+fn keep_small_levels_of_many() {
+    let levels: Vec<Vec<u8>> = (0..40).map(|_| vec![0; 16]).collect();
+    let image = CompressedImage::parse(&make_ktx2(151, 4, 4, &levels)).expect("Parse KTX2");
+    assert_eq!(image.get_levels().len(), 40);
+    assert!(image.get_levels().iter().skip(2).all(|level| (level.width, level.height) == (1, 1)));
+}
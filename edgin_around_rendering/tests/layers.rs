@@ -0,0 +1,42 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+use edgin_around_rendering::game::{TerrainLayers, LAYERS_FILE, MAX_LAYERS, UNBOUNDED};
+
+const LAYERS: &str = "
+height_blend: 1.0
+layers:
+  - texture: sand
+    max_height: 0.5
+  - texture: grass
+    min_height: 0.5
+    max_slope: 0.6
+  - texture: rock
+    min_slope: 0.6
+  - texture: snow
+    min_height: 6.0
+  - texture: lava
+";
+
+#[test]
+fn load_terrain_layers() {
+    let dir = std::env::temp_dir().join("edgin_around_layers");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join(LAYERS_FILE), LAYERS).unwrap();
+    let layers = TerrainLayers::load(&dir).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let textures: Vec<_> = layers.layers.iter().map(|l| l.texture.as_str()).collect();
+    assert_eq!(textures, vec!["sand", "grass", "rock", "snow"]);
+    assert_eq!(layers.layers.len(), MAX_LAYERS);
+    assert_eq!(layers.height_blend, 1.0);
+    assert_eq!(layers.slope_blend, TerrainLayers::default().slope_blend);
+    assert_eq!(layers.layers[1].get_ranges(), (0.5, UNBOUNDED, -UNBOUNDED, 0.6));
+}
+
+#[test]
+fn default_terrain_layers_without_file() {
+    let dir = std::env::temp_dir().join("edgin_around_no_layers");
+    assert!(TerrainLayers::load(&dir).is_none());
+    assert_eq!(TerrainLayers::default().layers.len(), 1);
+}
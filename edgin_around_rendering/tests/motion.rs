@@ -0,0 +1,145 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+use edgin_around_rendering::{
+    game::{Actor, ElevationFunction, MotionSmoothing, Scene},
+    utils::coordinates::Point,
+};
+
+const RADIUS: f32 = 1000.0;
+const EPSILON: f32 = 1.0e-3;
+
+fn make_scene(smoothing: MotionSmoothing) -> Scene {
+    let mut scene = Scene::new();
+    scene.configure(1, ElevationFunction::new(RADIUS));
+    scene.create_actors(&vec![Actor::new(1, "pirate".to_string(), Some(Point::new(1.5, 0.0)))]);
+    scene.set_motion_smoothing(smoothing);
+    scene
+}
+
+/// Returns the distance along the equator of the rendered position from the origin.
+fn rendered_distance(scene: &Scene, time: f64) -> f32 {
+    let mut scene = scene.clone();
+    scene.set_time(time);
+    let position = scene.get_rendered_position(1).unwrap();
+    Point::great_circle_distance(&Point::new(1.5, 0.0), &position, RADIUS)
+}
+
+fn assert_close(actual: f32, expected: f32) {
+    assert!((actual - expected).abs() < EPSILON, "{} != {}", actual, expected);
+}
+
+fn east(distance: f32) -> Point {
+    Point::new(1.5, 0.0).moved_by(distance, 0.5 * std::f32::consts::PI, RADIUS)
+}
+
+#[test]
+fn interpolate_between_updates() {
+    let mut scene = make_scene(MotionSmoothing::new(0.1, 0.0, 10.0));
+    scene.update_actor_position(1, east(0.0), 1.0);
+    scene.update_actor_position(1, east(2.0), 1.2);
+
+    // The latest known position is used for everything but rendering
+    let position = scene.get_actor(1).unwrap().get_position().unwrap().clone();
+    assert_close(Point::great_circle_distance(&position, &east(2.0), RADIUS), 0.0);
+
+    assert_close(rendered_distance(&scene, 1.0), 0.0);
+    assert_close(rendered_distance(&scene, 1.2), 1.0);
+    assert_close(rendered_distance(&scene, 1.25), 1.5);
+    assert_close(rendered_distance(&scene, 2.0), 2.0);
+
+    // Updates older than the last one are ignored
+    scene.update_actor_position(1, east(5.0), 1.1);
+    assert_close(rendered_distance(&scene, 2.0), 2.0);
+}
+
+#[test]
+fn interpolate_with_delay_longer_than_update_interval() {
+    let mut scene = make_scene(MotionSmoothing::new(0.3, 0.0, 10.0));
+    for step in 0..5 {
+        scene.update_actor_position(1, east(step as f32), 1.0 + 0.1 * step as f64);
+    }
+
+    // Rendering lags three updates behind and moves steadily between them
+    assert_close(rendered_distance(&scene, 1.4), 1.0);
+    assert_close(rendered_distance(&scene, 1.45), 1.5);
+    assert_close(rendered_distance(&scene, 1.5), 2.0);
+    assert_close(rendered_distance(&scene, 1.65), 3.5);
+    assert_close(rendered_distance(&scene, 2.0), 4.0);
+}
+
+#[test]
+fn extrapolate_from_last_velocity() {
+    let mut scene = make_scene(MotionSmoothing::new(0.0, 0.5, 10.0));
+    scene.update_actor_position(1, east(0.0), 1.0);
+    scene.update_actor_position(1, east(1.0), 1.1);
+
+    assert_close(rendered_distance(&scene, 1.1), 1.0);
+    assert_close(rendered_distance(&scene, 1.3), 3.0);
+    assert_close(rendered_distance(&scene, 1.6), 6.0);
+    assert_close(rendered_distance(&scene, 5.0), 6.0);
+}
+
+#[test]
+fn snap_on_teleport() {
+    let mut scene = make_scene(MotionSmoothing::new(0.1, 0.0, 10.0));
+    scene.update_actor_position(1, east(0.0), 1.0);
+    scene.update_actor_position(1, east(50.0), 1.2);
+    assert_close(rendered_distance(&scene, 1.2), 50.0);
+
+    // Setting the position directly stops smoothing
+    scene.update_actor_position(1, east(52.0), 1.4);
+    scene.set_actor_position(1, east(60.0));
+    assert_close(rendered_distance(&scene, 1.4), 60.0);
+
+    scene.set_time(1.4);
+    assert_close(Point::great_circle_distance(&scene.get_focus_point(), &east(60.0), RADIUS), 0.0);
+}
+
+#[test]
+fn walk_along_path() {
+    let mut scene = make_scene(MotionSmoothing::default());
+    scene.set_actor_path(1, vec![east(3.0), east(5.0)], 2.0).unwrap();
+    assert!(scene.is_actor_moving(1));
+
+    // The first tick only starts the clock
+    scene.set_time(10.0);
+    assert_close(rendered_distance(&scene, 10.0), 0.0);
+
+    scene.set_time(11.0);
+    assert_close(rendered_distance(&scene, 11.0), 2.0);
+    assert!(scene.take_arrived_actors().is_empty());
+
+    // Passing a waypoint continues towards the next one
+    scene.set_time(12.0);
+    assert_close(rendered_distance(&scene, 12.0), 4.0);
+    assert_eq!(scene.get_actor_path(1).unwrap().get_waypoints().len(), 1);
+
+    scene.set_time(15.0);
+    assert_close(rendered_distance(&scene, 15.0), 5.0);
+    assert!(!scene.is_actor_moving(1));
+    assert_eq!(scene.take_arrived_actors(), vec![1]);
+    assert!(scene.take_arrived_actors().is_empty());
+
+    // Setting the position directly cancels the path
+    scene.set_actor_path(1, vec![east(10.0)], 1.0).unwrap();
+    scene.set_actor_position(1, east(0.0));
+    assert!(!scene.is_actor_moving(1));
+    scene.set_time(20.0);
+    assert!(scene.take_arrived_actors().is_empty());
+}
+
+#[test]
+fn reject_invalid_paths() {
+    let mut scene = make_scene(MotionSmoothing::default());
+    for speed in [0.0, -1.0, f32::NAN, f32::INFINITY].iter() {
+        assert!(scene.set_actor_path(1, vec![east(3.0)], *speed).is_err());
+        assert!(!scene.is_actor_moving(1));
+    }
+
+    // Hidden actors cannot walk, so they arrive at once
+    scene.hide_actors(&vec![1]);
+    scene.set_actor_path(1, vec![east(3.0)], 1.0).unwrap();
+    assert!(!scene.is_actor_moving(1));
+    assert_eq!(scene.take_arrived_actors(), vec![1]);
+}
@@ -0,0 +1,259 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use edgin_around_rendering::{
+    animations::{Parser, Sprite},
+    game::{Curve, CurveKey, EmitterAnchor, EmitterSpec, ParticleEmitter},
+    utils::coordinates::{Point, Point3D},
+};
+
+const ERROR: f32 = 0.001;
+
+static NEXT_EMITTER: AtomicUsize = AtomicUsize::new(0);
+
+const EMITTER: &str = "
+spawn_rate: 10.0
+lifetime: 1.0
+speed: 2.0
+spread: 0.3
+";
+
+const SAML: &str = "
+sources: []
+skeletons:
+  - id: body
+    scale: 2.0
+    bones:
+      - { id: torso, pose: { position_x: 0.0, position_y: 1.0 } }
+      - { id: hand, parent: torso, pose: { position_x: 0.5, position_y: 0.0 } }
+animations:
+  - { id: stand, skeleton_id: body, length: 1.0, keys: {}, muscles: [] }
+selection:
+  - { variant: default, action: idle, animation: stand }
+";
+
+fn load_spec(name: &str, yaml: &str) -> EmitterSpec {
+    let dir = std::env::temp_dir();
+    let path = dir.join(name).with_extension("yaml");
+    std::fs::write(&path, yaml).unwrap();
+    let spec = EmitterSpec::load(&dir, name);
+    std::fs::remove_file(&path).unwrap();
+    spec.unwrap()
+}
+
+fn make_emitter(extra: &str) -> ParticleEmitter {
+    // Tests run in parallel, so each of them needs a file of its own
+    let index = NEXT_EMITTER.fetch_add(1, Ordering::Relaxed);
+    let yaml = format!("{}{}", EMITTER, extra);
+    let spec = load_spec(&format!("edgin_around_emitter_{}", index), &yaml);
+    ParticleEmitter::new(spec, EmitterAnchor::Point(Point::new(0.0, 0.0)), 0)
+}
+
+#[test]
+fn load_emitter_spec() {
+    let spec = load_spec("edgin_around_default_emitter", EMITTER);
+    assert_eq!(spec.texture, None);
+    assert_eq!(spec.burst, 0);
+    assert_eq!(spec.duration, None);
+    assert_eq!(spec.gravity, 0.0);
+    assert_eq!(spec.size.sample(0.5), 0.2);
+    assert_eq!(spec.color.sample(0.5), [1.0, 1.0, 1.0, 1.0]);
+    assert_eq!(spec.max_particles, 256);
+
+    let yaml = format!("{}texture: spark\nsize: [{{ at: 0.0, value: 0.5 }}]\n", EMITTER);
+    let spec = load_spec("edgin_around_custom_emitter", &yaml);
+    assert_eq!(spec.texture, Some("spark".to_string()));
+    assert_eq!(spec.size.sample(0.0), 0.5);
+
+    let dir = std::env::temp_dir();
+    assert!(EmitterSpec::load(&dir, "edgin_around_missing_emitter").is_err());
+    let path = dir.join("edgin_around_broken_emitter").with_extension("yaml");
+    std::fs::write(&path, "spawn_rate: fast\n").unwrap();
+    let result = EmitterSpec::load(&dir, "edgin_around_broken_emitter");
+    std::fs::remove_file(&path).unwrap();
+    assert!(result.is_err());
+}
+
+#[test]
+fn sample_curves() {
+    let curve =
+        Curve { keys: vec![CurveKey { at: 0.2, value: 1.0 }, CurveKey { at: 0.6, value: 3.0 }] };
+    assert_eq!(curve.sample(0.0), 1.0);
+    assert!((curve.sample(0.4) - 2.0).abs() < ERROR);
+    assert_eq!(curve.sample(1.0), 3.0);
+    assert_eq!(Curve::<f32> { keys: Vec::new() }.sample(0.5), 0.0);
+
+    let colors = Curve {
+        keys: vec![
+            CurveKey { at: 0.0, value: [1.0, 1.0, 1.0, 1.0] },
+            CurveKey { at: 1.0, value: [0.0, 0.0, 0.0, 0.0] },
+        ],
+    };
+    assert_eq!(colors.sample(0.5), [0.5, 0.5, 0.5, 0.5]);
+}
+
+#[test]
+fn spawn_particles() {
+    let origin = Point3D::new(0.0, 10.0, 0.0);
+    let up = Point3D::new(0.0, 1.0, 0.0);
+
+    let mut emitter = make_emitter("");
+    emitter.update(0.05, Some((&origin, &up)));
+    assert_eq!(emitter.get_particles().len(), 0);
+    emitter.update(0.5, Some((&origin, &up)));
+    assert_eq!(emitter.get_particles().len(), 5);
+    emitter.update(0.5, None);
+    assert_eq!(emitter.get_particles().len(), 5);
+    emitter.update(0.5, None);
+    assert_eq!(emitter.get_particles().len(), 0);
+    assert!(!emitter.is_finished());
+
+    let mut emitter = make_emitter("burst: 8\nmax_particles: 10\n");
+    emitter.update(0.0, None);
+    assert_eq!(emitter.get_particles().len(), 0);
+    emitter.update(0.0, Some((&origin, &up)));
+    assert_eq!(emitter.get_particles().len(), 8);
+    emitter.update(0.5, Some((&origin, &up)));
+    assert_eq!(emitter.get_particles().len(), 10);
+}
+
+#[test]
+fn carry_fractions_of_particles_over() {
+    let origin = Point3D::new(0.0, 10.0, 0.0);
+    let up = Point3D::new(0.0, 1.0, 0.0);
+    let mut emitter = make_emitter("");
+
+    // Each step spawns 0.625 of a particle, so the counts grow unevenly
+    let mut counts = Vec::new();
+    for _ in 0..8 {
+        emitter.update(0.0625, Some((&origin, &up)));
+        counts.push(emitter.get_particles().len());
+    }
+    assert_eq!(counts, vec![0, 1, 1, 2, 3, 3, 4, 5]);
+}
+
+#[test]
+fn burst_only_once() {
+    let origin = Point3D::new(0.0, 10.0, 0.0);
+    let up = Point3D::new(0.0, 1.0, 0.0);
+    let mut emitter = make_emitter("burst: 4\n");
+    emitter.update(0.0, Some((&origin, &up)));
+    assert_eq!(emitter.get_particles().len(), 4);
+
+    // Losing and finding the anchor again does not repeat the burst
+    emitter.update(0.0, None);
+    emitter.update(0.0, Some((&origin, &up)));
+    assert_eq!(emitter.get_particles().len(), 4);
+
+    // The burst is spawned even if the emitter stops at once
+    let mut emitter = make_emitter("burst: 3\nduration: 0.0\n");
+    emitter.update(0.5, Some((&origin, &up)));
+    assert_eq!(emitter.get_particles().len(), 3);
+    emitter.update(1.0, Some((&origin, &up)));
+    assert!(emitter.is_finished());
+}
+
+#[test]
+fn refill_up_to_max_particles() {
+    let origin = Point3D::new(0.0, 10.0, 0.0);
+    let up = Point3D::new(0.0, 1.0, 0.0);
+    let mut emitter = make_emitter("burst: 20\nmax_particles: 6\n");
+    emitter.update(0.0, Some((&origin, &up)));
+    assert_eq!(emitter.get_particles().len(), 6);
+    emitter.update(0.5, Some((&origin, &up)));
+    assert_eq!(emitter.get_particles().len(), 6);
+    assert!(emitter.get_particles().iter().all(|particle| particle.age == 0.5));
+
+    // Dead particles make room for new ones
+    emitter.update(0.6, Some((&origin, &up)));
+    assert_eq!(emitter.get_particles().len(), 6);
+    assert!(emitter.get_particles().iter().all(|particle| particle.age == 0.0));
+}
+
+#[test]
+fn stop_emitting_after_duration() {
+    let origin = Point3D::new(0.0, 10.0, 0.0);
+    let up = Point3D::new(0.0, 1.0, 0.0);
+    let mut emitter = make_emitter("duration: 0.3\n");
+    emitter.update(0.5, Some((&origin, &up)));
+    assert_eq!(emitter.get_particles().len(), 3);
+    assert!(!emitter.is_finished());
+    emitter.update(0.6, Some((&origin, &up)));
+    assert_eq!(emitter.get_particles().len(), 3);
+    emitter.update(0.6, Some((&origin, &up)));
+    assert!(emitter.is_finished());
+}
+
+#[test]
+fn move_particles_within_cone() {
+    let origin = Point3D::new(0.0, 10.0, 0.0);
+    let up = Point3D::new(0.0, 1.0, 0.0);
+    let mut emitter = make_emitter("burst: 50\n");
+    emitter.update(0.0, Some((&origin, &up)));
+    for particle in emitter.get_particles() {
+        assert!((particle.velocity.length() - 2.0).abs() < ERROR);
+        assert!(particle.velocity.angle(&up) <= 0.3 + ERROR);
+    }
+
+    emitter.update(0.1, None);
+    for particle in emitter.get_particles() {
+        assert!((Point3D::distance(&particle.position, &origin) - 0.2).abs() < ERROR);
+    }
+}
+
+#[test]
+fn spread_particles_by_seed() {
+    let origin = Point3D::new(0.0, 10.0, 0.0);
+    let up = Point3D::new(0.0, 1.0, 0.0);
+    let spec = load_spec("edgin_around_seeded_emitter", &format!("{}burst: 5\n", EMITTER));
+    let anchor = EmitterAnchor::Point(Point::new(0.0, 0.0));
+    let mut emitters: Vec<ParticleEmitter> = [1, 1, 2]
+        .iter()
+        .map(|seed| ParticleEmitter::new(spec.clone(), anchor.clone(), *seed))
+        .collect();
+    for emitter in emitters.iter_mut() {
+        emitter.update(0.0, Some((&origin, &up)));
+    }
+
+    let velocities: Vec<Vec<(f32, f32, f32)>> = emitters
+        .iter()
+        .map(|emitter| {
+            let particles = emitter.get_particles();
+            particles.iter().map(|p| (p.velocity.x, p.velocity.y, p.velocity.z)).collect()
+        })
+        .collect();
+    assert_eq!(velocities[0], velocities[1]);
+    assert_ne!(velocities[0], velocities[2]);
+}
+
+#[test]
+fn pull_particles_towards_planet() {
+    let origin = Point3D::new(0.0, 10.0, 0.0);
+    let up = Point3D::new(0.0, 1.0, 0.0);
+    let mut falling = make_emitter("burst: 1\ngravity: 10.0\n");
+    let mut rising = make_emitter("burst: 1\ngravity: -10.0\n");
+    falling.update(0.0, Some((&origin, &up)));
+    rising.update(0.0, Some((&origin, &up)));
+    for _ in 0..5 {
+        falling.update(0.1, None);
+        rising.update(0.1, None);
+    }
+    assert!(falling.get_particles()[0].velocity.y < 0.0);
+    assert!(falling.get_particles()[0].position.y < origin.y + 0.5);
+    assert!(rising.get_particles()[0].position.y > origin.y + 1.0);
+}
+
+#[test]
+fn find_hook_of_sprite() {
+    let path = std::env::temp_dir().join("edgin_around_hooks").with_extension("saml");
+    std::fs::write(&path, SAML).unwrap();
+    let parser = Parser::new(&path);
+    std::fs::remove_file(&path).unwrap();
+    let sprite = Sprite::new(0, parser.to_stock());
+
+    assert_eq!(sprite.get_hook_position("torso", 0.0), Some((0.0, 2.0)));
+    assert_eq!(sprite.get_hook_position("hand", 0.0), Some((1.0, 2.0)));
+    assert_eq!(sprite.get_hook_position("tail", 0.0), None);
+}
@@ -0,0 +1,123 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+use std::f32::consts::PI;
+
+use edgin_around_rendering::{
+    renderers::{Path, PathMesh},
+    utils::coordinates::Point,
+};
+
+const RADIUS: f32 = 100.0;
+const ERROR: f32 = 0.001;
+
+fn make_straight_path() -> Path {
+    let start = Point::new(0.5 * PI, 0.0);
+    let end = start.moved_by(10.0, 0.5 * PI, RADIUS);
+    Path::new(vec![start, end], 2.0)
+}
+
+#[test]
+fn split_path_into_pieces() {
+    let mesh = PathMesh::new(&make_straight_path(), RADIUS, 1.0);
+    assert_eq!(mesh.points.len(), 2 * 11);
+    assert_eq!(mesh.triangles.len(), 2 * 10);
+    assert_eq!(mesh.colors.len(), mesh.points.len());
+
+    let (distance, _) = mesh.tex_coords[mesh.tex_coords.len() - 1];
+    assert!((distance - 10.0).abs() < ERROR);
+    for pair in mesh.points.chunks(2) {
+        assert!((Point::great_circle_distance(&pair[0], &pair[1], RADIUS) - 2.0).abs() < ERROR);
+    }
+    for (a, b, c) in mesh.triangles.iter() {
+        let len = mesh.points.len() as u32;
+        assert!(*a < len && *b < len && *c < len);
+    }
+}
+
+#[test]
+fn place_edges_on_both_sides() {
+    let mesh = PathMesh::new(&make_straight_path(), RADIUS, 1.0);
+
+    // The path heads east along the equator, so the left edge lies north of it
+    for (point, (_, side)) in mesh.points.iter().zip(mesh.tex_coords.iter()) {
+        if *side == 0.0 {
+            assert!(point.theta < 0.5 * PI);
+        } else {
+            assert!(point.theta > 0.5 * PI);
+        }
+    }
+}
+
+#[test]
+fn keep_width_in_corners() {
+    let corner = Point::new(0.5 * PI, 0.0);
+    let start = corner.moved_by(5.0, PI, RADIUS);
+    let end = corner.moved_by(5.0, 0.5 * PI, RADIUS);
+    let mesh = PathMesh::new(&Path::new(vec![start, corner.clone(), end], 1.0), RADIUS, 10.0);
+    assert_eq!(mesh.points.len(), 6);
+
+    // In a right angle the edges are moved by the half width divided by cosine of 45 degrees
+    let expected = 0.5 / (0.25 * PI).cos();
+    for point in mesh.points[2..4].iter() {
+        let distance = Point::great_circle_distance(&corner, point, RADIUS);
+        assert!((distance - expected).abs() < ERROR);
+    }
+}
+
+#[test]
+fn limit_widening_in_sharp_corners() {
+    let corner = Point::new(0.5 * PI, 0.0);
+    let start = corner.moved_by(5.0, PI, RADIUS);
+    let end = corner.moved_by(5.0, PI - 0.2, RADIUS);
+    let mesh = PathMesh::new(&Path::new(vec![start, corner.clone(), end], 1.0), RADIUS, 10.0);
+    assert_eq!(mesh.points.len(), 6);
+
+    // Turning back almost completely would move the edges far away, so they are moved only by
+    // the half width times the miter limit
+    for point in mesh.points[2..4].iter() {
+        let distance = Point::great_circle_distance(&corner, point, RADIUS);
+        assert!((distance - 0.5 * 2.0).abs() < ERROR);
+    }
+}
+
+#[test]
+fn measure_distance_along_path() {
+    let corner = Point::new(0.5 * PI, 0.0);
+    let start = corner.moved_by(5.0, PI, RADIUS);
+    let end = corner.moved_by(3.0, 0.5 * PI, RADIUS);
+    let points = vec![start, corner.clone(), corner, end];
+    let mesh = PathMesh::new(&Path::new(points, 1.0), RADIUS, 1.0);
+
+    // Pieces of both segments are one unit long and the repeated corner adds nothing
+    assert_eq!(mesh.points.len(), 2 * (5 + 3 + 1));
+    for (i, pair) in mesh.tex_coords.chunks(2).enumerate() {
+        assert!((pair[0].0 - i as f32).abs() < ERROR);
+        assert_eq!(pair[0].0, pair[1].0);
+        assert_eq!((pair[0].1, pair[1].1), (0.0, 1.0));
+    }
+}
+
+#[test]
+fn blend_colors_along_path() {
+    let mut path = make_straight_path();
+    path.colors = vec![[1.0, 0.0, 0.0, 1.0], [0.0, 0.0, 1.0, 1.0]];
+    let mesh = PathMesh::new(&path, RADIUS, 1.0);
+    assert_eq!(mesh.colors[0], [1.0, 0.0, 0.0, 1.0]);
+    assert_eq!(mesh.colors[mesh.colors.len() - 1], [0.0, 0.0, 1.0, 1.0]);
+    let middle = mesh.colors[10];
+    assert!((middle[0] - 0.5).abs() < ERROR && (middle[2] - 0.5).abs() < ERROR);
+
+    path.colors = vec![[0.0, 1.0, 0.0, 1.0]];
+    assert_eq!(path.get_color(1), [0.0, 1.0, 0.0, 1.0]);
+    path.colors.clear();
+    assert_eq!(path.get_color(0), [1.0, 1.0, 1.0, 1.0]);
+}
+
+#[test]
+fn skip_degenerate_paths() {
+    let point = Point::new(0.5 * PI, 0.0);
+    assert!(PathMesh::new(&Path::new(vec![], 1.0), RADIUS, 1.0).triangles.is_empty());
+    let repeated = Path::new(vec![point.clone(), point.clone()], 1.0);
+    assert!(PathMesh::new(&repeated, RADIUS, 1.0).triangles.is_empty());
+}
@@ -0,0 +1,72 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+use std::path::PathBuf;
+
+use edgin_around_rendering::game::{Filter, Sampling, Wrap, SAMPLING_FILE};
+
+fn make_dir(name: &str, yaml: Option<&str>) -> PathBuf {
+    let dir = std::env::temp_dir().join(name);
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(SAMPLING_FILE);
+    match yaml {
+        Some(yaml) => std::fs::write(&path, yaml).unwrap(),
+        None => {
+            let _ = std::fs::remove_file(&path);
+        }
+    }
+    dir
+}
+
+#[test]
+fn parse_sampling() {
+    let yaml = "filter: nearest\nmipmaps: true\nanisotropy: 4.0\nwrap: clamp_to_edge\n";
+    let dir = make_dir("edgin_around_sampling_full", Some(yaml));
+    let expected = Sampling {
+        filter: Filter::Nearest,
+        mipmaps: true,
+        anisotropy: Some(4.0),
+        wrap: Wrap::ClampToEdge,
+    };
+    assert_eq!(Sampling::load(&dir), Some(expected));
+
+    let dir = make_dir("edgin_around_sampling_mirrored", Some("wrap: mirrored_repeat\n"));
+    assert_eq!(Sampling::load(&dir).unwrap().wrap, Wrap::MirroredRepeat);
+}
+
+#[test]
+fn default_missing_fields() {
+    let default = Sampling::default();
+    assert_eq!(default.filter, Filter::Linear);
+    assert!(!default.mipmaps);
+    assert_eq!(default.anisotropy, None);
+    assert_eq!(default.wrap, Wrap::Repeat);
+
+    let dir = make_dir("edgin_around_sampling_partial", Some("mipmaps: true\n"));
+    assert_eq!(Sampling::load(&dir), Some(Sampling { mipmaps: true, ..Sampling::default() }));
+
+    let dir = make_dir("edgin_around_sampling_empty", Some("{}\n"));
+    assert_eq!(Sampling::load(&dir), Some(Sampling::default()));
+}
+
+#[test]
+fn fall_back_without_sidecar() {
+    let fallback = Sampling { filter: Filter::Nearest, ..Sampling::default() };
+    let dir = make_dir("edgin_around_sampling_missing", None);
+    assert_eq!(Sampling::load(&dir), None);
+    assert_eq!(Sampling::load_or(&dir, &fallback), fallback);
+
+    let dir = make_dir("edgin_around_sampling_override", Some("wrap: clamp_to_edge\n"));
+    let sampling = Sampling::load_or(&dir, &fallback);
+    assert_eq!(sampling, Sampling { wrap: Wrap::ClampToEdge, ..Sampling::default() });
+}
+
+#[test]
+fn ignore_invalid_sidecar() {
+    let fallback = Sampling { mipmaps: true, ..Sampling::default() };
+    for yaml in ["filter: bilinear\n", "wrap: 3\n", "anisotropy: strong\n", "mipmaps: [\n"].iter() {
+        let dir = make_dir("edgin_around_sampling_invalid", Some(yaml));
+        assert_eq!(Sampling::load(&dir), None);
+        assert_eq!(Sampling::load_or(&dir, &fallback), fallback);
+    }
+}
@@ -4,10 +4,33 @@
 use std::f32::consts::PI;
 
 use edgin_around_rendering::{
-    game::{Actor, Scene},
+    game::{
+        Actor, ActorAppearance, ActorChange, ElevationFunction, Heightmap, Scene, SceneDescription,
+    },
     utils::coordinates::Point,
 };
 
+const WORLD_DESCRIPTION: &str = "
+radius: 500.0
+sea_level: 0.5
+hero_id: 1
+terrain:
+  - variant: continents
+  - variant: bump
+    origin: { theta: 1.5, phi: 0.5 }
+    range: 100.0
+    amplitude: -2.0
+    falloff: linear
+  - variant:
+      noise: { seed: 42, octaves: 5, frequency: 4.0, amplitude: 20.0 }
+actors:
+  - id: 2
+    entity_name: axe
+  - id: 1
+    entity_name: pirate
+    position: { theta: 1.5, phi: 0.0 }
+";
+
 #[test]
 fn find_closest_actors() {
     let mut scene = Scene::new();
@@ -25,3 +48,167 @@ fn find_closest_actors() {
 
     assert_eq!(actual, expected);
 }
+
+#[test]
+fn load_and_save_world_description() {
+    let description: SceneDescription = serde_yaml::from_str(WORLD_DESCRIPTION).unwrap();
+    let scene = Scene::from_description(description).unwrap();
+    let points = [Point::new(1.5, 0.5), Point::new(1.5, 0.52), Point::new(0.3, 4.0)];
+
+    assert!(scene.is_ready());
+    assert_eq!(scene.get_radius(), 500.0);
+    assert_eq!(scene.get_sea_radius(), 500.5);
+    assert_eq!(scene.get_hero_id(), 1);
+    assert_eq!(scene.get_focus_point().theta, 1.5);
+    assert!(!scene.get_actor(2).unwrap().is_visible());
+
+    for extension in &["yaml", "json"] {
+        let path = std::env::temp_dir().join(format!("edgin_around_world.{}", extension));
+        scene.save_to(&path).unwrap();
+        let loaded = Scene::load_from(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let actor_ids: Vec<_> = loaded.to_description().actors.iter().map(|a| a.get_id()).collect();
+        assert_eq!(actor_ids, vec![1, 2]);
+        assert_eq!(loaded.get_sea_radius(), scene.get_sea_radius());
+        for point in points.iter() {
+            assert_eq!(loaded.get_elevation(point), scene.get_elevation(point));
+        }
+    }
+}
+
+#[test]
+fn load_heightmap_relative_to_world_file() {
+    let dir = std::env::temp_dir().join("edgin_around_heightmap_world");
+    std::fs::create_dir_all(&dir).unwrap();
+    let world_path = dir.join("world.yaml");
+    let world = "
+radius: 500.0
+hero_id: 1
+terrain:
+  - variant:
+      heightmap: { path: height.png, min_height: 10.0, max_height: 30.0 }
+";
+    std::fs::write(&world_path, world).unwrap();
+
+    // The heightmap is missing
+    assert!(Scene::load_from(&world_path).is_err());
+
+    let file = std::fs::File::create(dir.join("height.png")).unwrap();
+    let mut encoder = png::Encoder::new(file, 2, 1);
+    encoder.set_color(png::ColorType::Grayscale);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.write_header().unwrap().write_image_data(&[255, 255]).unwrap();
+
+    let scene = Scene::load_from(&world_path).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(scene.get_elevation(&Point::new(0.5 * PI, 0.0)), 530.0);
+}
+
+#[test]
+fn refuse_to_save_heightmap_without_path() {
+    let mut elevation = ElevationFunction::new(500.0);
+    elevation.add_heightmap(Heightmap::new(1, 1, vec![0.5]), 0.0, 10.0);
+    let mut scene = Scene::new();
+    scene.configure(1, elevation);
+
+    let path = std::env::temp_dir().join("edgin_around_unstorable_world.yaml");
+    assert!(scene.save_to(&path).is_err());
+    assert!(!path.exists());
+}
+
+#[test]
+fn reconfiguring_scene_advances_generation() {
+    let mut scene = Scene::new();
+    assert!(!scene.is_ready());
+    assert_eq!(scene.get_generation(), 0);
+
+    scene.configure(1, ElevationFunction::new(500.0));
+    assert!(scene.is_ready());
+    let generation = scene.get_generation();
+    assert_ne!(generation, 0);
+    assert!(!scene.should_reset_renderers());
+
+    scene.reconfigure(2, ElevationFunction::new(800.0), true);
+    assert_ne!(scene.get_generation(), generation);
+    assert_eq!(scene.get_radius(), 800.0);
+    assert_eq!(scene.get_hero_id(), 2);
+    assert!(scene.should_reset_renderers());
+
+    // Another scene configured the same number of times still differs
+    let mut other = Scene::new();
+    other.configure(1, ElevationFunction::new(500.0));
+    other.configure(1, ElevationFunction::new(500.0));
+    assert_ne!(other.get_generation(), scene.get_generation());
+}
+
+#[test]
+fn record_actor_changes() {
+    let mut scene = Scene::new();
+    scene.create_actors(&vec![
+        Actor::new(1, "pirate".to_string(), Some(Point::new(0.1, 0.1))),
+        Actor::new(2, "axe".to_string(), None),
+    ]);
+    let revision = scene.get_actor_revision();
+
+    scene.set_actor_position(2, Point::new(0.2, 0.2));
+    scene.set_actor_entity_name(1, "warrior".to_string());
+    scene.set_actor_entity_name(2, "axe".to_string());
+    scene.delete_actors(&vec![1, 3]);
+    scene.create_actors(&vec![Actor::new(2, "sword".to_string(), None)]);
+
+    let expected = vec![
+        ActorChange::Moved(2),
+        ActorChange::Renamed(1),
+        ActorChange::Removed(1),
+        ActorChange::Renamed(2),
+    ];
+    assert_eq!(scene.get_actor_changes_since(revision).unwrap(), expected.as_slice());
+    assert_eq!(scene.get_actor_revision(), revision + expected.len());
+    assert_eq!(scene.get_actor_changes_since(scene.get_actor_revision()).unwrap(), &[]);
+
+    // Old changes are dropped when too many of them accumulate
+    for i in 0..2000 {
+        scene.set_actor_entity_name(2, format!("sword{}", i));
+    }
+    assert!(scene.get_actor_changes_since(revision).is_none());
+    assert!(scene.get_actor_changes_since(scene.get_actor_revision() - 10).is_some());
+
+    // Many moves are recorded once until the changes are read
+    scene.create_actors(&vec![Actor::new(4, "pirate".to_string(), Some(Point::new(0.4, 0.4)))]);
+    let revision = scene.get_actor_revision();
+    for _ in 0..2000 {
+        scene.move_actor_by(2, 1.0, 0.0);
+        scene.update_actor_position(2, Point::new(0.3, 0.3), 1.0);
+        scene.set_actor_position(4, Point::new(0.5, 0.5));
+    }
+    let expected = vec![ActorChange::Moved(2), ActorChange::Moved(4)];
+    assert_eq!(scene.get_actor_changes_since(revision).unwrap(), expected.as_slice());
+    scene.create_actors(&vec![Actor::new(4, "pirate".to_string(), None)]);
+    scene.move_actor_by(2, 1.0, 0.0);
+    let expected = vec![ActorChange::Moved(4), ActorChange::Moved(2)];
+    assert_eq!(scene.get_actor_changes_since(revision + 2).unwrap(), expected.as_slice());
+    assert_ne!(Scene::new().get_id(), scene.get_id());
+}
+
+#[test]
+fn save_actor_appearance() {
+    let description: SceneDescription = serde_yaml::from_str(WORLD_DESCRIPTION).unwrap();
+    let mut scene = Scene::from_description(description).unwrap();
+    let appearance = ActorAppearance::new(2.0, [1.0, 0.5, 0.5, 1.0], 0.8, 3.0);
+    scene.set_actor_appearance(1, appearance.clone());
+
+    let yaml = serde_yaml::to_string(&scene.to_description()).unwrap();
+    assert_eq!(yaml.matches("appearance").count(), 1);
+
+    let loaded = Scene::from_description(serde_yaml::from_str(&yaml).unwrap()).unwrap();
+    assert_eq!(loaded.get_actor(1).unwrap().get_appearance(), &appearance);
+    assert!(loaded.get_actor(2).unwrap().get_appearance().is_default());
+
+    // Missing fields take default values
+    let actor: Actor =
+        serde_yaml::from_str("{ id: 3, entity_name: bird, appearance: { altitude: 5.0 } }")
+            .unwrap();
+    let expected = ActorAppearance { altitude: 5.0, ..ActorAppearance::default() };
+    assert_eq!(actor.get_appearance(), &expected);
+}
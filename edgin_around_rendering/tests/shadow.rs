@@ -0,0 +1,90 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+use std::f32::consts::PI;
+
+use edgin_around_rendering::{
+    animations::Parser,
+    game::{Actor, EditKind, ElevationFunction, Scene, TerrainEdit},
+    renderers::ShadowRenderer,
+    utils::{
+        coordinates::{Point, Point3D},
+        geometry::Vector3D,
+    },
+};
+
+const SAML: &str = "
+sources: []
+skeletons:
+  - { id: body, scale: 1.0, bones: [] }
+animations:
+  - { id: stand, skeleton_id: body, length: 1.0, keys: {}, muscles: [] }
+selection:
+  - { variant: default, action: idle, animation: stand }
+";
+
+fn load_shadow_radius(name: &str, saml: &str) -> f32 {
+    let path = std::env::temp_dir().join(name).with_extension("saml");
+    std::fs::write(&path, saml).unwrap();
+    let parser = Parser::new(&path);
+    std::fs::remove_file(&path).unwrap();
+    parser.to_stock().get_shadow_radius()
+}
+
+#[test]
+fn read_shadow_radius_from_saml() {
+    assert_eq!(load_shadow_radius("edgin_around_default_shadow", SAML), 0.3);
+    let saml = format!("shadow_radius: 0.8\n{}", SAML);
+    assert_eq!(load_shadow_radius("edgin_around_custom_shadow", &saml), 0.8);
+}
+
+#[test]
+fn override_shadow_radius_of_actor() {
+    let mut scene = Scene::new();
+    scene.create_actors(&vec![Actor::new(1, "pirate".to_string(), Some(Point::new(0.1, 0.1)))]);
+    assert_eq!(scene.get_actor(1).unwrap().get_appearance().shadow_radius, None);
+
+    scene.set_actor_shadow_radius(1, Some(0.0));
+    assert_eq!(scene.get_actor(1).unwrap().get_appearance().shadow_radius, Some(0.0));
+    assert!(!scene.get_actor(1).unwrap().get_appearance().is_default());
+
+    scene.set_actor_shadow_radius(1, None);
+    assert!(scene.get_actor(1).unwrap().get_appearance().is_default());
+}
+
+#[test]
+fn estimate_ground_normal() {
+    let center = Point::new(0.5 * PI, 0.0);
+    let mut scene = Scene::new();
+    scene.configure(1, ElevationFunction::new(500.0));
+
+    // On a smooth sphere the normal points away from its center
+    let normal = scene.get_ground_normal(&center);
+    assert!((normal.z - 1.0).abs() < 1e-3);
+    assert!(normal.x.abs() < 1e-3 && normal.y.abs() < 1e-3);
+
+    // On the eastern slope of a hill the normal leans to the east
+    let mut elevation = ElevationFunction::new(500.0);
+    elevation.add_edit(TerrainEdit::new(EditKind::Raise, center.clone(), 20.0, 5.0));
+    scene.configure(1, elevation);
+    let slope = center.moved_by(10.0, 0.5 * PI, 500.0);
+    let normal = scene.get_ground_normal(&slope);
+    let up = slope.to_point3d(1.0);
+    assert!((normal.length() - 1.0).abs() < 1e-3);
+    assert!(normal.x > up.x + 0.05);
+}
+
+#[test]
+fn place_shadow_in_ground_plane() {
+    let center = Point3D::new(0.0, 0.0, 500.0);
+    let normal = Point3D::new(0.0, 0.6, 0.8);
+    let model = ShadowRenderer::prepare_model(&center, &normal, 2.0);
+
+    for (x, y) in [(1.0, 0.0), (0.0, 1.0), (-1.0, 0.0)] {
+        let corner = model.clone() * Vector3D::new(x, y, 0.0);
+        let corner = Point3D::new(corner.get_x(), corner.get_y(), corner.get_z());
+        let offset = &corner - &center;
+        assert!(offset.dot(&normal) > 0.0 && offset.dot(&normal) < 0.1);
+        assert!((offset.length() - 2.0).abs() < 0.01);
+    }
+}
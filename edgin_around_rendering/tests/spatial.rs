@@ -0,0 +1,113 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+use std::{cmp::Ordering, f32::consts::PI};
+
+use edgin_around_rendering::{
+    game::{Actor, ElevationFunction, Scene},
+    utils::{coordinates::Point, ids::ActorId},
+};
+
+const RADIUS: f32 = 500.0;
+const NUM_ACTORS: usize = 2000;
+
+/// Simple deterministic generator of numbers in `[0, 1)`.
+struct Random(u64);
+
+impl Random {
+    fn next(&mut self) -> f32 {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (self.0 >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    fn next_point(&mut self) -> Point {
+        Point::new((1.0 - 2.0 * self.next()).acos(), 2.0 * PI * self.next())
+    }
+}
+
+fn make_scene(random: &mut Random) -> Scene {
+    let mut scene = Scene::new();
+    scene.configure(0, ElevationFunction::new(RADIUS));
+    let mut actors: Vec<Actor> = (0..NUM_ACTORS)
+        .map(|id| Actor::new(id, "actor".to_string(), Some(random.next_point())))
+        .collect();
+
+    // Some actors close to the poles and the antimeridian and some hidden
+    actors.push(Actor::new(NUM_ACTORS, "actor".to_string(), Some(Point::new(0.0, 0.0))));
+    actors.push(Actor::new(NUM_ACTORS + 1, "actor".to_string(), Some(Point::new(PI, 1.0))));
+    actors.push(Actor::new(NUM_ACTORS + 2, "actor".to_string(), Some(Point::new(1.0, PI))));
+    actors.push(Actor::new(NUM_ACTORS + 3, "actor".to_string(), Some(Point::new(1.0, -PI))));
+    actors.push(Actor::new(NUM_ACTORS + 4, "actor".to_string(), None));
+    scene.create_actors(&actors);
+    scene
+}
+
+/// Returns IDs of all visible actors with their distances, the closest first.
+fn brute_force<D>(scene: &Scene, distance: D) -> Vec<(ActorId, f32)>
+where
+    D: Fn(&Point) -> f32,
+{
+    let mut actors: Vec<(ActorId, f32)> = scene
+        .get_actors()
+        .filter_map(|a| a.get_position().map(|p| (a.get_id(), distance(p))))
+        .collect();
+    actors.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal).then(a.0.cmp(&b.0)));
+    actors
+}
+
+fn ids(actors: Vec<(ActorId, f32)>) -> Vec<ActorId> {
+    actors.iter().map(|a| a.0).collect()
+}
+
+#[test]
+fn proximity_queries_match_brute_force() {
+    let mut random = Random(7);
+    let mut scene = make_scene(&mut random);
+
+    // Move some actors, with and without updating the index
+    for id in 0..100 {
+        scene.set_actor_position(id, random.next_point());
+        scene.move_actor_by(id + 100, 50.0, random.next() * PI);
+        let point = random.next_point();
+        scene.get_actor_mut(id + 200).unwrap().set_position(point);
+    }
+    scene.hide_actors(&vec![300, 301]);
+    scene.delete_actors(&vec![302]);
+
+    let mut centers: Vec<Point> = (0..20).map(|_| random.next_point()).collect();
+    centers.extend(vec![Point::new(0.0, 0.0), Point::new(PI, 0.0), Point::new(1.0, PI)]);
+
+    for center in centers.iter() {
+        let by_chord = brute_force(&scene, |p| Point::distance(center, p, RADIUS));
+        let by_arc = brute_force(&scene, |p| Point::great_circle_distance(center, p, RADIUS));
+
+        for max_distance in [0.0, 5.0, 30.0, 150.0, 700.0, 2000.0].iter() {
+            let mut expected = by_chord.clone();
+            expected.retain(|a| a.1 < *max_distance);
+            assert_eq!(scene.find_closest_actors(center, *max_distance), ids(expected));
+
+            let mut expected = by_arc.clone();
+            expected.retain(|a| a.1 <= *max_distance);
+            assert_eq!(scene.find_actors_in_cap(center, *max_distance), ids(expected));
+        }
+
+        for count in [0, 1, 7, 50, NUM_ACTORS + 10].iter() {
+            let mut expected = by_chord.clone();
+            expected.truncate(*count);
+            assert_eq!(scene.find_nearest_actors(center, *count), ids(expected));
+        }
+    }
+}
+
+#[test]
+fn reconfiguring_scene_reindexes_actors() {
+    let mut random = Random(11);
+    let mut scene = make_scene(&mut random);
+    scene.configure(0, ElevationFunction::new(2.0 * RADIUS));
+
+    let center = Point::new(1.0, 1.0);
+    let mut expected = brute_force(&scene, |p| Point::distance(&center, p, 2.0 * RADIUS));
+    expected.retain(|a| a.1 < 100.0);
+    assert!(!expected.is_empty());
+    assert_eq!(scene.find_closest_actors(&center, 100.0), ids(expected));
+}
@@ -0,0 +1,64 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+use edgin_around_rendering::{
+    game::{Actor, ActorChange, Scene, Transition, TransitionEffect, TransitionKind},
+    utils::coordinates::Point,
+};
+
+#[test]
+fn compute_transition_effects() {
+    let fade = Transition::new(TransitionKind::from_name("fade").unwrap(), 2.0);
+    assert_eq!(fade.progress(-1.0), 0.0);
+    assert_eq!(fade.progress(0.5), 0.25);
+    assert_eq!(fade.progress(3.0), 1.0);
+    assert_eq!(Transition::new(TransitionKind::Fade, 0.0).progress(0.0), 1.0);
+    assert!(TransitionKind::from_name("explode").is_none());
+
+    let expected = TransitionEffect { opacity: 0.25, scale: 1.0, dissolve: 0.0 };
+    assert_eq!(fade.effect(0.25), expected);
+
+    let scale = Transition::new(TransitionKind::Scale, 1.0);
+    let expected = TransitionEffect { opacity: 1.0, scale: 0.5, dissolve: 0.0 };
+    assert_eq!(scale.effect(0.5), expected);
+
+    let dissolve = Transition::new(TransitionKind::Dissolve, 1.0);
+    let expected = TransitionEffect { opacity: 1.0, scale: 1.0, dissolve: 0.75 };
+    assert_eq!(dissolve.effect(0.25), expected);
+    assert_eq!(dissolve.effect(1.5), TransitionEffect::default());
+}
+
+#[test]
+fn record_transitions_with_actor_changes() {
+    let fade = Transition::new(TransitionKind::Fade, 0.5);
+    let dissolve = Transition::new(TransitionKind::Dissolve, 1.0);
+
+    let mut scene = Scene::new();
+    let revision = scene.get_actor_revision();
+    scene.create_actors_with_transition(
+        &vec![Actor::new(1, "pirate".to_string(), Some(Point::new(0.1, 0.1)))],
+        fade.clone(),
+    );
+    scene.create_actors(&vec![Actor::new(2, "pirate".to_string(), Some(Point::new(0.2, 0.2)))]);
+    scene.hide_actors_with_transition(&vec![1], fade.clone());
+    scene.delete_actors_with_transition(&vec![2, 3], dissolve.clone());
+
+    let expected = vec![
+        ActorChange::Added(1),
+        ActorChange::Added(2),
+        ActorChange::Moved(1),
+        ActorChange::Removed(2),
+    ];
+    assert_eq!(scene.get_actor_changes_since(revision).unwrap(), expected.as_slice());
+    assert_eq!(scene.get_actor_transition(revision), Some(&fade));
+    assert_eq!(scene.get_actor_transition(revision + 1), None);
+    assert_eq!(scene.get_actor_transition(revision + 2), Some(&fade));
+    assert_eq!(scene.get_actor_transition(revision + 3), Some(&dissolve));
+    assert_eq!(scene.get_actor_transition(revision + 4), None);
+
+    // Transitions are dropped together with old changes
+    for i in 0..2000 {
+        scene.set_actor_entity_name(1, format!("pirate{}", i));
+    }
+    assert_eq!(scene.get_actor_transition(revision), None);
+}
@@ -1,6 +1,9 @@
 from typing import List, Optional, Set, Tuple
 
 ActorId = int
+DecalId = int
+EmitterId = int
+PathId = int
 Radian = float
 Zoom = float
 
@@ -10,6 +13,23 @@ class Point:
     def __init__(self, theta: float, phi: float) -> None: ...
     def get_theta(self) -> float: ...
     def get_phi(self) -> float: ...
+    def moved_by(self, distance: float, bearing: float, radius: float) -> Point: ...
+    @staticmethod
+    def great_circle_distance(point1: Point, point2: Point, radius: float) -> float: ...
+    @staticmethod
+    def initial_bearing(point1: Point, point2: Point) -> float: ...
+    @staticmethod
+    def final_bearing(point1: Point, point2: Point) -> float: ...
+    @staticmethod
+    def interpolate(point1: Point, point2: Point, fraction: float) -> Point: ...
+    @staticmethod
+    def midpoint(point1: Point, point2: Point) -> Point: ...
+    @staticmethod
+    def destination(start: Point, distance: float, bearing: float, radius: float) -> Point: ...
+    @staticmethod
+    def cross_track_distance(point: Point, start: Point, end: Point, radius: float) -> float: ...
+    @staticmethod
+    def polygon_area(vertices: List[Point], radius: float) -> float: ...
 
 class Actor:
     def __init__(self, id: ActorId, entity_name: str, position: Optional[Point]) -> None: ...
@@ -17,18 +37,99 @@ class Actor:
 class ElevationFunction:
     def __init__(self, radius: float) -> None: ...
     def add_terrain(self, name: str, theta: float, phi: float) -> None: ...
+    def add_local_terrain(
+        self,
+        name: str,
+        theta: float,
+        phi: float,
+        range: float,
+        amplitude: float,
+        falloff: str,
+    ) -> None: ...
+    def set_sea_level(self, sea_level: float) -> None: ...
+    def add_noise_terrain(
+        self,
+        seed: int,
+        octaves: int,
+        frequency: float,
+        amplitude: float,
+    ) -> None: ...
+    def add_heightmap_terrain(self, path: str, min_height: float, max_height: float) -> None: ...
 
 class Scene:
     def __init__(self) -> None: ...
+    @staticmethod
+    def load_from(path: str) -> Scene: ...
+    def save_to(self, path: str) -> None: ...
     def configure(self, hero_actor_id: ActorId, elevation: ElevationFunction) -> None: ...
+    def reconfigure(
+        self, hero_actor_id: ActorId, elevation: ElevationFunction, reset_renderers: bool
+    ) -> None: ...
     def get_hero_id(self) -> ActorId: ...
     def create_actors(self, actors: List[Actor]) -> None: ...
     def delete_actors(self, actor_ids: List[ActorId]) -> None: ...
     def hide_actors(self, actor_ids: List[ActorId]) -> None: ...
+    def create_actors_with_transition(
+        self, actors: List[Actor], kind: str, duration: float
+    ) -> None: ...
+    def delete_actors_with_transition(
+        self, actor_ids: List[ActorId], kind: str, duration: float
+    ) -> None: ...
+    def hide_actors_with_transition(
+        self, actor_ids: List[ActorId], kind: str, duration: float
+    ) -> None: ...
     def get_radius(self) -> float: ...
     def find_closest_actors(self, position: Point, max_distance: float) -> List[ActorId]: ...
+    def find_nearest_actors(self, position: Point, count: int) -> List[ActorId]: ...
+    def find_actors_in_cap(self, center: Point, distance: float) -> List[ActorId]: ...
+    def raise_terrain(self, center: Point, range: float, amount: float) -> None: ...
+    def lower_terrain(self, center: Point, range: float, amount: float) -> None: ...
+    def flatten_terrain(self, center: Point, range: float, height: float) -> None: ...
     def get_actor_position(self, actor_id: ActorId) -> Optional[Point]: ...
     def set_actor_position(self, actor_id: ActorId, position: Point) -> None: ...
+    def update_actor_position(self, actor_id: ActorId, position: Point, timestamp: float) -> None: ...
+    def set_time(self, time: float) -> None: ...
+    def set_motion_smoothing(
+        self, delay: float, max_extrapolation: float, snap_distance: float
+    ) -> None: ...
+    def get_rendered_position(self, actor_id: ActorId) -> Optional[Point]: ...
+    def set_actor_path(self, actor_id: ActorId, waypoints: List[Point], speed: float) -> None: ...
+    def is_actor_moving(self, actor_id: ActorId) -> bool: ...
+    def take_arrived_actors(self) -> List[ActorId]: ...
+    def set_actor_entity_name(self, actor_id: ActorId, entity_name: str) -> None: ...
+    def set_actor_bearing(self, actor_id: ActorId, bearing: Optional[Radian]) -> None: ...
+    def get_actor_bearing(self, actor_id: ActorId) -> Optional[Radian]: ...
+    def set_actor_appearance(
+        self,
+        actor_id: ActorId,
+        scale: float,
+        tint: Tuple[float, float, float, float],
+        opacity: float,
+        altitude: float,
+    ) -> None: ...
+    def set_actor_shadow_radius(self, actor_id: ActorId, shadow_radius: Optional[float]) -> None: ...
+    def get_actor_shadow_radius(self, actor_id: ActorId) -> Optional[float]: ...
+
+class Decal:
+    @staticmethod
+    def circle(center: Point, radius: float) -> Decal: ...
+    @staticmethod
+    def ring(center: Point, inner_radius: float, outer_radius: float) -> Decal: ...
+    @staticmethod
+    def sector(center: Point, radius: float, bearing: Radian, angle: Radian) -> Decal: ...
+    @staticmethod
+    def polygon(vertices: List[Point]) -> Decal: ...
+    def set_color(self, color: Tuple[float, float, float, float]) -> None: ...
+    def set_texture(self, texture: Optional[str]) -> None: ...
+    def set_animation(self, kind: str, speed: float) -> None: ...
+
+class Path:
+    def __init__(self, points: List[Point], width: float) -> None: ...
+    def set_color(self, color: Tuple[float, float, float, float]) -> None: ...
+    def set_colors(self, colors: List[Tuple[float, float, float, float]]) -> None: ...
+    def set_dash(self, dash: Optional[Tuple[float, float]]) -> None: ...
+    def set_texture(self, texture: Optional[str]) -> None: ...
+    def set_scroll_speed(self, scroll_speed: float) -> None: ...
 
 class PreviewExpositor:
     def __init__(
@@ -48,14 +149,49 @@ class WorldExpositor:
     def resize(self, width: int, height: int) -> None: ...
     def render(self, scene: Scene) -> None: ...
     def get_bearing(self) -> Radian: ...
-    def get_highlighted_actor_id(self) -> Optional[ActorId]: ...
-    def set_highlighted_actor_id(self, ActorId) -> None: ...
-    def remove_highlight(self) -> None: ...
+    def get_highlighted_actor_ids(self) -> List[ActorId]: ...
+    def highlight_actor(
+        self, actor_id: ActorId, style: str, color: Tuple[float, float, float, float]
+    ) -> None: ...
+    def unhighlight_actor(self, actor_id: ActorId) -> None: ...
+    def clear_highlights(self) -> None: ...
+    def get_hovered_actor_id(self) -> Optional[ActorId]: ...
+    def set_hovered_actor_id(self, actor_id: Optional[ActorId]) -> None: ...
+    def set_hover_highlight(self, style: str, color: Tuple[float, float, float, float]) -> None: ...
     def zoom_by(self, zoom: Zoom) -> None: ...
     def rotate_by(self, angle: Radian) -> None: ...
     def tilt_by(self, angle: Radian) -> None: ...
+    def set_lighting(
+        self, azimuth: Radian, altitude: Radian, ambient: float, diffuse: float
+    ) -> None: ...
+    def set_movement_actions(self, walk_action: str, idle_action: str) -> None: ...
     def create_renderers(self, actors: List[Actor]) -> None: ...
     def delete_renderers(self, ids: List[ActorId]) -> None: ...
+    def create_renderers_with_transition(
+        self, actors: List[Actor], kind: str, duration: float
+    ) -> None: ...
+    def delete_renderers_with_transition(
+        self, ids: List[ActorId], kind: str, duration: float
+    ) -> None: ...
+    def add_decal(self, decal_id: DecalId, decal: Decal) -> None: ...
+    def remove_decal(self, decal_id: DecalId) -> None: ...
+    def clear_decals(self) -> None: ...
+    def get_decal_ids(self) -> List[DecalId]: ...
+    def add_path(self, path_id: PathId, path: Path) -> None: ...
+    def remove_path(self, path_id: PathId) -> None: ...
+    def clear_paths(self) -> None: ...
+    def get_path_ids(self) -> List[PathId]: ...
+    def add_emitter_at_point(self, emitter_id: EmitterId, name: str, point: Point) -> None: ...
+    def add_emitter_on_actor(
+        self,
+        emitter_id: EmitterId,
+        name: str,
+        actor_id: ActorId,
+        hook_name: Optional[str],
+    ) -> None: ...
+    def remove_emitter(self, emitter_id: EmitterId) -> None: ...
+    def clear_emitters(self) -> None: ...
+    def get_emitter_ids(self) -> List[EmitterId]: ...
     def play_animation(self, actor_id: ActorId, animation_name: str) -> None: ...
     def select_variant(self, actor_id: ActorId, variant_name: str) -> None: ...
     def attach_actor(
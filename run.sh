@@ -8,6 +8,7 @@ function usage() {
     echo ' - build or build_all - builds everything'
     echo ' - tests - runs unit tests'
     echo ' - format - runs `rust-fmt` code formatter'
+    echo ' - convert_textures DIR [BLOCK] - converts PNG files in DIR to ASTC KTX2 files using `toktx`'
     echo ' - clean - removes all build artifacts'
 }
 
@@ -34,6 +35,22 @@ function run_format() {
     cargo fmt --all
 }
 
+function run_convert_textures() {
+    local resource_dir=$1
+    local block_size=${2:-4x4}
+
+    if [[ -z "$resource_dir" ]]; then
+        echo 'Please give a resource directory.'
+        return 1
+    fi
+
+    find "$resource_dir" -name '*.png' | while read -r png_path; do
+        local ktx2_path="${png_path%.png}.ktx2"
+        echo "Converting $png_path"
+        toktx --t2 --encode astc --astc_blk_d "$block_size" --genmipmap "$ktx2_path" "$png_path"
+    done
+}
+
 function run_clean() {
     rm -rf target
 }
@@ -62,6 +79,9 @@ if (( $# > 0 )); then
         'format')
             run_format
             ;;
+        'convert_textures')
+            run_convert_textures "$@"
+            ;;
         'clean')
             run_clean
             ;;